@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::io;
 use std::thread;
 use std::time::Duration;
 
@@ -6,24 +7,30 @@ use clipboard::{ClipboardContext, ClipboardProvider};
 use colored::*;
 use log::*;
 
-use crate::util::{display, hierarchy, vault_path};
+use crate::util::{display, hierarchy, time, vault_path};
 use libknox::*;
 
 pub(crate) fn list(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let path = args.value_of("path");
+  let tags: Vec<&str> = args.values_of("tag").map(Iterator::collect).unwrap_or_default();
   let context = VaultContext::open(vault_path()?)?;
   if context.vault.get_index().is_empty() {
     info!("the vault is empty");
     return Ok(());
   }
 
-  let list = hierarchy::build(&context.vault, path);
+  if args.is_present("sort") || DATE_FILTERS.iter().any(|filter| args.is_present(filter)) {
+    return list_by_date(&context, path, &tags, args);
+  }
+
+  let list = hierarchy::build(&context.vault, path, &tags);
 
   match list {
     Some(list) => {
       println!("🔒 Knox");
       hierarchy::print(&mut vec![], &list);
     }
+    None if !tags.is_empty() => info!("no entry carries the requested tags"),
     None => {
       return Err(VaultError::throw(&format!("the directory {} was not found in the vault", path.unwrap().bold())));
     }
@@ -32,19 +39,86 @@ pub(crate) fn list(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   Ok(())
 }
 
+const DATE_FILTERS: [&str; 4] = ["created_before", "created_after", "modified_before", "modified_after"];
+
+fn list_by_date(context: &VaultContext, prefix: Option<&str>, tags: &[&str], args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let filter = |name: &str| args.value_of(name).map(time::parse_date).transpose();
+  let (created_before, created_after) = (filter("created_before")?, filter("created_after")?);
+  let (modified_before, modified_after) = (filter("modified_before")?, filter("modified_after")?);
+
+  let mut entries = Vec::new();
+
+  for path in context.vault.get_index().keys() {
+    if let Some(prefix) = prefix {
+      if !path.starts_with(&format!("{}/", prefix)) {
+        continue;
+      }
+    }
+    if !context.vault.is_tagged(path, tags) {
+      continue;
+    }
+
+    let entry = context.read_entry(path)?;
+
+    if !in_range(entry.get_created(), created_after, created_before) || !in_range(entry.get_modified(), modified_after, modified_before) {
+      continue;
+    }
+
+    entries.push((path, entry));
+  }
+
+  match args.value_of("sort") {
+    Some("created") => entries.sort_by_key(|(path, entry)| (entry.get_created(), path.to_string())),
+    Some("modified") => entries.sort_by_key(|(path, entry)| (entry.get_modified(), path.to_string())),
+    _ => entries.sort_by_key(|(path, _)| path.to_string()),
+  }
+
+  if entries.is_empty() {
+    info!("no entry matches the provided filters");
+    return Ok(());
+  }
+
+  println!("🔒 Knox");
+
+  for (path, entry) in entries {
+    println!("   {} {} {}", "»".bold(), path, format!("({})", display::stamp(&entry)).dimmed());
+  }
+
+  Ok(())
+}
+
+fn in_range(timestamp: u64, after: Option<u64>, before: Option<u64>) -> bool {
+  after.unwrap_or(0) <= timestamp && timestamp < before.unwrap_or(u64::MAX)
+}
+
 pub(crate) fn search(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let context = VaultContext::open(vault_path()?)?;
-  let term = args.value_of("term").unwrap();
+  let term = args.value_of("term").unwrap_or_default();
+  let tags: Vec<&str> = args.values_of("tag").map(Iterator::collect).unwrap_or_default();
 
-  let list = hierarchy::search(&context.vault, term);
+  if term.is_empty() && tags.is_empty() {
+    return Err(VaultError::throw("please provide a term or a tag to search for"));
+  }
+
+  let list = hierarchy::search(&context.vault, term, &tags);
 
   match list.len() {
     0 => info!("the term you searched for was not found in the vault"),
     _ => {
-      println!("🔒 Knox (search for '{}'):", term.dimmed());
+      let query = Some(term)
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("'{}'", term.dimmed()))
+        .into_iter()
+        .chain(tags.iter().map(|tag| format!("#{}", tag.dimmed())))
+        .collect::<Vec<String>>();
+
+      println!("🔒 Knox (search for {}):", query.join(", "));
 
       for path in list {
-        println!("   {} {}", "»".bold(), path.replace(term, &format!("{}", term.blue().bold())));
+        match term {
+          "" => println!("   {} {}", "»".bold(), path),
+          _ => println!("   {} {}", "»".bold(), path.replace(term, &format!("{}", term.blue().bold()))),
+        }
       }
     }
   }
@@ -52,6 +126,8 @@ pub(crate) fn search(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   Ok(())
 }
 
+const EXPIRY_WARNING: u64 = 14 * 86400;
+
 pub(crate) fn show(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let vault = VaultContext::open(vault_path()?)?;
   let path = args.value_of("path").unwrap();
@@ -60,13 +136,30 @@ pub(crate) fn show(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let copy = args.is_present("copy");
   let write = args.is_present("write");
 
-  let entry = vault.read_entry(path)?;
+  let (entry, signature) = match args.value_of("rev") {
+    Some(revision) => vault.read_entry_at(revision, path)?,
+    None => vault.read_signed_entry(path)?,
+  };
+
+  for (key, expires) in entry.get_expirations() {
+    if expires > time::now() + EXPIRY_WARNING {
+      continue;
+    }
+
+    match key {
+      Some(key) => warn!("attribute {} of this entry {} ({})", key.bold(), time::expiry(expires), time::format(expires)),
+      None => warn!("this entry {} ({})", time::expiry(expires), time::format(expires)),
+    }
+  }
 
   if copy {
     let name = if let Some(attributes) = args.values_of("attribute") {
       attributes.collect()
     } else {
-      vec!["password"]
+      match entry.find_attribute(AttributeKind::PASSWORD) {
+        Some((name, _)) => vec![name.as_str()],
+        None => vec!["password"],
+      }
     };
 
     if name.len() > 1 {
@@ -74,6 +167,9 @@ pub(crate) fn show(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     }
 
     match entry.get_attributes().get(name[0]) {
+      Some(attribute) if attribute.is_blob() => {
+        return Err(VaultError::throw("attribute is stored as a blob, cannot copy to clipboard"));
+      }
       Some(attribute) => {
         let value = match attribute.value() {
           AttributeValue::String(string) => string,
@@ -101,6 +197,10 @@ pub(crate) fn show(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     if args.is_present("stdout") {
       match args.value_of("attribute") {
         Some(attribute) => match entry.get_attributes().get(attribute) {
+          Some(attribute) if attribute.is_blob() => match args.value_of("rev") {
+            Some(revision) => vault.read_blob_at(revision, attribute, io::stdout())?,
+            None => vault.read_blob(attribute, io::stdout())?,
+          },
           Some(attribute) => match attribute.value() {
             AttributeValue::String(string) => println!("{}", string),
             AttributeValue::Binary(_) => {
@@ -119,12 +219,22 @@ pub(crate) fn show(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
       return Ok(());
     }
 
-    display::write_files(path, &entry, &args.value_of("attribute").map(|attribute| attribute.split(',').collect::<Vec<&str>>()))?;
+    display::write_files(
+      &vault,
+      path,
+      &entry,
+      args.value_of("rev"),
+      &args.value_of("attribute").map(|attribute| attribute.split(',').collect::<Vec<&str>>()),
+    )?;
 
     return Ok(());
   }
 
   display::entry(path, &entry, print);
 
+  if let Some(signature) = display::signature(&vault, signature.as_ref()) {
+    println!("{}", signature);
+  }
+
   Ok(())
 }
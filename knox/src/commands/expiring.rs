@@ -0,0 +1,92 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::{time, vault_path};
+
+pub(crate) fn expiring(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let context = VaultContext::open(vault_path()?)?;
+  let deadline = match args.value_of("within") {
+    Some(within) => time::parse_deadline(within)?,
+    None => time::now(),
+  };
+
+  let paths = context
+    .vault
+    .expiring_before(deadline)
+    .into_iter()
+    .filter(|(path, _)| context.vault.get_index().contains_key(*path))
+    .map(|(path, _)| path)
+    .collect::<Vec<&str>>();
+
+  if paths.is_empty() {
+    info!("no entry has expired or will expire within this period");
+    return Ok(());
+  }
+
+  println!("🔒 Knox (expiring entries):");
+
+  for path in &paths {
+    let entry = context.read_entry(path)?;
+
+    for (key, expires) in entry.get_expirations() {
+      if expires > deadline {
+        continue;
+      }
+
+      let target = match key {
+        Some(key) => format!("{} {} {}", path.bold(), "»".dimmed(), key),
+        None => format!("{}", path.bold()),
+      };
+      let status = match expires <= time::now() {
+        true => time::expiry(expires).red(),
+        false => time::expiry(expires).yellow(),
+      };
+
+      println!("   {} {} {} {}", "»".bold(), target, status, format!("({})", time::format(expires)).dimmed());
+    }
+  }
+
+  Err(VaultError::throw(&format!("{} entries have expired or will expire within this period", paths.len())))
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  use crate::util::time;
+
+  #[test]
+  fn expiring() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("token", "abcdef");
+    entry.set_attribute_expiry("token", Some(time::now() + 10 * 86400)).expect("could not set expiry");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "expiring"]);
+
+    if let ("expiring", Some(args)) = app.subcommand() {
+      assert!(super::expiring(args).is_ok());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "expiring", "--within", "30d"]);
+
+    if let ("expiring", Some(args)) = app.subcommand() {
+      assert!(super::expiring(args).is_err());
+
+      return;
+    }
+
+    panic!("command expiring not triggering");
+  }
+}
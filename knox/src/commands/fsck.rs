@@ -0,0 +1,84 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::vault_path;
+
+pub(crate) fn fsck(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let repair = args.is_present("repair");
+  let mut context = if repair {
+    VaultContext::open_exclusive(vault_path()?)?
+  } else {
+    VaultContext::open(vault_path()?)?
+  };
+
+  let issues = context.check()?;
+
+  if issues.is_empty() {
+    info!("no issue was found in the vault");
+
+    return Ok(());
+  }
+
+  for issue in &issues {
+    println!(" {} {}", "::".red().bold(), issue);
+  }
+
+  let unrepairable = issues.iter().filter(|issue| !issue.is_repairable()).count();
+
+  if !repair {
+    return Err(VaultError::throw(&format!("found {} issue(s) in the vault, run with --repair to fix them", issues.len())));
+  }
+
+  context.repair(&issues)?;
+
+  info!("repaired {} issue(s), orphan packs were moved to _knox.quarantine", issues.len() - unrepairable);
+
+  if unrepairable > 0 {
+    return Err(VaultError::throw(&format!("{} issue(s) cannot be repaired automatically", unrepairable)));
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn fsck() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+    context.add_index("lorem/ipsum", "ab/abcdef");
+    context.write().expect("could not write metadata");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "fsck"]);
+
+    if let ("fsck", Some(args)) = app.subcommand() {
+      assert!(super::fsck(args).is_err());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "fsck", "--repair"]);
+
+    if let ("fsck", Some(args)) = app.subcommand() {
+      assert!(super::fsck(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+      assert!(context.vault.get_index().contains_key("foo/bar"));
+      assert!(!context.vault.get_index().contains_key("lorem/ipsum"));
+
+      return;
+    }
+
+    panic!("command fsck not triggering");
+  }
+}
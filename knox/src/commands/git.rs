@@ -1,14 +1,75 @@
+use std::env;
 use std::error::Error;
+use std::fs;
 
+use colored::*;
 use libknox::*;
 use log::*;
 
-use crate::util::vault_path;
+use crate::util::{display, time, vault_path};
+
+pub(crate) fn clone(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let path = vault_path()?;
+  let url = args.value_of("url").unwrap();
+  let mut config = GitConfig::default();
+
+  config.set_remote(args.value_of("name").unwrap_or_default().to_string());
+  config.set_branch(args.value_of("branch").unwrap_or_default().to_string());
+  config.set_username(args.value_of("username").unwrap_or_default().to_string());
+
+  if let Some(key) = args.value_of("key_file") {
+    config.set_auth(GitAuth::KEY_FILE);
+    config.set_credential(key_file(key)?);
+  }
+
+  if let Some(variable) = args.value_of("token_env") {
+    config.set_auth(GitAuth::TOKEN_VARIABLE);
+    config.set_credential(variable.to_string());
+  }
+
+  match VaultContext::clone_remote(url, &path, config)? {
+    Access::Recipient { identity } => {
+      info!("vault cloned to {}", path.bold());
+
+      if let Some(identity) = identity {
+        info!("you can decrypt it as {}", identity.bold());
+      }
+
+      Ok(())
+    }
+
+    Access::NotRecipient { identities } => {
+      if identities.is_empty() {
+        return Err(VaultError::throw(&format!(
+          "vault cloned to {}, but you are not one of its recipients, and no local key was found to ask access with",
+          path
+        )));
+      }
+
+      println!("You are not one of the recipients of this vault. Send one of these identities to one of its members, so they can add it:");
+
+      for identity in &identities {
+        println!("  - {}", identity);
+      }
+
+      println!();
+
+      Err(VaultError::throw(&format!("vault cloned to {}, but you are not one of its recipients", path)))
+    }
+  }
+}
 
 pub(crate) fn set_remote(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
-  let vault = VaultContext::open(vault_path()?)?;
+  let mut vault = VaultContext::open_exclusive(vault_path()?)?;
   let origin = args.value_of("url").unwrap();
 
+  if let Some(name) = args.value_of("name") {
+    let mut config = vault.get_local_config();
+    config.mut_git().set_remote(name.to_string());
+
+    vault.write_local_config(config)?;
+  }
+
   vault.set_git_origin(origin)?;
 
   info!("git remote URL set to '{}'", origin);
@@ -16,12 +77,382 @@ pub(crate) fn set_remote(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>>
   Ok(())
 }
 
-pub(crate) fn push(_args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+pub(crate) fn set_branch(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut vault = VaultContext::open_exclusive(vault_path()?)?;
+  let branch = args.value_of("branch").unwrap();
+  let mut config = vault.get_local_config();
+
+  config.mut_git().set_branch(branch.to_string());
+  vault.write_local_config(config)?;
+
+  info!("git branch set to '{}'", branch);
+
+  Ok(())
+}
+
+pub(crate) fn set_auth(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut vault = VaultContext::open_exclusive(vault_path()?)?;
+  let mut config = vault.get_local_config();
+  let git = config.mut_git();
+  let credential = args.value_of("credential").unwrap_or_default();
+
+  git.set_auth(match args.value_of("method").unwrap() {
+    "key-file" => GitAuth::KEY_FILE,
+    "token-env" => GitAuth::TOKEN_VARIABLE,
+    "token-entry" => GitAuth::TOKEN_ENTRY,
+    _ => GitAuth::AGENT,
+  });
+  git.set_credential(match git.get_auth() {
+    GitAuth::KEY_FILE => key_file(credential)?,
+    _ => credential.to_string(),
+  });
+  git.set_username(args.value_of("username").unwrap_or_default().to_string());
+  git.set_attribute(args.value_of("attribute").unwrap_or_default().to_string());
+
+  vault.write_local_config(config)?;
+
+  info!("git authentication method set to {}", args.value_of("method").unwrap().bold());
+
+  Ok(())
+}
+
+// The key is read whenever the remote is reached, from any directory, so its
+// path is stored as an absolute path.
+fn key_file(path: &str) -> Result<String, Box<dyn Error>> {
+  let key = fs::canonicalize(path).map_err(|err| VaultError::throw(&format!("could not read SSH key {}: {}", path, err)))?;
+
+  if !key.is_file() {
+    return Err(VaultError::throw(&format!("SSH key {} is not a file", path)));
+  }
+
+  Ok(key.display().to_string())
+}
+
+pub(crate) fn push(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let vault = VaultContext::open(vault_path()?)?;
 
-  vault.push()?;
+  if args.is_present("force") {
+    vault.force_push()?;
+  } else {
+    vault.push()?;
+  }
 
   info!("vault modifications successfully pushed upstream");
 
   Ok(())
 }
+
+pub(crate) fn pull(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut vault = VaultContext::open_exclusive(vault_path()?)?;
+  let (changes, conflicts) = vault.pull(resolution(args))?;
+
+  report(&changes, &conflicts);
+
+  info!("upstream modifications successfully pulled");
+
+  Ok(())
+}
+
+pub(crate) fn sync(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut vault = VaultContext::open_exclusive(vault_path()?)?;
+  let (changes, conflicts) = vault.sync(resolution(args))?;
+
+  report(&changes, &conflicts);
+
+  info!("vault successfully synchronized with upstream");
+
+  Ok(())
+}
+
+fn resolution(args: &clap::ArgMatches) -> Resolution {
+  if args.is_present("theirs") {
+    Resolution::Theirs
+  } else {
+    Resolution::Ours
+  }
+}
+
+fn report(changes: &[Change], conflicts: &[Conflict]) {
+  for change in changes {
+    println!(" {} {}", "::".blue().bold(), change);
+  }
+
+  for conflict in conflicts {
+    println!(" {} {}", "::".red().bold(), conflict);
+  }
+
+  if !conflicts.is_empty() {
+    warn!("found {} conflict(s) with upstream, please review the entries listed above", conflicts.len());
+  }
+}
+
+pub(crate) fn purge(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let dry_run = args.is_present("dry_run");
+  let before = args.value_of("before").map(time::parse_date).transpose()?;
+
+  if !dry_run && !args.is_present("yes") {
+    return Err(VaultError::throw(
+      "purging rewrites the whole git history of the vault, and every other copy of it will have to be cloned again, run with --yes to proceed",
+    ));
+  }
+
+  let mut vault = VaultContext::open_exclusive(vault_path()?)?;
+  let purge = vault.purge_history(before, dry_run)?;
+
+  for file in &purge.files {
+    println!(" {} {}", "::".blue().bold(), file);
+  }
+
+  if purge.commits == 0 && purge.squashed == 0 {
+    info!("no deleted file was found in the history of the vault");
+    return Ok(());
+  }
+
+  if dry_run {
+    info!(
+      "{} file(s) would be purged from {} commit(s), and {} commit(s) squashed, run without --dry-run to proceed",
+      purge.files.len(),
+      purge.commits,
+      purge.squashed
+    );
+    return Ok(());
+  }
+
+  info!("{} file(s) purged from {} commit(s), and {} commit(s) squashed", purge.files.len(), purge.commits, purge.squashed);
+
+  if let Some(backup) = purge.backup {
+    warn!("the previous history was kept under {}, and still contains the purged files", backup.bold());
+    warn!("the metadata of previous commits still lists the paths of the purged entries");
+    warn!("run 'knox git push --force' to replace the history of the remote repository");
+    warn!(
+      "once done, run 'git update-ref -d {}', 'git reflog expire --expire=now --all' and 'git gc --prune=now' in the vault to discard it",
+      backup
+    );
+  }
+
+  Ok(())
+}
+
+pub(crate) fn merge_driver(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let ours = args.value_of("ours").unwrap();
+  let base = fs::read(args.value_of("base").unwrap())?;
+  let theirs = fs::read(args.value_of("theirs").unwrap())?;
+
+  // Git runs merge drivers from the root of the working tree, which is the
+  // vault being merged.
+  let (metadata, conflicts) = VaultContext::merge_metadata(env::current_dir()?, &base, &fs::read(ours)?, &theirs)?;
+
+  fs::write(ours, metadata)?;
+
+  for conflict in &conflicts {
+    println!(" {} {}", "::".red().bold(), conflict);
+  }
+
+  if !conflicts.is_empty() {
+    return Err(VaultError::throw(&format!(
+      "found {} conflict(s) in the vault metadata, our side was kept, please check the merged entries",
+      conflicts.len()
+    )));
+  }
+
+  info!("vault metadata successfully merged");
+
+  Ok(())
+}
+
+pub(crate) fn textconv(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let pack = fs::read(args.value_of("file").unwrap())?;
+
+  // Git runs textconv drivers from the root of the working tree, and
+  // compares their output as plain text.
+  let entry = VaultContext::decrypt_pack(env::current_dir()?, &pack)?;
+
+  colored::control::set_override(false);
+
+  println!("{}", display::text(&entry));
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn merge_driver() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    let metadata = tmp.path().join("_knox.meta");
+    let (base, ours, theirs) = (tmp.path().join("base"), tmp.path().join("ours"), tmp.path().join("theirs"));
+
+    fs::copy(&metadata, &base).expect("could not copy metadata");
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    fs::copy(&metadata, &ours).expect("could not copy metadata");
+    context.delete_entry("foo").expect("could not delete entry");
+    context.write_entry("bar", &Entry::default()).expect("could not write entry");
+    fs::copy(&metadata, &theirs).expect("could not copy metadata");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "merge-driver", base.to_str().unwrap(), ours.to_str().unwrap(), theirs.to_str().unwrap()]);
+
+    if let ("git", Some(args)) = app.subcommand() {
+      if let ("merge-driver", Some(args)) = args.subcommand() {
+        assert!(super::merge_driver(args).is_ok());
+
+        fs::copy(&ours, &metadata).expect("could not copy metadata");
+        drop(context);
+
+        let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+        assert!(context.vault.get_index().contains_key("foo"));
+        assert!(context.vault.get_index().contains_key("bar"));
+
+        return;
+      }
+    }
+
+    panic!("command git merge-driver not triggering");
+  }
+
+  #[test]
+  fn textconv() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+
+    let pack = tmp.path().join(&context.vault.get_index()["foo/bar"]);
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "textconv", pack.to_str().unwrap()]);
+
+    if let ("git", Some(args)) = app.subcommand() {
+      if let ("textconv", Some(args)) = args.subcommand() {
+        assert!(super::textconv(args).is_ok());
+
+        return;
+      }
+    }
+
+    panic!("command git textconv not triggering");
+  }
+
+  #[test]
+  fn purge() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+    context.delete_entry("foo").expect("could not delete entry");
+    context.commit("Deleted entry.").expect("could not commit changes");
+    drop(context);
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "purge"]);
+
+    if let ("git", Some(args)) = app.subcommand() {
+      if let ("purge", Some(args)) = args.subcommand() {
+        assert!(super::purge(args).is_err());
+
+        let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "purge", "--yes"]);
+
+        if let ("git", Some(args)) = app.subcommand() {
+          if let ("purge", Some(args)) = args.subcommand() {
+            assert!(super::purge(args).is_ok());
+            assert_eq!(fs::read_dir(tmp.path().join(".git/refs/knox/backup")).expect("could not read references").count(), 1);
+
+            return;
+          }
+        }
+      }
+    }
+
+    panic!("command git purge not triggering");
+  }
+
+  #[test]
+  fn clone() {
+    let (upstream, local) = (spec::setup(), spec::setup());
+    let context = crate::spec::get_test_vault(upstream.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "clone", upstream.path().to_str().unwrap(), "--branch", "master"]);
+
+    if let ("git", Some(args)) = app.subcommand() {
+      if let ("clone", Some(args)) = args.subcommand() {
+        assert!(super::clone(args).is_ok());
+
+        let clone = VaultContext::open(local.path()).expect("could not open vault");
+
+        assert_eq!(clone.get_local_config().get_git().get_branch(), "master");
+
+        return;
+      }
+    }
+
+    panic!("command git clone not triggering");
+  }
+
+  #[test]
+  fn set_auth() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "auth", "token-env", "FORGE_TOKEN", "--username", "knox"]);
+
+    if let ("git", Some(args)) = app.subcommand() {
+      if let ("auth", Some(args)) = args.subcommand() {
+        assert!(super::set_auth(args).is_ok());
+
+        let context = VaultContext::open(tmp.path()).expect("could not open vault");
+        let config = context.get_local_config();
+
+        assert_eq!(config.get_git().get_auth(), GitAuth::TOKEN_VARIABLE);
+        assert_eq!(config.get_git().get_credential(), "FORGE_TOKEN");
+        assert_eq!(config.get_git().get_username(), "knox");
+
+        return;
+      }
+    }
+
+    panic!("command git auth not triggering");
+  }
+
+  #[test]
+  fn set_auth_key_file() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let key = tmp.path().join("id_knox");
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "git", "auth", "key-file", key.to_str().unwrap()]);
+
+    if let ("git", Some(args)) = app.subcommand() {
+      if let ("auth", Some(args)) = args.subcommand() {
+        assert!(super::set_auth(args).is_err());
+
+        fs::write(&key, "").expect("could not write key");
+
+        assert!(super::set_auth(args).is_ok());
+
+        let context = VaultContext::open(tmp.path()).expect("could not open vault");
+        let config = context.get_local_config();
+
+        assert_eq!(config.get_git().get_auth(), GitAuth::KEY_FILE);
+        assert_eq!(config.get_git().get_credential(), fs::canonicalize(&key).unwrap().to_str().unwrap());
+
+        return;
+      }
+    }
+
+    panic!("command git auth not triggering");
+  }
+}
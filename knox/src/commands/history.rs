@@ -0,0 +1,288 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::{display, time, vault_path};
+
+pub(crate) fn history(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let path = args.value_of("path").unwrap();
+  let attribute = args.value_of("attribute");
+  let print = args.is_present("print");
+
+  if let Some(revision) = args.value_of("restore") {
+    let attribute = attribute.ok_or_else(|| VaultError::throw("an attribute is required to restore a previous value"))?;
+    let revision = revision.parse::<usize>().map_err(|_| VaultError::throw("the revision to restore should be a number"))?;
+
+    return restore(path, attribute, revision);
+  }
+
+  let context = VaultContext::open(vault_path()?)?;
+  let entry = context.read_entry(path)?;
+
+  let mut keys = match attribute {
+    Some(attribute) => vec![attribute.to_string()],
+    None => entry.get_history().keys().cloned().collect(),
+  };
+
+  keys.sort();
+
+  if keys.iter().all(|key| entry.get_revisions(key).is_empty()) {
+    info!("no previous value was recorded for this entry");
+    return Ok(());
+  }
+
+  display::header(path);
+
+  for key in keys {
+    let revisions = entry.get_revisions(&key);
+
+    if revisions.is_empty() {
+      continue;
+    }
+
+    println!("  {}", key.bold());
+
+    for (index, revision) in revisions.iter().enumerate() {
+      let author = match revision.get_author() {
+        "" => String::new(),
+        author => format!(" by {}", author),
+      };
+
+      println!(
+        "  {: >4} = {} {}",
+        (index + 1).to_string().bold(),
+        display::value(revision.get_attribute(), print),
+        format!("(replaced {}{})", time::format(revision.get_timestamp()), author).dimmed()
+      );
+    }
+  }
+
+  Ok(())
+}
+
+pub(crate) fn log(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let path = args.value_of("path");
+  let context = VaultContext::open(vault_path()?)?;
+  let log = context.log(path)?;
+
+  if log.is_empty() {
+    info!("no commit modified this entry");
+    return Ok(());
+  }
+
+  for entry in log {
+    println!(
+      "{} {} {}",
+      entry.commit[..8].bold(),
+      entry.change,
+      format!("({}, {} by {})", entry.summary, time::format(entry.timestamp), entry.author).dimmed()
+    );
+  }
+
+  Ok(())
+}
+
+pub(crate) fn restore_entry(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let path = args.value_of("path").unwrap();
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+
+  let revision = context.restore_entry(path, args.value_of("rev"))?;
+
+  info!("entry {} was restored from revision {}", path.bold(), revision);
+
+  context.commit("Restored entry.")?;
+
+  Ok(())
+}
+
+pub(crate) fn diff(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let path = args.value_of("path").unwrap();
+  let print = args.is_present("print");
+  let context = VaultContext::open(vault_path()?)?;
+
+  let differences = context.diff_entry(path, args.value_of("from"), args.value_of("to"))?;
+
+  if differences.is_empty() {
+    info!("no difference was found between both versions of this entry");
+    return Ok(());
+  }
+
+  display::header(path);
+
+  for difference in differences {
+    match difference {
+      Difference::Added { name, attribute } => println!("  {} {} = {}", "+".green().bold(), name.bold(), display::value(&attribute, print)),
+      Difference::Removed { name, attribute } => println!("  {} {} = {}", "-".red().bold(), name.bold(), display::value(&attribute, print)),
+      Difference::Changed { name, before, after } if (before.confidential || after.confidential) && !print => {
+        println!("  {} {} = {}", "~".yellow().bold(), name.bold(), "changed".yellow())
+      }
+      Difference::Changed { name, before, after } => println!(
+        "  {} {} = {} {} {}",
+        "~".yellow().bold(),
+        name.bold(),
+        display::value(&before, print),
+        "->".dimmed(),
+        display::value(&after, print)
+      ),
+      Difference::Totp { before: None, .. } => println!("  {} {}", "+".green().bold(), "@totp".bold()),
+      Difference::Totp { after: None, .. } => println!("  {} {}", "-".red().bold(), "@totp".bold()),
+      Difference::Totp { .. } => println!("  {} {} = {}", "~".yellow().bold(), "@totp".bold(), "changed".yellow()),
+    }
+  }
+
+  Ok(())
+}
+
+fn restore(path: &str, attribute: &str, revision: usize) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let previous = context.read_entry(path)?;
+  let mut entry = previous.clone();
+
+  entry.restore_attribute(attribute, revision)?;
+
+  context.update_entry(path, &previous, &entry)?;
+
+  info!("attribute {} of {} was restored to revision {}", attribute.bold(), path.bold(), revision);
+
+  context.commit("Restored attribute.")?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn history() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_confidential_attribute("password", "first");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    entry.add_confidential_attribute("password", "second");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "history", "foo/bar"]);
+
+    if let ("history", Some(args)) = app.subcommand() {
+      assert!(super::history(args).is_ok());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "history", "foo/bar", "--restore", "1"]);
+
+    if let ("history", Some(args)) = app.subcommand() {
+      assert!(super::history(args).is_err());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "history", "foo/bar", "password", "--restore", "1"]);
+
+    if let ("history", Some(args)) = app.subcommand() {
+      assert!(super::history(args).is_ok());
+
+      let entry = VaultContext::open(tmp.path()).expect("could not open vault").read_entry("foo/bar").expect("could not read entry");
+
+      assert_eq!(entry.get_attributes().get("password").expect("could not get attribute").get_value(), "first");
+      assert_eq!(entry.get_revisions("password")[0].get_attribute().get_value(), "second");
+
+      return;
+    }
+
+    panic!("command history not triggering");
+  }
+
+  #[test]
+  fn log() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "log", "foo/bar"]);
+
+    if let ("log", Some(args)) = app.subcommand() {
+      assert!(super::log(args).is_ok());
+
+      let app = App::from_yaml(yml).get_matches_from(vec!["", "show", "foo/bar", "--rev", "HEAD"]);
+
+      if let ("show", Some(args)) = app.subcommand() {
+        assert!(crate::commands::display::show(args).is_ok());
+
+        return;
+      }
+    }
+
+    panic!("command log not triggering");
+  }
+
+  #[test]
+  fn restore_entry() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+    context.delete_entry("foo/bar").expect("could not delete entry");
+    context.commit("Deleted entry.").expect("could not commit changes");
+    drop(context);
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "restore", "foo/bar"]);
+
+    if let ("restore", Some(args)) = app.subcommand() {
+      assert!(super::restore_entry(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+      assert!(context.vault.get_index().contains_key("foo/bar"));
+
+      return;
+    }
+
+    panic!("command restore not triggering");
+  }
+
+  #[test]
+  fn diff() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.add_confidential_attribute("password", "first");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    entry.add_confidential_attribute("password", "second");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "diff", "foo/bar", "--from", "HEAD~1"]);
+
+    if let ("diff", Some(args)) = app.subcommand() {
+      assert!(super::diff(args).is_ok());
+
+      let app = App::from_yaml(yml).get_matches_from(vec!["", "diff", "lorem"]);
+
+      if let ("diff", Some(args)) = app.subcommand() {
+        assert!(super::diff(args).is_err());
+
+        return;
+      }
+    }
+
+    panic!("command diff not triggering");
+  }
+}
@@ -8,50 +8,49 @@ use log::*;
 use crate::util::vault_path;
 
 pub(crate) fn add(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
-  let mut context = VaultContext::open(vault_path()?)?;
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
   let identity = args.value_of("identity").unwrap();
   let force = args.is_present("force");
 
   let exists = context.vault.get_identities().contains(&identity.to_string());
 
-  if exists {
-    if !force {
-      return Err(VaultError::throw(
-        "the vault already contains the provided identity, to re-encrypt all entries with this identity, use --force",
-      ));
-    }
-  } else {
-    info!("writing metadata file...");
-
-    context.add_identity(identity);
-    context.write()?;
+  context.backend().check_recipient(identity)?;
+
+  if exists && !force {
+    return Err(VaultError::throw(
+      "the vault already contains the provided identity, to re-encrypt all entries with this identity, use --force",
+    ));
   }
 
-  if !exists || force {
-    let index = context.vault.get_index().clone();
-    let progress = ProgressBar::new(context.vault.get_index().len() as u64);
+  let index = context.vault.get_index().clone();
+  let mut transaction = context.transaction();
 
-    for path in index.keys() {
-      let entry = context.read_entry(&path)?;
+  if !exists {
+    transaction.add_identity(identity);
+  }
 
-      context.write_entry(path, &entry)?;
+  let progress = ProgressBar::new(index.len() as u64);
 
-      progress.println(format!(" {} re-encrypting entry {}", "::".bold().blue(), path.bold()));
-      progress.inc(1);
-    }
+  for path in index.keys() {
+    transaction.reencrypt_entry(path)?;
 
-    progress.finish();
+    progress.println(format!(" {} re-encrypting entry {}", "::".bold().blue(), path.bold()));
+    progress.inc(1);
   }
 
-  info!("identity added successfully");
+  progress.finish();
+
+  info!("writing metadata file...");
 
-  context.commit("Added identity.")?;
+  transaction.commit("Added identity.")?;
+
+  info!("identity added successfully");
 
   Ok(())
 }
 
 pub(crate) fn delete(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
-  let mut context = VaultContext::open(vault_path()?)?;
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
   let identity = args.value_of("identity").unwrap();
 
   let exists = context.vault.get_identities().contains(&identity.to_string());
@@ -60,18 +59,15 @@ pub(crate) fn delete(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     return Err(VaultError::throw("the vault does not contain the provided identity"));
   }
 
-  info!("writing metadata file...");
+  let index = context.vault.get_index().clone();
+  let mut transaction = context.transaction();
 
-  context.remove_identity(&identity);
-  context.write()?;
+  transaction.remove_identity(&identity);
 
-  let index = context.vault.get_index().clone();
-  let progress = ProgressBar::new(context.vault.get_index().len() as u64);
+  let progress = ProgressBar::new(index.len() as u64);
 
   for path in index.keys() {
-    let entry = context.read_entry(&path)?;
-
-    context.write_entry(path, &entry)?;
+    transaction.reencrypt_entry(path)?;
 
     progress.println(format!(" {} re-encrypting entry {}", "::".blue().bold(), path.bold()));
     progress.inc(1);
@@ -79,9 +75,11 @@ pub(crate) fn delete(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
 
   progress.finish();
 
-  info!("identity deleted successfully");
+  info!("writing metadata file...");
 
-  context.commit("Removed identity.")?;
+  transaction.commit("Removed identity.")?;
+
+  info!("identity deleted successfully");
 
   Ok(())
 }
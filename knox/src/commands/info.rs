@@ -10,6 +10,9 @@ pub(crate) fn info(_args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let context = VaultContext::open(&path)?;
 
   println!("Vault path: {}", path.bold());
+  println!("Storage format: version {}", context.vault.get_version().to_string().bold());
+  println!("Backend: {}", context.vault.get_backend().name().bold());
+  println!("Generation: {}", context.vault.get_generation().to_string().bold());
 
   println!("\nIdentities:");
   for id in context.vault.get_identities() {
@@ -18,5 +21,14 @@ pub(crate) fn info(_args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
 
   println!("\nNumber of entries: {}", context.vault.get_index().len().to_string().bold());
 
+  let tags = context.vault.count_tags();
+
+  if !tags.is_empty() {
+    println!("\nTags:");
+    for (tag, count) in tags {
+      println!("  - {} ({} entries)", tag, count);
+    }
+  }
+
   Ok(())
 }
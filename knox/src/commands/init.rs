@@ -5,13 +5,25 @@ use log::*;
 
 use libknox::*;
 
-use crate::util::vault_path;
+use crate::util::{passphrase, vault_path};
 
 pub(crate) fn init(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let path = vault_path()?;
-  let identities: Vec<String> = args.values_of("identity").unwrap().map(std::string::ToString::to_string).collect();
 
-  let context = VaultContext::create(&path, &identities)?;
+  let mut context = if args.is_present("passphrase") {
+    VaultContext::create_with_passphrase(&path, &passphrase::read_new()?)?
+  } else {
+    let identities: Vec<String> = args.values_of("identity").unwrap().map(std::string::ToString::to_string).collect();
+    let kind = BackendKind::parse(args.value_of("backend").unwrap_or("gpg"))?;
+    let backend = backend::open(kind, &path)?;
+
+    for identity in &identities {
+      backend.check_recipient(identity)?;
+    }
+
+    VaultContext::create_with_backend(&path, kind, &identities)?
+  };
+
   context.write()?;
 
   info!("vault initialized successfully at {}", path.bold());
@@ -51,4 +63,48 @@ mod tests {
 
     panic!("command init not triggering");
   }
+
+  #[test]
+  fn init_age() {
+    let tmp = spec::setup_age();
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "init", "--no-git", "--backend", "age", spec::AGE_RECIPIENT]);
+
+    if let ("init", Some(args)) = app.subcommand() {
+      assert!(super::init(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not get vault metadata");
+
+      assert_eq!(context.vault.get_backend(), BackendKind::AGE);
+      assert_eq!(context.vault.get_identities(), &[spec::AGE_RECIPIENT.to_string()]);
+
+      return;
+    }
+
+    panic!("command init not triggering");
+  }
+
+  #[test]
+  fn init_passphrase() {
+    let tmp = spec::setup();
+    std::env::set_var("KNOX_PASSPHRASE", "correct horse battery staple");
+    libknox::set_passphrase_provider(crate::util::passphrase::read);
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "init", "--no-git", "--passphrase"]);
+
+    if let ("init", Some(args)) = app.subcommand() {
+      assert!(super::init(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not get vault metadata");
+
+      assert_eq!(context.vault.get_backend(), BackendKind::PASSPHRASE);
+      assert!(context.vault.get_identities().is_empty());
+
+      return;
+    }
+
+    panic!("command init not triggering");
+  }
 }
@@ -0,0 +1,79 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::vault_path;
+
+pub(crate) fn migrate(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let dry_run = args.is_present("dry_run");
+  let migrations = context.pending_migrations();
+
+  if migrations.is_empty() {
+    info!("the vault already uses the latest storage format (version {})", VAULT_VERSION);
+
+    return Ok(());
+  }
+
+  for migration in migrations {
+    if dry_run {
+      println!(" {} version {}: {}", "::".blue().bold(), migration.version.to_string().bold(), migration.description);
+
+      continue;
+    }
+
+    context.migrate(migration)?;
+
+    info!("vault migrated to version {} ({})", migration.version.to_string().bold(), migration.description);
+  }
+
+  if dry_run {
+    info!("the migrations above would be applied, run without --dry-run to proceed");
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn migrate() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.vault.set_version(0);
+    context.write().expect("could not write metadata");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "migrate", "--dry-run"]);
+
+    if let ("migrate", Some(args)) = app.subcommand() {
+      assert!(super::migrate(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+      assert_eq!(context.vault.get_version(), 0);
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "migrate"]);
+
+    if let ("migrate", Some(args)) = app.subcommand() {
+      assert!(super::migrate(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+      assert_eq!(context.vault.get_version(), VAULT_VERSION);
+
+      return;
+    }
+
+    panic!("command migrate not triggering");
+  }
+}
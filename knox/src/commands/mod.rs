@@ -1,9 +1,16 @@
 pub(crate) mod delete;
 pub(crate) mod display;
+pub(crate) mod expiring;
+pub(crate) mod fsck;
 pub(crate) mod git;
+pub(crate) mod history;
 pub(crate) mod identities;
 pub(crate) mod info;
 pub(crate) mod init;
+pub(crate) mod migrate;
 pub(crate) mod pwned;
+pub(crate) mod rollback;
+pub(crate) mod signers;
+pub(crate) mod template;
 pub(crate) mod totp;
 pub(crate) mod write;
@@ -0,0 +1,93 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::vault_path;
+
+pub(crate) fn accept(_args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let generation = VaultContext::accept_rollback(vault_path()?)?;
+
+  info!("the vault metadata was accepted at generation {}", generation.to_string().bold());
+
+  Ok(())
+}
+
+pub(crate) fn policy(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let mut config = context.get_local_config();
+
+  let policy = match args.value_of("policy").unwrap() {
+    "refuse" => RollbackPolicy::REFUSE_ROLLBACK,
+    _ => RollbackPolicy::WARN_ROLLBACK,
+  };
+
+  config.set_rollback_policy(policy);
+  context.write_local_config(config)?;
+
+  info!(
+    "metadata older than the latest version seen on this machine will now be {}",
+    if policy == RollbackPolicy::REFUSE_ROLLBACK { "refused" } else { "reported" }
+  );
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn accept_and_policy() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    let metadata = tmp.path().join("_knox.meta");
+    let previous = fs::read(&metadata).expect("could not read metadata");
+    let mut config = context.get_local_config();
+    config.set_rollback_policy(RollbackPolicy::REFUSE_ROLLBACK);
+
+    context.write_local_config(config).expect("could not write local config");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+    drop(context);
+    fs::write(&metadata, &previous).expect("could not write metadata");
+
+    assert!(VaultContext::open(tmp.path()).is_err());
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "rollback", "accept"]);
+
+    if let ("rollback", Some(args)) = app.subcommand() {
+      if let ("accept", Some(args)) = args.subcommand() {
+        assert!(super::accept(args).is_ok());
+      }
+    }
+
+    let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(context.get_generation_mark(), 1);
+    drop(context);
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "rollback", "policy", "warn"]);
+
+    if let ("rollback", Some(args)) = app.subcommand() {
+      if let ("policy", Some(args)) = args.subcommand() {
+        assert!(super::policy(args).is_ok());
+
+        let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+        assert_eq!(context.get_local_config().get_rollback_policy(), RollbackPolicy::WARN_ROLLBACK);
+
+        return;
+      }
+    }
+
+    panic!("command rollback not triggering");
+  }
+}
@@ -0,0 +1,142 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::vault_path;
+
+pub(crate) fn list(_args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let context = VaultContext::open(vault_path()?)?;
+  let config = context.get_local_config();
+
+  if config.get_trusted_signers().is_empty() {
+    info!("no trusted signer is configured, signatures are not verified");
+    return Ok(());
+  }
+
+  println!("Signature policy: {}", policy_name(config.get_signature_policy()).bold());
+  println!("\nTrusted signers:");
+
+  for signer in config.get_trusted_signers() {
+    println!("  - {}", signer);
+  }
+
+  Ok(())
+}
+
+pub(crate) fn add(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let fingerprint = args.value_of("fingerprint").unwrap().to_uppercase();
+  let mut config = context.get_local_config();
+
+  if config.get_trusted_signers().contains(&fingerprint) {
+    return Err(VaultError::throw("this key is already a trusted signer"));
+  }
+
+  config.mut_trusted_signers().push(fingerprint.clone());
+  context.write_local_config(config)?;
+
+  info!("{} was added to the trusted signers", fingerprint.bold());
+
+  Ok(())
+}
+
+pub(crate) fn delete(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let fingerprint = args.value_of("fingerprint").unwrap().to_uppercase();
+  let mut config = context.get_local_config();
+
+  if !config.get_trusted_signers().contains(&fingerprint) {
+    return Err(VaultError::throw("this key is not a trusted signer"));
+  }
+
+  let signers = config.get_trusted_signers().iter().filter(|signer| **signer != fingerprint).cloned().collect::<Vec<String>>();
+
+  config.set_trusted_signers(signers.into());
+  context.write_local_config(config)?;
+
+  info!("{} was removed from the trusted signers", fingerprint.bold());
+
+  Ok(())
+}
+
+pub(crate) fn policy(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let mut config = context.get_local_config();
+
+  let policy = match args.value_of("policy").unwrap() {
+    "refuse" => SignaturePolicy::REFUSE_UNSIGNED,
+    _ => SignaturePolicy::WARN_UNSIGNED,
+  };
+
+  config.set_signature_policy(policy);
+  context.write_local_config(config)?;
+
+  info!(
+    "data not signed by a trusted signer will now be {}",
+    if policy == SignaturePolicy::REFUSE_UNSIGNED { "refused" } else { "reported" }
+  );
+
+  Ok(())
+}
+
+fn policy_name(policy: SignaturePolicy) -> &'static str {
+  match policy {
+    SignaturePolicy::WARN_UNSIGNED => "warn",
+    SignaturePolicy::REFUSE_UNSIGNED => "refuse",
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn add_and_delete() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "signers", "add", &spec::GPG_FINGERPRINT.to_lowercase()]);
+
+    if let ("signers", Some(args)) = app.subcommand() {
+      if let ("add", Some(args)) = args.subcommand() {
+        assert!(super::add(args).is_ok());
+        assert!(super::add(args).is_err());
+      }
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "signers", "policy", "refuse"]);
+
+    if let ("signers", Some(args)) = app.subcommand() {
+      if let ("policy", Some(args)) = args.subcommand() {
+        assert!(super::policy(args).is_ok());
+      }
+    }
+
+    let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(context.get_local_config().get_trusted_signers(), &[spec::GPG_FINGERPRINT.to_string()]);
+    assert_eq!(context.get_local_config().get_signature_policy(), SignaturePolicy::REFUSE_UNSIGNED);
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "signers", "delete", spec::GPG_FINGERPRINT]);
+
+    if let ("signers", Some(args)) = app.subcommand() {
+      if let ("delete", Some(args)) = args.subcommand() {
+        assert!(super::delete(args).is_ok());
+
+        let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+        assert!(context.get_local_config().get_trusted_signers().is_empty());
+
+        return;
+      }
+    }
+
+    panic!("command signers not triggering");
+  }
+}
@@ -0,0 +1,109 @@
+use std::error::Error;
+
+use colored::*;
+use libknox::*;
+use log::*;
+
+use crate::util::vault_path;
+
+pub(crate) fn list(_args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let context = VaultContext::open(vault_path()?)?;
+
+  for (name, template) in context.list_templates() {
+    println!("{: <16} {}", name.bold(), template.get_description().dimmed());
+  }
+
+  Ok(())
+}
+
+pub(crate) fn show(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let context = VaultContext::open(vault_path()?)?;
+  let name = args.value_of("name").unwrap();
+
+  let template = context.get_template(name).ok_or_else(|| VaultError::throw(&format!("no template named {} was found", name)))?;
+
+  println!("{} {}", name.bold(), template.get_description().dimmed());
+
+  for field in template.get_fields() {
+    println!("  {}", field);
+  }
+
+  Ok(())
+}
+
+pub(crate) fn add(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let name = args.value_of("name").unwrap();
+  let fields = args.values_of("fields").unwrap_or_default().collect::<Vec<&str>>();
+
+  let template = Template::parse(args.value_of("description").unwrap_or_default(), &fields)?;
+
+  context.add_template(name, template);
+  context.write()?;
+
+  info!("template {} was successfully stored in the vault", name.bold());
+
+  context.commit("Added template.")?;
+
+  Ok(())
+}
+
+pub(crate) fn delete(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
+  let name = args.value_of("name").unwrap();
+
+  context.remove_template(name)?;
+  context.write()?;
+
+  info!("template {} was removed from the vault", name.bold());
+
+  context.commit("Removed template.")?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use clap::App;
+
+  use knox_testing::spec;
+  use libknox::*;
+
+  #[test]
+  fn add_and_delete() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "template", "add", "wifi", "ssid", "password:password", "-D", "Wi-Fi network"]);
+
+    if let ("template", Some(args)) = app.subcommand() {
+      if let ("add", Some(args)) = args.subcommand() {
+        assert!(super::add(args).is_ok());
+      }
+    }
+
+    let context = VaultContext::open(tmp.path()).expect("could not open vault");
+    let template = context.get_template("wifi").expect("could not get template");
+
+    assert_eq!(template.get_description(), "Wi-Fi network");
+    assert_eq!(template.get_fields().len(), 2);
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "template", "delete", "wifi"]);
+
+    if let ("template", Some(args)) = app.subcommand() {
+      if let ("delete", Some(args)) = args.subcommand() {
+        assert!(super::delete(args).is_ok());
+        assert!(super::delete(args).is_err());
+
+        let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+        assert!(context.get_template("wifi").is_none());
+
+        return;
+      }
+    }
+
+    panic!("command template not triggering");
+  }
+}
@@ -11,7 +11,7 @@ use crate::util::vault_path;
 pub(crate) fn configure(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let path = args.value_of("path").unwrap();
 
-  let mut context = VaultContext::open(vault_path()?)?;
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
 
   if !context.vault.get_index().contains_key(path) {
     return Err(VaultError::throw("no entry was found at this path"));
@@ -30,7 +30,8 @@ pub(crate) fn configure(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     _ => TotpConfig_Hash::SHA1,
   };
 
-  let mut entry = context.read_entry(&path)?;
+  let previous = context.read_entry(&path)?;
+  let mut entry = previous.clone();
   let has_totp = entry.has_totp();
 
   if !has_totp && !args.is_present("secret") {
@@ -57,7 +58,7 @@ pub(crate) fn configure(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     entry.mut_totp().set_hash(hash);
   }
 
-  context.write_entry(&path, &entry)?;
+  context.update_entry(&path, &previous, &entry)?;
 
   info!("the TOTP configuration for {} has been saved successfully", path.bold());
 
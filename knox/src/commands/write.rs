@@ -15,7 +15,14 @@ pub(crate) fn add(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     return Err(VaultError::throw("an entry already exists at this path"));
   }
 
-  let attributes = util::attributes::build(args)?;
+  let (mut attributes, blobs) = util::attributes::build(args)?;
+
+  if let Some(name) = args.value_of("template") {
+    let template = context.get_template(name).ok_or_else(|| VaultError::throw(&format!("no template named {} was found", name)))?;
+
+    util::attributes::apply_template(&template, &mut attributes, args)?;
+  }
+
   let pwnage = pwned::check_attributes(&attributes);
   let mut abort = false;
 
@@ -41,20 +48,30 @@ pub(crate) fn add(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     return Err(VaultError::throw("some confidential attributes were found in HIBP's database, use --force to override"));
   }
 
-  let entry = Entry { attributes, ..Entry::default() };
+  let mut entry = Entry { attributes, ..Entry::default() };
+
+  for tag in args.values_of("tag").unwrap_or_default() {
+    entry.add_tag(tag)?;
+  }
+
+  util::attributes::apply_expiry(&mut entry, args)?;
 
-  context.write_entry(&path, &entry)?;
+  // The vault is only locked from here on, and the write fails if another
+  // process modified it while the input was being collected.
+  let mut transaction = context.transaction();
+
+  util::attributes::write_blobs(&mut transaction, entry.mut_attributes(), blobs)?;
+  transaction.write_entry(&path, &entry)?;
 
   info!("entry {} was successfully added to the vault", path.bold());
 
-  context.commit("Added entry.")?;
+  transaction.commit("Added entry.")?;
 
   Ok(())
 }
 
 pub(crate) fn edit(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let path = args.value_of("path").unwrap();
-  let attributes = util::attributes::build(args)?;
   let delete_attributes = args.values_of("delete");
 
   let mut context = VaultContext::open(vault_path()?)?;
@@ -63,6 +80,8 @@ pub(crate) fn edit(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     return Err(VaultError::throw("no entry was found at this path"));
   }
 
+  let (mut attributes, blobs) = util::attributes::build(args)?;
+
   let pwnage = pwned::check_attributes(&attributes);
   let mut abort = false;
 
@@ -88,7 +107,16 @@ pub(crate) fn edit(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     return Err(VaultError::throw("some confidential attributes were found in HIBP's database, use --force to override"));
   }
 
-  let mut entry = context.read_entry(&path)?;
+  let previous = context.read_entry(&path)?;
+  let mut entry = previous.clone();
+
+  for (key, attribute) in attributes.iter_mut() {
+    if let (AttributeKind::GENERIC, Some(existing)) = (attribute.get_kind(), entry.get_attributes().get(key)) {
+      attribute.set_kind(existing.get_kind());
+      attribute.confidential |= existing.confidential;
+      attribute.validate().map_err(|err| VaultError::throw(&format!("invalid value for attribute {}: {}", key, err)))?;
+    }
+  }
 
   entry.mut_attributes().extend(attributes);
   if let Some(delete_attributes) = delete_attributes {
@@ -97,11 +125,23 @@ pub(crate) fn edit(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
     }
   }
 
-  context.write_entry(&path, &entry)?;
+  for tag in args.values_of("tag").unwrap_or_default() {
+    entry.add_tag(tag)?;
+  }
+  for tag in args.values_of("untag").unwrap_or_default() {
+    entry.remove_tag(tag);
+  }
+
+  util::attributes::apply_expiry(&mut entry, args)?;
+
+  let mut transaction = context.transaction();
+
+  util::attributes::write_blobs(&mut transaction, entry.mut_attributes(), blobs)?;
+  transaction.update_entry(&path, &previous, &entry)?;
 
   info!("entry {} was successfully edited", path.bold());
 
-  context.commit("Edited entry.")?;
+  transaction.commit("Edited entry.")?;
 
   Ok(())
 }
@@ -109,20 +149,9 @@ pub(crate) fn edit(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
 pub(crate) fn rename(args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
   let source = args.value_of("source").unwrap();
   let destination = args.value_of("destination").unwrap();
-  let mut context = VaultContext::open(vault_path()?)?;
+  let mut context = VaultContext::open_exclusive(vault_path()?)?;
 
-  if !context.vault.get_index().contains_key(source) {
-    return Err(VaultError::throw("no entry was found at this path"));
-  }
-  if context.vault.get_index().contains_key(destination) {
-    return Err(VaultError::throw("an entry already exists at this destination"));
-  }
-
-  let salt = context.vault.get_index().get(source).unwrap().clone();
-
-  context.add_index(destination, &salt);
-  context.remove_index(source);
-  context.write()?;
+  context.rename_entry(source, destination)?;
 
   info!("entry {} was successfully renamed to {}", source.bold(), destination.bold());
 
@@ -141,12 +170,12 @@ mod tests {
   #[test]
   fn add() {
     let tmp = spec::setup();
-    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
 
     context.write().expect("could not write tests vault");
 
     let yml = load_yaml!("../cli.yml");
-    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "username=mitch", "password=supersecret"]);
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "username=mitch", "password=supersecret", "-t", "prod"]);
 
     if let ("add", Some(args)) = app.subcommand() {
       assert_eq!(super::add(args).is_ok(), true);
@@ -172,6 +201,131 @@ mod tests {
       );
 
       assert_eq!(entry.get_attributes().get("unknown"), None);
+      assert!(entry.has_tag("prod"));
+      assert!(context.vault.is_tagged("foo/bar", &["prod"]));
+
+      return;
+    }
+
+    panic!("command add not triggering");
+  }
+
+  #[test]
+  fn add_typed() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "website:url=example.com"]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_err());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "website:url=https://example.com", "code:pin=1234", "localhost:8080=admin"]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_ok());
+
+      let entry = VaultContext::open(tmp.path()).expect("could not get vault").read_entry("foo/bar").expect("could not read added entry");
+
+      assert_eq!(entry.find_attribute(AttributeKind::URL).map(|(name, _)| name.as_str()), Some("website"));
+      assert!(entry.get_attributes().get("code").expect("could not get attribute").confidential);
+      assert_eq!(entry.get_attributes().get("localhost:8080").map(Attribute::get_kind), Some(AttributeKind::GENERIC));
+
+      return;
+    }
+
+    panic!("command add not triggering");
+  }
+
+  #[test]
+  fn add_template() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "--template", "unknown", "username=bob"]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_err());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "--template", "login", "--force", "username=bob"]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_ok());
+
+      let entry = VaultContext::open(tmp.path()).expect("could not get vault").read_entry("foo/bar").expect("could not read added entry");
+      let (_, password) = entry.find_attribute(AttributeKind::PASSWORD).expect("could not find password");
+
+      assert_eq!(password.get_value().len(), 16);
+      assert!(password.confidential);
+      assert_eq!(entry.find_attribute(AttributeKind::USERNAME).map(|(name, _)| name.as_str()), Some("username"));
+      assert!(entry.get_attributes().get("url").is_none());
+
+      return;
+    }
+
+    panic!("command add not triggering");
+  }
+
+  #[test]
+  fn add_expiring() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "token=abcdef", "--expires", "2020-04-01", "-E", "missing=30d"]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_err());
+    }
+
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "token=abcdef", "--expires", "2020-04-01", "-E", "token=30d"]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not get vault");
+      let entry = context.read_entry("foo/bar").expect("could not read added entry");
+
+      assert!(entry.get_attributes().get("token").expect("could not get attribute").get_expires() > entry.get_expires());
+      assert_eq!(context.vault.get_entry_expiry("foo/bar"), Some(entry.get_expires()));
+
+      return;
+    }
+
+    panic!("command add not triggering");
+  }
+
+  #[test]
+  fn add_blob() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let file = tmp.path().join("backup.tar");
+    let data = vec![42; BLOB_THRESHOLD as usize + 1];
+
+    std::fs::write(&file, &data).expect("could not write file");
+
+    let attribute = format!("backup=@{}", file.display());
+    let yml = load_yaml!("../cli.yml");
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "add", "foo/bar", "username=bob", &attribute]);
+
+    if let ("add", Some(args)) = app.subcommand() {
+      assert!(super::add(args).is_ok());
+
+      let context = VaultContext::open(tmp.path()).expect("could not get vault");
+      let entry = context.read_entry("foo/bar").expect("could not read added entry");
+      let attribute = entry.get_attributes().get("backup").expect("could not get attribute");
+      let mut content = Vec::new();
+
+      context.read_blob(attribute, &mut content).expect("could not read blob");
+
+      assert!(attribute.is_blob());
+      assert!(attribute.get_bytes_value().is_empty());
+      assert_eq!(content, data);
 
       return;
     }
@@ -187,11 +341,12 @@ mod tests {
     let mut entry = Entry::default();
     entry.add_attribute("apikey", "abcdef");
     entry.add_attribute("to_be_deleted", "abcdef");
+    entry.add_tag("prod").expect("could not add tag");
 
     vault.write_entry("foo/bar", &entry).expect("could not write entry");
 
     let yml = load_yaml!("../cli.yml");
-    let app = App::from_yaml(yml).get_matches_from(vec!["", "edit", "foo/bar", "username=mitch", "-d", "to_be_deleted"]);
+    let app = App::from_yaml(yml).get_matches_from(vec!["", "edit", "foo/bar", "username=mitch", "-d", "to_be_deleted", "-t", "ops", "-u", "prod"]);
 
     if let ("edit", Some(args)) = app.subcommand() {
       assert_eq!(super::edit(args).is_ok(), true);
@@ -216,6 +371,8 @@ mod tests {
       );
 
       assert_eq!(entry.get_attributes().get("to_be_deleted"), None);
+      assert_eq!(entry.get_tags(), &["ops".to_string()]);
+      assert!(vault.vault.is_tagged("foo/bar", &["ops"]));
 
       return;
     }
@@ -243,7 +400,7 @@ mod tests {
 
       let retrieved = VaultContext::open(tmp.path()).expect("could not open vault").read_entry("foo/bar").expect("could not read entry");
 
-      assert_eq!(&entry, &retrieved);
+      assert_eq!(entry.get_attributes(), retrieved.get_attributes());
 
       return;
     }
@@ -255,7 +412,7 @@ mod tests {
 
       let retrieved = VaultContext::open(tmp.path()).expect("could not open vault").read_entry("lorem/ipsum").expect("could not read entry");
 
-      assert_eq!(&entry, &retrieved);
+      assert_eq!(entry.get_attributes(), retrieved.get_attributes());
 
       return;
     }
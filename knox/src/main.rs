@@ -6,6 +6,7 @@ mod commands;
 mod util;
 
 use std::error::Error;
+use std::time::Duration;
 use std::{env, process};
 
 use clap::App;
@@ -18,6 +19,15 @@ fn main() -> Result<(), Box<dyn Error>> {
   env::set_var("RUST_LOG", "info");
   pretty_env_logger::init();
 
+  if let Ok(timeout) = env::var("KNOX_LOCK_TIMEOUT") {
+    match timeout.parse::<u64>() {
+      Ok(timeout) => libknox::set_lock_timeout(Duration::from_secs(timeout)),
+      Err(_) => warn!("KNOX_LOCK_TIMEOUT should be a number of seconds, ignoring"),
+    }
+  }
+
+  libknox::set_passphrase_provider(util::passphrase::read);
+
   let yml = load_yaml!("cli.yml");
   let mut app = App::from_yaml(yml).name(crate_name!()).version(crate_version!()).author(crate_authors!());
 
@@ -26,14 +36,32 @@ fn main() -> Result<(), Box<dyn Error>> {
   let result = match matches.subcommand() {
     ("init", Some(args)) => commands::init::init(args),
     ("info", Some(args)) => commands::info::info(args),
+    ("migrate", Some(args)) => commands::migrate::migrate(args),
+    ("fsck", Some(args)) => commands::fsck::fsck(args),
     ("identities", Some(args)) => match args.subcommand() {
       ("add", Some(args)) => commands::identities::add(args),
       ("delete", Some(args)) => commands::identities::delete(args),
       _ => usage(&mut app),
     },
+    ("signers", Some(args)) => match args.subcommand() {
+      ("list", Some(args)) => commands::signers::list(args),
+      ("add", Some(args)) => commands::signers::add(args),
+      ("delete", Some(args)) => commands::signers::delete(args),
+      ("policy", Some(args)) => commands::signers::policy(args),
+      _ => usage(&mut app),
+    },
+    ("rollback", Some(args)) => match args.subcommand() {
+      ("accept", Some(args)) => commands::rollback::accept(args),
+      ("policy", Some(args)) => commands::rollback::policy(args),
+      _ => usage(&mut app),
+    },
     ("list", Some(args)) => commands::display::list(args),
     ("search", Some(args)) => commands::display::search(args),
     ("show", Some(args)) => commands::display::show(args),
+    ("history", Some(args)) => commands::history::history(args),
+    ("log", Some(args)) => commands::history::log(args),
+    ("restore", Some(args)) => commands::history::restore_entry(args),
+    ("diff", Some(args)) => commands::history::diff(args),
     ("add", Some(args)) => commands::write::add(args),
     ("edit", Some(args)) => commands::write::edit(args),
     ("totp", Some(args)) => match args.subcommand() {
@@ -42,14 +70,30 @@ fn main() -> Result<(), Box<dyn Error>> {
       ("show", Some(args)) => commands::totp::show(args),
       _ => usage(&mut app),
     },
+    ("expiring", Some(args)) => commands::expiring::expiring(args),
+    ("template", Some(args)) => match args.subcommand() {
+      ("list", Some(args)) => commands::template::list(args),
+      ("show", Some(args)) => commands::template::show(args),
+      ("add", Some(args)) => commands::template::add(args),
+      ("delete", Some(args)) => commands::template::delete(args),
+      _ => usage(&mut app),
+    },
     ("rename", Some(args)) => commands::write::rename(args),
     ("delete", Some(args)) => commands::delete::delete(args),
     ("pwned", Some(args)) => commands::pwned::pwned(args),
     ("git", Some(args)) => match args.subcommand() {
+      ("clone", Some(args)) => commands::git::clone(args),
       ("remote", Some(args)) => commands::git::set_remote(args),
+      ("branch", Some(args)) => commands::git::set_branch(args),
+      ("auth", Some(args)) => commands::git::set_auth(args),
       ("push", Some(args)) => commands::git::push(args),
+      ("pull", Some(args)) => commands::git::pull(args),
+      ("purge", Some(args)) => commands::git::purge(args),
+      ("merge-driver", Some(args)) => commands::git::merge_driver(args),
+      ("textconv", Some(args)) => commands::git::textconv(args),
       _ => usage(&mut app),
     },
+    ("sync", Some(args)) => commands::git::sync(args),
     _ => usage(&mut app),
   };
 
@@ -1,13 +1,20 @@
 use std::collections::HashMap;
 use std::error::Error;
-use std::fs::OpenOptions;
-use std::io::Read;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
 
 use libknox::*;
+
+use crate::util::time;
 use rand::{distributions::Alphanumeric, seq::SliceRandom, Rng};
 
-pub(crate) fn build(args: &clap::ArgMatches) -> Result<HashMap<String, Attribute>, Box<dyn Error>> {
+/// Files too large to be stored in their entry, which are only written as
+/// blobs once the vault is locked, by [write_blobs](fn.write_blobs.html).
+pub(crate) type Blobs = Vec<(String, File)>;
+
+pub(crate) fn build(args: &clap::ArgMatches) -> Result<(HashMap<String, Attribute>, Blobs), Box<dyn Error>> {
   let mut attributes: HashMap<String, Attribute> = HashMap::new();
+  let mut blobs: Blobs = Vec::new();
 
   for attribute in args.values_of("attributes").unwrap_or_default() {
     let attribute: Vec<&str> = attribute.splitn(2, '=').collect();
@@ -16,15 +23,24 @@ pub(crate) fn build(args: &clap::ArgMatches) -> Result<HashMap<String, Attribute
     }
 
     let (key, value) = (&attribute[0], &attribute[1]);
+    // Names can contain colons, so a suffix that is not a known kind is part
+    // of the name.
+    let (key, kind) = match key.rsplitn(2, ':').collect::<Vec<&str>>().as_slice() {
+      [kind, name] => match AttributeKind::parse(kind) {
+        Ok(kind) => (*name, kind),
+        Err(_) => (*key, AttributeKind::GENERIC),
+      },
+      _ => (*key, AttributeKind::GENERIC),
+    };
+
     let mut attribute = Attribute {
       value: (*value).to_string(),
+      kind,
       ..Attribute::default()
     };
 
     if value == &"-" {
-      let (length, symbols) = (args.value_of("random_length").unwrap_or("16"), args.is_present("random_symbols"));
-
-      attribute.value = random_secret(length, symbols)?;
+      attribute.value = generate(kind, args)?;
       attribute.confidential = true;
     }
     if value == &"" {
@@ -36,18 +52,146 @@ pub(crate) fn build(args: &clap::ArgMatches) -> Result<HashMap<String, Attribute
       file_chars.next();
 
       let mut file = OpenOptions::new().read(true).open(file_chars.as_str())?;
-      let mut buffer: Vec<u8> = Vec::new();
-      file.read_to_end(&mut buffer)?;
 
-      attribute.value = String::new();
-      attribute.bytes_value = buffer;
-      attribute.file = true;
+      if file.metadata()?.len() > BLOB_THRESHOLD {
+        if let AttributeKind::SSH_KEY | AttributeKind::CERTIFICATE = kind {
+          validate_file(key, kind, &mut file)?;
+        }
+
+        // The blob reference is filled in when the blob is written, the path
+        // of the file stands in for it until then.
+        attribute = Attribute {
+          kind,
+          file: true,
+          blob: file_chars.as_str().to_string(),
+          ..Attribute::default()
+        };
+
+        blobs.push((key.to_string(), file));
+      } else {
+        let mut buffer: Vec<u8> = Vec::new();
+        file.read_to_end(&mut buffer)?;
+
+        attribute.value = String::new();
+        attribute.bytes_value = buffer;
+        attribute.file = true;
+      }
+    }
+
+    if let AttributeKind::PASSWORD | AttributeKind::PIN = kind {
+      attribute.confidential = true;
+    }
+
+    attribute.validate().map_err(|err| VaultError::throw(&format!("invalid value for attribute {}: {}", key, err)))?;
+
+    attributes.insert(key.to_string(), attribute);
+  }
+
+  Ok((attributes, blobs))
+}
+
+/// Write the blobs collected by [build](fn.build.html) as part of a
+/// transaction, and reference them from their attributes.
+pub(crate) fn write_blobs(transaction: &mut Transaction, attributes: &mut HashMap<String, Attribute>, blobs: Blobs) -> Result<(), Box<dyn Error>> {
+  for (key, file) in blobs {
+    if let Some(attribute) = attributes.get_mut(&key) {
+      let blob = transaction.write_blob(file)?;
+
+      attribute.blob = blob.blob;
+      attribute.size = blob.size;
+      attribute.digest = blob.digest;
     }
+  }
+
+  Ok(())
+}
+
+// Blobs are streamed, so their content is checked before they are written.
+fn validate_file(key: &str, kind: AttributeKind, file: &mut File) -> Result<(), Box<dyn Error>> {
+  let mut attribute = Attribute {
+    kind,
+    file: true,
+    ..Attribute::default()
+  };
+
+  file.read_to_end(&mut attribute.bytes_value)?;
+  file.seek(SeekFrom::Start(0))?;
 
-    attributes.insert((*key).to_string(), attribute);
+  attribute.validate().map_err(|err| VaultError::throw(&format!("invalid value for attribute {}: {}", key, err)))
+}
+
+pub(crate) fn apply_template(template: &Template, attributes: &mut HashMap<String, Attribute>, args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  for field in template.get_fields() {
+    let name = field.get_name();
+
+    match attributes.get_mut(name) {
+      Some(attribute) => {
+        if attribute.kind == AttributeKind::GENERIC {
+          attribute.kind = field.get_kind();
+        }
+
+        attribute.confidential |= field.get_confidential();
+      }
+
+      None if field.get_generate() || !field.get_optional() => {
+        let value = match (field.get_generate(), field.get_kind(), field.get_confidential()) {
+          (true, kind, _) => generate(kind, args)?,
+          (false, AttributeKind::SSH_KEY, _) | (false, AttributeKind::CERTIFICATE, _) => prompt_for_text(name)?,
+          (false, _, true) => prompt_for_secret(name)?,
+          (false, _, false) => prompt_for_value(name)?,
+        };
+
+        let attribute = Attribute {
+          value,
+          kind: field.get_kind(),
+          confidential: field.get_confidential(),
+          ..Attribute::default()
+        };
+
+        attributes.insert(name.to_string(), attribute);
+      }
+
+      None => {}
+    }
   }
 
-  Ok(attributes)
+  template.check(&Entry {
+    attributes: attributes.clone(),
+    ..Entry::default()
+  })
+}
+
+pub(crate) fn apply_expiry(entry: &mut Entry, args: &clap::ArgMatches) -> Result<(), Box<dyn Error>> {
+  if let Some(expires) = args.value_of("expires") {
+    entry.set_expires(parse_expiry(expires)?.unwrap_or_default());
+  }
+
+  for spec in args.values_of("expires_attribute").unwrap_or_default() {
+    let (key, expires) = match spec.splitn(2, '=').collect::<Vec<&str>>().as_slice() {
+      [key, expires] => (*key, parse_expiry(expires)?),
+      _ => return Err(VaultError::throw("could not parse attribute expiration, expected ATTRIBUTE=WHEN")),
+    };
+
+    entry.set_attribute_expiry(key, expires)?;
+  }
+
+  Ok(())
+}
+
+fn parse_expiry(expires: &str) -> Result<Option<u64>, Box<dyn Error>> {
+  match expires {
+    "never" => Ok(None),
+    _ => time::parse_expiry(expires).map(Some),
+  }
+}
+
+fn generate(kind: AttributeKind, args: &clap::ArgMatches) -> Result<String, Box<dyn Error>> {
+  let (length, symbols) = (args.value_of("random_length").unwrap_or("16"), args.is_present("random_symbols"));
+
+  match kind {
+    AttributeKind::PIN => Ok(random_pin(length)),
+    _ => random_secret(length, symbols),
+  }
 }
 
 const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789)(*&^%$#@!~";
@@ -64,6 +208,38 @@ fn random_secret(length: &str, symbols: bool) -> Result<String, Box<dyn Error>>
   }
 }
 
+fn random_pin(length: &str) -> String {
+  let length = length.parse::<usize>().unwrap_or(16);
+  let mut rng = rand::thread_rng();
+
+  (0..length).map(|_| rng.gen_range(0, 10).to_string()).collect::<String>()
+}
+
+fn prompt_for_value(key: &str) -> Result<String, Box<dyn Error>> {
+  use colored::*;
+
+  print!("Enter value for '{}': ", key.bold());
+  io::stdout().flush()?;
+
+  let mut value = String::new();
+  io::stdin().read_line(&mut value)?;
+
+  Ok(value.trim_end_matches(&['\n', '\r'][..]).to_string())
+}
+
+// Keys and certificates span several lines, so they are read until the end of
+// the input.
+fn prompt_for_text(key: &str) -> Result<String, Box<dyn Error>> {
+  use colored::*;
+
+  println!("Enter value for '{}', then press Ctrl-D:", key.bold());
+
+  let mut value = String::new();
+  io::stdin().read_to_string(&mut value)?;
+
+  Ok(value)
+}
+
 fn prompt_for_secret(key: &str) -> Result<String, Box<dyn Error>> {
   use colored::*;
 
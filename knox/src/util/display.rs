@@ -2,13 +2,14 @@ use std::cell::Cell;
 use std::error::Error;
 use std::fmt;
 use std::fs::{self, OpenOptions};
-use std::io::Write;
 use std::path::Path;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use libknox::{totp, *};
 
-pub(crate) fn entry(path: &str, entry: &Entry, print: bool) {
+use crate::util::time;
+
+pub(crate) fn header(path: &str) {
   use colored::*;
 
   let mut components: Vec<&str> = path.split('/').collect();
@@ -20,6 +21,48 @@ pub(crate) fn entry(path: &str, entry: &Entry, print: bool) {
 
   print!("{}", crumbs.join(&format!("{}", " / ".dimmed())));
   println!(" {} {}", "/".dimmed(), file_name.bold());
+}
+
+pub(crate) fn value(attribute: &Attribute, print: bool) -> String {
+  use colored::*;
+
+  let value = match (attribute.confidential, attribute.file, attribute.kind, print) {
+    (true, false, _, false) => format!("{}", "<redacted>".red()),
+    (true, false, _, true) => format!("{}", attribute.value.red()),
+    (_, _, AttributeKind::SSH_KEY, false) => format!("{}", "<ssh key>".green()),
+    (_, _, AttributeKind::CERTIFICATE, false) => format!("{}", "<certificate>".green()),
+    (false, true, _, _) if attribute.is_blob() => format!("{} {}", "<file content>".green(), format!("({})", size(attribute.get_size())).dimmed()),
+    (false, true, _, _) => format!("{}", "<file content>".green()),
+    (_, _, AttributeKind::URL, _) => format!("{}", attribute.value.underline()),
+    _ => attribute.value.clone(),
+  };
+
+  match attribute.kind {
+    AttributeKind::GENERIC => value,
+    kind => format!("{} {}", value, format!("[{}]", kind.name()).dimmed()),
+  }
+}
+
+fn size(bytes: u64) -> String {
+  let units = ["bytes", "KiB", "MiB", "GiB", "TiB"];
+  let mut size = bytes as f64;
+  let mut unit = 0;
+
+  while size >= 1024.0 && unit < units.len() - 1 {
+    size /= 1024.0;
+    unit += 1;
+  }
+
+  match unit {
+    0 => format!("{} {}", bytes, units[0]),
+    _ => format!("{:.1} {}", size, units[unit]),
+  }
+}
+
+pub(crate) fn entry(path: &str, entry: &Entry, print: bool) {
+  use colored::*;
+
+  header(path);
 
   let length = Cell::new(0);
   let mut attributes: Vec<(String, String)> = entry
@@ -30,11 +73,9 @@ pub(crate) fn entry(path: &str, entry: &Entry, print: bool) {
         length.set(key.len());
       }
 
-      match (attribute.confidential, attribute.file, print) {
-        (true, false, false) => (key.to_string(), format!("{}", "<redacted>".red())),
-        (true, false, true) => (key.to_string(), format!("{}", attribute.value.red())),
-        (false, true, _) => (key.to_string(), format!("{}", "<file content>".green())),
-        _ => (key.to_string(), attribute.value.clone()),
+      match attribute.expires {
+        0 => (key.to_string(), value(attribute, print)),
+        expires => (key.to_string(), format!("{} {}", value(attribute, print), format!("({})", time::expiry(expires)).dimmed())),
       }
     })
     .collect();
@@ -54,9 +95,80 @@ pub(crate) fn entry(path: &str, entry: &Entry, print: bool) {
   for (key, value) in attributes {
     println!("{: >width$} = {}", key.bold(), value, width = length.get() + 2);
   }
+
+  if !entry.get_tags().is_empty() {
+    let tags = entry.get_tags().iter().map(|tag| format!("#{}", tag)).collect::<Vec<String>>();
+
+    println!("\n{}", tags.join(" ").blue());
+  }
+
+  println!("\n{}", stamp(entry).dimmed());
+}
+
+pub(crate) fn text(entry: &Entry) -> String {
+  let mut keys: Vec<&String> = entry.get_attributes().keys().collect();
+  let mut lines = Vec::new();
+
+  keys.sort();
+
+  for key in keys {
+    let attribute = &entry.get_attributes()[key];
+    let mut line = format!("{} = {}", key, value(attribute, false));
+
+    if attribute.expires > 0 {
+      line.push_str(&format!(" (expires {})", time::format(attribute.expires)));
+    }
+
+    // Confidential values are redacted, so their last modification is shown
+    // for changes to appear in the diff.
+    if let Some(revision) = entry.get_revisions(key).first() {
+      line.push_str(&format!(" (changed {})", time::format(revision.get_timestamp())));
+    }
+
+    lines.push(line);
+  }
+
+  if entry.has_totp() {
+    let totp = entry.get_totp();
+
+    lines.push(format!("@totp = <redacted> ({:?}, {} digits, every {}s)", totp.get_hash(), totp.get_length(), totp.get_interval()));
+  }
+
+  if !entry.get_tags().is_empty() {
+    lines.push(entry.get_tags().iter().map(|tag| format!("#{}", tag)).collect::<Vec<String>>().join(" "));
+  }
+
+  lines.push(stamp(entry));
+
+  lines.join("\n")
+}
+
+pub(crate) fn stamp(entry: &Entry) -> String {
+  let author = match entry.get_author() {
+    "" => String::new(),
+    author => format!(" by {}", author),
+  };
+
+  let expires = match entry.get_expires() {
+    0 => String::new(),
+    expires => format!(", expires {}", time::format(expires)),
+  };
+
+  format!("created {}, modified {}{}{}", time::format(entry.get_created()), time::format(entry.get_modified()), author, expires)
+}
+
+pub(crate) fn signature(context: &VaultContext, signature: Option<&Signature>) -> Option<String> {
+  use colored::*;
+
+  match signature {
+    Some(signature) if context.is_trusted_signer(signature) => Some(format!("{}", format!("signed by {} ({}, trusted)", signature.signer, signature.fingerprint).dimmed())),
+    Some(signature) => Some(format!("{}", format!("signed by {} ({}, untrusted)", signature.signer, signature.fingerprint).yellow())),
+    None if context.backend().kind() == BackendKind::GPG => Some(format!("{}", "not signed".yellow())),
+    None => None,
+  }
 }
 
-pub(crate) fn write_files<T>(path: T, entry: &Entry, filter: &Option<Vec<&str>>) -> Result<(), Box<dyn Error>>
+pub(crate) fn write_files<T>(context: &VaultContext, path: T, entry: &Entry, revision: Option<&str>, filter: &Option<Vec<&str>>) -> Result<(), Box<dyn Error>>
 where
   T: AsRef<Path> + fmt::Display,
 {
@@ -76,11 +188,11 @@ where
   });
 
   for (key, attribute) in attributes {
-    let mut file = OpenOptions::new().create(true).truncate(true).write(true).open(format!("{}/{}", &dir, key))?;
+    let file = OpenOptions::new().create(true).truncate(true).write(true).open(format!("{}/{}", &dir, key))?;
 
-    match attribute.value() {
-      AttributeValue::String(string) => file.write_all(string.as_bytes())?,
-      AttributeValue::Binary(bytes) => file.write_all(&bytes)?,
+    match revision {
+      Some(revision) => context.read_blob_at(revision, attribute, file)?,
+      None => context.read_blob(attribute, file)?,
     }
   }
 
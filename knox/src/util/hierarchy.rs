@@ -11,11 +11,16 @@ pub(crate) enum Item {
   File(String),
 }
 
-pub(crate) fn search(vault: &Vault, term: &str) -> Vec<String> {
-  vault.get_index().keys().filter(|item| item.contains(term)).cloned().collect::<Vec<String>>()
+pub(crate) fn search(vault: &Vault, term: &str, tags: &[&str]) -> Vec<String> {
+  vault
+    .get_index()
+    .keys()
+    .filter(|item| item.contains(term) && vault.is_tagged(item, tags))
+    .cloned()
+    .collect::<Vec<String>>()
 }
 
-pub(crate) fn build(paths: &Vault, prefix: Option<&str>) -> Option<Rc<Item>> {
+pub(crate) fn build(paths: &Vault, prefix: Option<&str>, tags: &[&str]) -> Option<Rc<Item>> {
   let root = Rc::new(Item::Directory("/".to_string(), RefCell::new(Vec::new())));
   let mut index: HashMap<String, Rc<Item>> = HashMap::new();
 
@@ -26,6 +31,7 @@ pub(crate) fn build(paths: &Vault, prefix: Option<&str>) -> Option<Rc<Item>> {
       None => true,
       Some(prefix) => item.starts_with(&format!("{}/", prefix)),
     })
+    .filter(|item| paths.is_tagged(item, tags))
     .map(|path| path.split('/').collect::<Vec<&str>>())
     .collect();
 
@@ -137,6 +143,27 @@ mod tests {
       ]),
     ));
 
-    assert_eq!(Some(expected), super::build(&vault, None));
+    assert_eq!(Some(expected), super::build(&vault, None, &[]));
+  }
+
+  #[test]
+  fn build_with_tags() {
+    let mut vault = Vault::default();
+    let mut tags = Tags::default();
+
+    tags.mut_tags().push("prod".to_string());
+
+    vault.mut_index().insert("etc/hosts".to_string(), "".to_string());
+    vault.mut_index().insert("etc/passwd".to_string(), "".to_string());
+    vault.mut_tags().insert("etc/passwd".to_string(), tags);
+
+    let expected = Rc::new(Item::Directory(
+      "/".to_string(),
+      RefCell::new(vec![Rc::new(Item::Directory("etc".to_string(), RefCell::new(vec![Rc::new(Item::File("passwd".to_string()))])))]),
+    ));
+
+    assert_eq!(Some(expected), super::build(&vault, None, &["prod"]));
+    assert_eq!(None, super::build(&vault, None, &["ops"]));
+    assert_eq!(super::search(&vault, "etc", &["prod"]), vec!["etc/passwd".to_string()]);
   }
 }
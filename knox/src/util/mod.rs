@@ -1,6 +1,8 @@
 pub(crate) mod attributes;
 pub(crate) mod display;
 pub(crate) mod hierarchy;
+pub(crate) mod passphrase;
+pub(crate) mod time;
 
 use std::env;
 use std::error::Error;
@@ -0,0 +1,72 @@
+use std::env;
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::mem::ManuallyDrop;
+use std::os::unix::io::{FromRawFd, RawFd};
+use std::sync::Mutex;
+
+use libknox::*;
+
+static PASSPHRASE: Mutex<Option<String>> = Mutex::new(None);
+
+/// Retrieve the passphrase of the vault, asking for it at most once.
+pub(crate) fn read() -> Result<String, Box<dyn Error>> {
+  let mut cached = PASSPHRASE.lock().map_err(|_| VaultError::throw("could not retrieve the passphrase"))?;
+
+  if let Some(passphrase) = cached.as_ref() {
+    return Ok(passphrase.clone());
+  }
+
+  let passphrase = match from_environment()? {
+    Some(passphrase) => passphrase,
+    None => rpassword::prompt_password_stdout("Enter vault passphrase: ")?,
+  };
+
+  *cached = Some(passphrase.clone());
+
+  Ok(passphrase)
+}
+
+/// Retrieve the passphrase of a new vault, asking for it twice.
+pub(crate) fn read_new() -> Result<String, Box<dyn Error>> {
+  if let Some(passphrase) = from_environment()? {
+    return Ok(passphrase);
+  }
+
+  let passphrase = rpassword::prompt_password_stdout("Enter new vault passphrase: ")?;
+  let confirmation = rpassword::prompt_password_stdout("Confirm vault passphrase: ")?;
+
+  if passphrase != confirmation {
+    return Err(VaultError::throw("the passphrases do not match"));
+  }
+
+  Ok(passphrase)
+}
+
+fn from_environment() -> Result<Option<String>, Box<dyn Error>> {
+  if let Ok(passphrase) = env::var("KNOX_PASSPHRASE") {
+    return Ok(Some(passphrase));
+  }
+
+  if let Ok(fd) = env::var("KNOX_PASSPHRASE_FD") {
+    let fd = fd.parse::<RawFd>().map_err(|_| VaultError::throw("KNOX_PASSPHRASE_FD should be a file descriptor number"))?;
+
+    if fd < 0 || fd == libc::STDOUT_FILENO || fd == libc::STDERR_FILENO {
+      return Err(VaultError::throw("KNOX_PASSPHRASE_FD should be an input file descriptor"));
+    }
+    if unsafe { libc::fcntl(fd, libc::F_GETFD) } == -1 {
+      return Err(VaultError::throw(&format!("KNOX_PASSPHRASE_FD refers to file descriptor {}, which is not open", fd)));
+    }
+
+    // The descriptor is not owned by knox, and is left open once read.
+    let file = ManuallyDrop::new(unsafe { File::from_raw_fd(fd) });
+    let mut passphrase = String::new();
+
+    BufReader::new(&*file).read_line(&mut passphrase)?;
+
+    return Ok(Some(passphrase.trim_end_matches(&['\n', '\r'][..]).to_string()));
+  }
+
+  Ok(None)
+}
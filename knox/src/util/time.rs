@@ -0,0 +1,130 @@
+use std::convert::TryFrom;
+use std::error::Error;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{Local, NaiveDate, TimeZone};
+
+use libknox::*;
+
+pub(crate) fn format(timestamp: u64) -> String {
+  match timestamp {
+    0 => "unknown".to_string(),
+    _ => Local.timestamp(timestamp as i64, 0).format("%Y-%m-%d %H:%M").to_string(),
+  }
+}
+
+pub(crate) fn parse_date(date: &str) -> Result<u64, Box<dyn Error>> {
+  let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| VaultError::throw(&format!("invalid date '{}', expected YYYY-MM-DD", date)))?;
+
+  match Local.from_local_datetime(&date.and_hms(0, 0, 0)).earliest() {
+    Some(datetime) => Ok(datetime.timestamp() as u64),
+    None => Err(VaultError::throw(&format!("invalid local date '{}'", date))),
+  }
+}
+
+pub(crate) fn now() -> u64 {
+  SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs()).unwrap_or_default()
+}
+
+pub(crate) fn parse_duration(duration: &str) -> Result<u64, Box<dyn Error>> {
+  let error = || VaultError::throw(&format!("invalid duration '{}', expected a number followed by h, d, w or y", duration));
+
+  let unit = match duration.chars().last() {
+    Some('h') => 3600,
+    Some('d') => 86400,
+    Some('w') => 7 * 86400,
+    Some('y') => 365 * 86400,
+    _ => return Err(error()),
+  };
+
+  let count = duration[..duration.len() - 1].parse::<u64>().map_err(|_| error())?;
+
+  count.checked_mul(unit).ok_or_else(|| VaultError::throw(&format!("duration '{}' is too long", duration)))
+}
+
+pub(crate) fn parse_expiry(expiry: &str) -> Result<u64, Box<dyn Error>> {
+  match parse_date(expiry) {
+    Ok(timestamp) => Ok(timestamp),
+    Err(_) => {
+      let duration = parse_duration(expiry).map_err(|_| VaultError::throw(&format!("invalid expiration '{}', expected YYYY-MM-DD or a duration such as 90d", expiry)))?;
+
+      from_now(duration).ok_or_else(|| VaultError::throw(&format!("expiration '{}' is too far in the future", expiry)))
+    }
+  }
+}
+
+pub(crate) fn parse_deadline(within: &str) -> Result<u64, Box<dyn Error>> {
+  let duration = parse_duration(within)?;
+
+  from_now(duration).ok_or_else(|| VaultError::throw(&format!("period '{}' is too far in the future", within)))
+}
+
+fn from_now(duration: u64) -> Option<u64> {
+  now()
+    .checked_add(duration)
+    .filter(|timestamp| i64::try_from(*timestamp).ok().and_then(|seconds| Local.timestamp_opt(seconds, 0).single()).is_some())
+}
+
+pub(crate) fn expiry(timestamp: u64) -> String {
+  let now = now();
+
+  match timestamp.checked_sub(now) {
+    Some(seconds) if seconds >= 86400 => format!("expires in {} days", seconds / 86400),
+    Some(seconds) if seconds >= 3600 => format!("expires in {} hours", seconds / 3600),
+    Some(seconds) if seconds > 0 => "expires within the hour".to_string(),
+    _ if now - timestamp < 86400 => "expired today".to_string(),
+    _ => format!("expired {} days ago", (now - timestamp) / 86400),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{Local, TimeZone};
+
+  #[test]
+  fn parse_date() {
+    let timestamp = super::parse_date("2020-04-01").expect("could not parse date");
+
+    assert_eq!(timestamp as i64, Local.ymd(2020, 4, 1).and_hms(0, 0, 0).timestamp());
+    assert!(super::parse_date("01/04/2020").is_err());
+  }
+
+  #[test]
+  fn parse_duration() {
+    assert_eq!(super::parse_duration("30d").expect("could not parse duration"), 30 * 86400);
+    assert_eq!(super::parse_duration("2w").expect("could not parse duration"), 14 * 86400);
+    assert_eq!(super::parse_duration("12h").expect("could not parse duration"), 12 * 3600);
+    assert!(super::parse_duration("30").is_err());
+    assert!(super::parse_duration("d").is_err());
+    assert!(super::parse_duration("").is_err());
+    assert!(super::parse_duration(&format!("{}y", u64::MAX / 86400)).is_err());
+  }
+
+  #[test]
+  fn parse_expiry() {
+    let timestamp = super::parse_expiry("90d").expect("could not parse expiry");
+
+    assert!(timestamp >= super::now() + 89 * 86400);
+    assert_eq!(
+      super::parse_expiry("2020-04-01").expect("could not parse expiry") as i64,
+      Local.ymd(2020, 4, 1).and_hms(0, 0, 0).timestamp()
+    );
+    assert!(super::parse_expiry("soon").is_err());
+    assert!(super::parse_expiry(&format!("{}h", u64::MAX / 3600)).is_err());
+  }
+
+  #[test]
+  fn parse_deadline() {
+    assert!(super::parse_deadline("30d").expect("could not parse deadline") >= super::now() + 29 * 86400);
+    assert!(super::parse_deadline("213503982334d").is_err());
+    assert!(super::parse_deadline("soon").is_err());
+  }
+
+  #[test]
+  fn format() {
+    let timestamp = Local.ymd(2020, 4, 1).and_hms(13, 37, 0).timestamp() as u64;
+
+    assert_eq!(super::format(timestamp), "2020-04-01 13:37");
+    assert_eq!(super::format(0), "unknown");
+  }
+}
@@ -0,0 +1,221 @@
+//! Encryption backend relying on [age](https://age-encryption.org) keys.
+
+use std::env;
+use std::error::Error;
+use std::io::{self, BufReader, Read, Write};
+use std::path::PathBuf;
+
+use ::age::armor::{ArmoredReader, ArmoredWriter, Format};
+use ::age::{x25519, Decryptor, Encryptor, IdentityFile, IdentityFileEntry};
+
+use super::Backend;
+use crate::pb::*;
+use crate::util::VaultError;
+
+/// Environment variable listing the age identity files, separated like
+/// `PATH`.
+pub const IDENTITIES_VARIABLE: &str = "KNOX_AGE_IDENTITIES";
+
+const ARMOR_HEADER: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+const BINARY_HEADER: &[u8] = b"age-encryption.org/";
+
+/// Encryption backend relying on age X25519 keys.
+///
+/// Recipients are age public keys, such as `age1ql3z7hjy54pw3...`. Data is
+/// decrypted with the identities found in the files listed in the
+/// `KNOX_AGE_IDENTITIES` environment variable, or in
+/// `~/.config/knox/identities` by default. Identity files use the format
+/// produced by `age-keygen`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Age;
+
+impl Backend for Age {
+  fn kind(&self) -> BackendKind {
+    BackendKind::AGE
+  }
+
+  fn check_recipient(&self, recipient: &str) -> Result<(), Box<dyn Error>> {
+    parse_recipient(recipient).map(|_| ())
+  }
+
+  fn get_author(&self, recipients: &[String]) -> Option<String> {
+    get_identities()
+      .ok()?
+      .iter()
+      .map(|identity| identity.to_public().to_string())
+      .find(|recipient| recipients.contains(recipient))
+  }
+
+  fn local_identities(&self) -> Vec<String> {
+    match get_identities() {
+      Ok(identities) => identities.iter().map(|identity| identity.to_public().to_string()).collect(),
+      Err(_) => Vec::new(),
+    }
+  }
+
+  fn encrypt(&self, recipients: &[String], plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let armor = ArmoredWriter::wrap_output(Vec::new(), Format::AsciiArmor)?;
+    let mut output = get_encryptor(recipients)?.wrap_output(armor)?;
+
+    output.write_all(plaintext)?;
+
+    Ok(output.finish()?.finish()?)
+  }
+
+  fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut plaintext = Vec::new();
+
+    self.decrypt_stream(&mut &ciphertext[..], &mut plaintext)?;
+
+    Ok(plaintext)
+  }
+
+  fn encrypt_stream(&self, recipients: &[String], reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>> {
+    let mut output = get_encryptor(recipients)?.wrap_output(writer)?;
+
+    io::copy(reader, &mut output)?;
+    output.finish()?;
+
+    Ok(())
+  }
+
+  fn decrypt_stream(&self, reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>> {
+    let identities = get_identities()?;
+
+    let mut input = match Decryptor::new(ArmoredReader::new(reader))? {
+      Decryptor::Recipients(decryptor) => decryptor.decrypt(identities.iter().map(|identity| identity as &dyn ::age::Identity))?,
+      Decryptor::Passphrase(_) => return Err(VaultError::throw("data encrypted with an age passphrase is not supported")),
+    };
+
+    io::copy(&mut input, writer)?;
+
+    Ok(())
+  }
+}
+
+pub(crate) fn is_ciphertext(data: &[u8]) -> bool {
+  data.starts_with(ARMOR_HEADER) || data.starts_with(BINARY_HEADER)
+}
+
+fn parse_recipient(recipient: &str) -> Result<x25519::Recipient, Box<dyn Error>> {
+  recipient
+    .parse::<x25519::Recipient>()
+    .map_err(|err| VaultError::throw(&format!("invalid age recipient '{}': {}", recipient, err)))
+}
+
+fn get_encryptor(recipients: &[String]) -> Result<Encryptor, Box<dyn Error>> {
+  let recipients = recipients
+    .iter()
+    .map(|recipient| parse_recipient(recipient).map(|recipient| Box::new(recipient) as Box<dyn ::age::Recipient + Send>))
+    .collect::<Result<Vec<_>, _>>()?;
+
+  Encryptor::with_recipients(recipients).ok_or_else(|| VaultError::throw("no recipient was provided"))
+}
+
+fn get_identity_files() -> Vec<PathBuf> {
+  if let Some(paths) = env::var_os(IDENTITIES_VARIABLE) {
+    return env::split_paths(&paths).collect();
+  }
+
+  let config = env::var_os("XDG_CONFIG_HOME")
+    .map(PathBuf::from)
+    .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+
+  config.map(|config| config.join("knox").join("identities")).into_iter().collect()
+}
+
+fn get_identities() -> Result<Vec<x25519::Identity>, Box<dyn Error>> {
+  let mut identities = Vec::new();
+
+  for path in get_identity_files() {
+    if !path.exists() {
+      continue;
+    }
+
+    let file = IdentityFile::from_buffer(BufReader::new(std::fs::File::open(&path)?)).map_err(|err| VaultError::throw(&format!("could not read age identities from {}: {}", path.display(), err)))?;
+
+    for IdentityFileEntry::Native(identity) in file.into_identities() {
+      identities.push(identity);
+    }
+  }
+
+  if identities.is_empty() {
+    return Err(VaultError::throw(&format!(
+      "no age identity was found, please set {} to the path of an identity file",
+      IDENTITIES_VARIABLE
+    )));
+  }
+
+  Ok(identities)
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::backend;
+  use crate::*;
+
+  #[test]
+  fn encrypt_and_decrypt() {
+    let tmp = spec::setup_age();
+    let recipient = spec::AGE_RECIPIENT.to_string();
+    let backend = backend::open(BackendKind::AGE, tmp.path()).expect("could not get backend");
+
+    let data = "foobarhelloworld".as_bytes();
+    let ciphertext = backend.encrypt(std::slice::from_ref(&recipient), data).expect("could not encrypt data");
+
+    assert_eq!(backend::detect(&ciphertext), BackendKind::AGE);
+    assert_eq!(backend.decrypt(&ciphertext).expect("could not decrypt data"), data);
+    assert_eq!(backend.get_author(std::slice::from_ref(&recipient)), Some(recipient));
+    assert!(backend.check_recipient("age1invalid").is_err());
+  }
+
+  #[test]
+  fn encrypt_and_decrypt_stream() {
+    let tmp = spec::setup_age();
+    let recipient = spec::AGE_RECIPIENT.to_string();
+    let backend = backend::open(BackendKind::AGE, tmp.path()).expect("could not get backend");
+
+    let data = vec![42; 1024 * 1024];
+    let (mut ciphertext, mut plaintext) = (Vec::new(), Vec::new());
+
+    backend.encrypt_stream(&[recipient], &mut data.as_slice(), &mut ciphertext).expect("could not encrypt data");
+    backend.decrypt_stream(&mut ciphertext.as_slice(), &mut plaintext).expect("could not decrypt data");
+
+    assert_eq!(plaintext, data);
+  }
+
+  #[test]
+  fn vault() {
+    let tmp = spec::setup_age();
+
+    let mut context = VaultContext::create_with_backend(tmp.path(), BackendKind::AGE, &[spec::AGE_RECIPIENT.to_string()]).expect("could not create vault");
+    context.write().expect("could not write vault");
+
+    let mut context = VaultContext::open(tmp.path()).expect("could not open vault");
+    let mut entry = Entry::default();
+    entry.add_confidential_attribute("password", "foobar");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.get_backend(), BackendKind::AGE);
+    assert_eq!(retrieved.read_entry("foo/bar").expect("could not read entry").get_attributes(), entry.get_attributes());
+  }
+
+  #[test]
+  fn trusted_signers() {
+    let tmp = spec::setup_age();
+
+    let mut context = VaultContext::create_with_backend(tmp.path(), BackendKind::AGE, &[spec::AGE_RECIPIENT.to_string()]).expect("could not create vault");
+    context.write().expect("could not write vault");
+
+    let mut context = VaultContext::open(tmp.path()).expect("could not open vault");
+    let mut config = LocalConfig::default();
+    config.mut_trusted_signers().push("0000000000000000000000000000000000000000".to_string());
+
+    assert!(context.write_local_config(config).is_err());
+  }
+}
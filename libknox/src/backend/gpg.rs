@@ -0,0 +1,294 @@
+//! Encryption backend relying on GPG keys through _gpg-agent_.
+
+use std::error::Error;
+use std::io::{Read, Write};
+
+use gpgme::data::IntoData;
+use gpgme::{Context, Data, Key, Protocol};
+
+use super::{Backend, Signature};
+use crate::pb::*;
+use crate::util::VaultError;
+
+/// Encryption backend relying on GPG keys.
+///
+/// Recipients are GPG identities, such as email addresses or key
+/// fingerprints, whose public keys must be available to _gpg-agent_.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Gpg;
+
+impl Backend for Gpg {
+  fn kind(&self) -> BackendKind {
+    BackendKind::GPG
+  }
+
+  fn check_recipient(&self, recipient: &str) -> Result<(), Box<dyn Error>> {
+    get_keys(&mut get_context()?, &[recipient.to_string()]).map(|_| ())
+  }
+
+  fn get_author(&self, recipients: &[String]) -> Option<String> {
+    get_author(recipients)
+  }
+
+  fn can_sign(&self) -> bool {
+    true
+  }
+
+  fn local_identities(&self) -> Vec<String> {
+    let mut context = match get_context() {
+      Ok(context) => context,
+      Err(_) => return Vec::new(),
+    };
+
+    let identities = match context.secret_keys() {
+      Ok(keys) => keys
+        .filter_map(Result::ok)
+        .filter(Key::can_encrypt)
+        .filter_map(|key| key.fingerprint().ok().map(ToString::to_string))
+        .collect(),
+      Err(_) => Vec::new(),
+    };
+
+    identities
+  }
+
+  fn encrypt(&self, recipients: &[String], plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    encrypt(recipients, plaintext)
+  }
+
+  fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    decrypt(ciphertext)
+  }
+
+  fn decrypt_and_verify(&self, ciphertext: &[u8]) -> Result<(Vec<u8>, Option<Signature>), Box<dyn Error>> {
+    decrypt_and_verify(ciphertext)
+  }
+
+  fn encrypt_stream(&self, recipients: &[String], reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>> {
+    encrypt_stream(recipients, reader, writer)
+  }
+
+  fn decrypt_stream(&self, reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>> {
+    decrypt_stream(reader, writer)
+  }
+}
+
+pub fn get_context() -> Result<Context, Box<dyn Error>> {
+  let mut context = Context::from_protocol(Protocol::OpenPgp)?;
+  context.set_armor(true);
+
+  Ok(context)
+}
+
+pub fn get_keys(context: &mut Context, identities: &[String]) -> Result<Vec<Key>, Box<dyn Error>> {
+  let keys: Vec<Key> = context.find_keys(identities)?.filter_map(Result::ok).filter(Key::can_encrypt).collect();
+
+  if !keys.is_empty() {
+    Ok(keys)
+  } else {
+    Err(VaultError::throw("no public key was found for provided identity"))
+  }
+}
+
+/// Describe the local identity modifying a vault.
+///
+/// Returns the user ID of the first secret key available for one of the
+/// provided identities, or its fingerprint if it has no readable user ID.
+pub fn get_author(identities: &[String]) -> Option<String> {
+  let mut context = get_context().ok()?;
+  let key = context.find_secret_keys(identities).ok()?.filter_map(Result::ok).next()?;
+
+  match key.user_ids().next().and_then(|id| id.id().ok().map(ToString::to_string)) {
+    Some(id) => Some(id),
+    None => key.fingerprint().ok().map(ToString::to_string),
+  }
+}
+
+pub fn encrypt(identities: &[String], object: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+  let mut context = get_context()?;
+  let keys = get_keys(&mut context, identities)?;
+
+  if keys.len() < identities.len() {
+    return Err(VaultError::throw("could not retrieve the public keys for all provided identities"));
+  }
+
+  let mut output = Vec::new();
+
+  match get_signer(&mut context, identities) {
+    Some(signer) => {
+      context.add_signer(&signer)?;
+      context.sign_and_encrypt(&keys, object, &mut output).map_err(|err| VaultError::throw(&err.to_string()))?;
+    }
+
+    None => {
+      context.encrypt(&keys, object, &mut output).map_err(|err| VaultError::throw(&err.to_string()))?;
+    }
+  }
+
+  Ok(output)
+}
+
+/// Find the secret key used to sign data written to a vault.
+///
+/// Returns the first secret key able to sign among the provided
+/// identities, if any.
+fn get_signer(context: &mut Context, identities: &[String]) -> Option<Key> {
+  context.find_secret_keys(identities).ok()?.filter_map(Result::ok).find(Key::can_sign)
+}
+
+pub fn decrypt<'a, T>(data: T) -> Result<Vec<u8>, Box<dyn Error>>
+where
+  T: IntoData<'a>,
+{
+  let mut output = Vec::new();
+  get_context()?.decrypt(data, &mut output).map_err(|err| VaultError::throw(&err.to_string()))?;
+
+  Ok(output)
+}
+
+/// Decrypt data and verify its signature.
+///
+/// Returns the decrypted data, along with the signer of the first valid
+/// signature it carries, if any.
+pub fn decrypt_and_verify(data: &[u8]) -> Result<(Vec<u8>, Option<Signature>), Box<dyn Error>> {
+  let mut context = get_context()?;
+  let mut output = Vec::new();
+
+  let (_, verification) = context.decrypt_and_verify(data, &mut output).map_err(|err| VaultError::throw(&err.to_string()))?;
+  let fingerprint = verification
+    .signatures()
+    .filter(|signature| signature.status().is_ok())
+    .find_map(|signature| signature.fingerprint().ok().map(ToString::to_string));
+
+  let signature = fingerprint.map(|fingerprint| match context.get_key(&fingerprint) {
+    Ok(key) => Signature {
+      fingerprint: key.fingerprint().map(ToString::to_string).unwrap_or_else(|_| fingerprint.clone()),
+      signer: key.user_ids().next().and_then(|id| id.id().ok().map(ToString::to_string)).unwrap_or_else(|| fingerprint.clone()),
+    },
+    Err(_) => Signature {
+      signer: fingerprint.clone(),
+      fingerprint,
+    },
+  });
+
+  Ok((output, signature))
+}
+
+/// Encrypt a stream of data for all provided identities.
+///
+/// The plaintext is read and the ciphertext written as they are processed,
+/// so the data is never held in memory as a whole. Unlike
+/// [encrypt](fn.encrypt.html), the output is not armored.
+pub fn encrypt_stream<R, W>(identities: &[String], reader: R, writer: W) -> Result<(), Box<dyn Error>>
+where
+  R: Read + Send,
+  W: Write + Send,
+{
+  let mut context = get_context()?;
+  let keys = get_keys(&mut context, identities)?;
+
+  if keys.len() < identities.len() {
+    return Err(VaultError::throw("could not retrieve the public keys for all provided identities"));
+  }
+
+  let plaintext = Data::from_reader(reader).map_err(|err| VaultError::throw(&err.to_string()))?;
+  let ciphertext = Data::from_writer(writer).map_err(|err| VaultError::throw(&err.to_string()))?;
+
+  context.set_armor(false);
+  context.encrypt(&keys, plaintext, ciphertext).map_err(|err| VaultError::throw(&err.to_string()))?;
+
+  Ok(())
+}
+
+/// Decrypt a stream of data encrypted with
+/// [encrypt_stream](fn.encrypt_stream.html).
+pub fn decrypt_stream<R, W>(reader: R, writer: W) -> Result<(), Box<dyn Error>>
+where
+  R: Read + Send,
+  W: Write + Send,
+{
+  let ciphertext = Data::from_reader(reader).map_err(|err| VaultError::throw(&err.to_string()))?;
+  let plaintext = Data::from_writer(writer).map_err(|err| VaultError::throw(&err.to_string()))?;
+
+  get_context()?.decrypt(ciphertext, plaintext).map_err(|err| VaultError::throw(&err.to_string()))?;
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  #[test]
+  fn get_context() {
+    assert_eq!(super::get_context().is_ok(), true);
+  }
+
+  #[test]
+  fn get_keys() {
+    spec::setup();
+    let mut context = super::get_context().expect("could not get GPG context");
+
+    assert_eq!(super::get_keys(&mut context, &spec::get_test_identities()).is_ok(), true);
+
+    let keys = super::get_keys(&mut context, &spec::get_test_identities()).expect("could not get key");
+
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].fingerprint(), Ok(spec::GPG_FINGERPRINT));
+
+    assert_eq!(
+      keys[0].user_ids().filter(|id| id.email() == Ok(spec::GPG_IDENTITY)).collect::<Vec<gpgme::keys::UserId>>().is_empty(),
+      false
+    );
+  }
+
+  #[test]
+  fn get_author() {
+    spec::setup();
+
+    let author = super::get_author(&spec::get_test_identities()).expect("could not get author");
+
+    assert!(author.contains(spec::GPG_IDENTITY));
+    assert_eq!(super::get_author(&["unknown@example.com".to_string()]), None);
+  }
+
+  #[test]
+  fn encrypt_and_decrypt() {
+    let tmp = spec::setup();
+    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let data = "foobarhelloworld".as_bytes();
+    let ciphertext = super::encrypt(context.vault.get_identities(), data).expect("could not encrypt data");
+
+    assert_eq!(data.to_vec(), super::decrypt(ciphertext).expect("could not decrypt data"));
+  }
+
+  #[test]
+  fn sign_and_verify() {
+    let tmp = spec::setup();
+    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let ciphertext = super::encrypt(context.vault.get_identities(), b"foobar").expect("could not encrypt data");
+    let (plaintext, signature) = super::decrypt_and_verify(&ciphertext).expect("could not decrypt data");
+    let signature = signature.expect("data was not signed");
+
+    assert_eq!(plaintext, b"foobar");
+    assert_eq!(signature.fingerprint, spec::GPG_FINGERPRINT);
+    assert!(signature.signer.contains(spec::GPG_IDENTITY));
+  }
+
+  #[test]
+  fn encrypt_and_decrypt_stream() {
+    let tmp = spec::setup();
+    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let data = vec![42; 1024 * 1024];
+    let (mut ciphertext, mut plaintext) = (Vec::new(), Vec::new());
+
+    super::encrypt_stream(context.vault.get_identities(), data.as_slice(), &mut ciphertext).expect("could not encrypt data");
+    super::decrypt_stream(ciphertext.as_slice(), &mut plaintext).expect("could not decrypt data");
+
+    assert!(ciphertext.len() < data.len());
+    assert_eq!(plaintext, data);
+  }
+}
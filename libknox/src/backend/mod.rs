@@ -0,0 +1,160 @@
+//! Encryption backends protecting the content of a vault.
+//!
+//! The backend of a vault is chosen when it is created, and recorded in its
+//! metadata. The identities of the vault are the recipients every entry, blob
+//! and the metadata itself are encrypted for, and their format depends on the
+//! backend: GPG identities for [Gpg](gpg/struct.Gpg.html), and X25519 public
+//! keys for [Age](age/struct.Age.html). Vaults encrypted with
+//! [Passphrase](passphrase/struct.Passphrase.html) have no identities.
+
+pub mod age;
+pub mod gpg;
+pub mod passphrase;
+
+use std::error::Error;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::pb::*;
+
+/// Valid signature found on decrypted data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+  /// Fingerprint of the signing key.
+  pub fingerprint: String,
+  /// Description of the signer, such as the user ID of its key.
+  pub signer: String,
+}
+
+/// Encryption and decryption of the content of a vault.
+///
+/// A backend is instantiated when a vault is opened, and shared by all
+/// operations on it, including those running on other threads.
+pub trait Backend: Send + Sync {
+  /// The kind of backend, as recorded in the metadata of a vault.
+  fn kind(&self) -> BackendKind;
+
+  /// Check that data can be encrypted for a recipient.
+  fn check_recipient(&self, recipient: &str) -> Result<(), Box<dyn Error>>;
+
+  /// Describe the local identity modifying a vault, if it is one of the
+  /// provided recipients.
+  fn get_author(&self, recipients: &[String]) -> Option<String>;
+
+  /// List the recipients whose private keys are available locally, which a
+  /// vault must be encrypted for to be decrypted on this machine.
+  fn local_identities(&self) -> Vec<String> {
+    Vec::new()
+  }
+
+  /// Unencrypted parameters required to decrypt the vault, stored in the
+  /// `_knox.header` file next to its metadata.
+  fn header(&self) -> Option<Vec<u8>> {
+    None
+  }
+
+  /// Whether the backend signs the data it encrypts, so that it can be
+  /// verified against trusted signers.
+  fn can_sign(&self) -> bool {
+    false
+  }
+
+  /// Encrypt data for all provided recipients, signing it with the key of
+  /// the local identity if the backend supports it.
+  fn encrypt(&self, recipients: &[String], plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>>;
+
+  /// Decrypt data with one of the local identities.
+  fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>>;
+
+  /// Decrypt data, and return the valid signature it carries, if any.
+  ///
+  /// Backends that do not sign data never return a signature.
+  fn decrypt_and_verify(&self, ciphertext: &[u8]) -> Result<(Vec<u8>, Option<Signature>), Box<dyn Error>> {
+    Ok((self.decrypt(ciphertext)?, None))
+  }
+
+  /// Encrypt a stream of data for all provided recipients, without holding
+  /// it in memory as a whole.
+  fn encrypt_stream(&self, recipients: &[String], reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>>;
+
+  /// Decrypt a stream of data with one of the local identities, without
+  /// holding it in memory as a whole.
+  fn decrypt_stream(&self, reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>>;
+}
+
+/// Instantiate the backend of a given kind for a vault.
+///
+/// Backends relying on a passphrase read their parameters from the vault
+/// and retrieve the passphrase from the provider set with
+/// [set_passphrase_provider](passphrase/fn.set_passphrase_provider.html).
+///
+/// # Arguments
+///
+///  * `kind` - the kind of backend.
+///  * `path` - filesystem path where the vault is located.
+pub fn open<P>(kind: BackendKind, path: P) -> Result<Arc<dyn Backend>, Box<dyn Error>>
+where
+  P: AsRef<Path>,
+{
+  match kind {
+    BackendKind::GPG => Ok(Arc::new(self::gpg::Gpg)),
+    BackendKind::AGE => Ok(Arc::new(self::age::Age)),
+    BackendKind::PASSPHRASE => Ok(Arc::new(self::passphrase::Passphrase::open(path)?)),
+  }
+}
+
+/// Guess which kind of backend produced a ciphertext.
+///
+/// This is used to decrypt the metadata of a vault, which records its
+/// backend.
+pub fn detect(ciphertext: &[u8]) -> BackendKind {
+  if self::age::is_ciphertext(ciphertext) {
+    BackendKind::AGE
+  } else if self::passphrase::is_ciphertext(ciphertext) {
+    BackendKind::PASSPHRASE
+  } else {
+    BackendKind::GPG
+  }
+}
+
+impl BackendKind {
+  /// Parse the name of a backend.
+  pub fn parse(name: &str) -> Result<Self, Box<dyn Error>> {
+    match name {
+      "gpg" => Ok(BackendKind::GPG),
+      "age" => Ok(BackendKind::AGE),
+      "passphrase" => Ok(BackendKind::PASSPHRASE),
+      _ => Err(crate::util::VaultError::throw(&format!("unknown backend '{}', expected gpg, age or passphrase", name))),
+    }
+  }
+
+  /// The name of a backend.
+  pub fn name(self) -> &'static str {
+    match self {
+      BackendKind::GPG => "gpg",
+      BackendKind::AGE => "age",
+      BackendKind::PASSPHRASE => "passphrase",
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::*;
+
+  #[test]
+  fn detect() {
+    assert_eq!(backend::detect(b"-----BEGIN PGP MESSAGE-----"), BackendKind::GPG);
+    assert_eq!(backend::detect(b"-----BEGIN AGE ENCRYPTED FILE-----"), BackendKind::AGE);
+    assert_eq!(backend::detect(b"age-encryption.org/v1\n"), BackendKind::AGE);
+    assert_eq!(backend::detect(b"KNXP"), BackendKind::PASSPHRASE);
+  }
+
+  #[test]
+  fn parse() {
+    assert_eq!(BackendKind::parse("age").expect("could not parse backend"), BackendKind::AGE);
+    assert_eq!(BackendKind::GPG.name(), "gpg");
+    assert!(BackendKind::parse("pgp").is_err());
+  }
+}
@@ -0,0 +1,341 @@
+//! Encryption backend relying on a key derived from a passphrase.
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::sync::RwLock;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::stream::{DecryptorBE32, EncryptorBE32};
+use chacha20poly1305::aead::{KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key};
+use protobuf::{parse_from_bytes, Message};
+
+use super::Backend;
+use crate::pb::*;
+use crate::util::{self, VaultError};
+
+/// Provides the passphrase of a vault when it is opened.
+pub type PassphraseProvider = fn() -> Result<String, Box<dyn Error>>;
+
+const CIPHERTEXT_HEADER: &[u8] = b"KNXP";
+const NONCE_SIZE: usize = 7;
+const CHUNK_SIZE: usize = 64 * 1024;
+const TAG_SIZE: usize = 16;
+const SALT_SIZE: usize = 16;
+
+const DEFAULT_MEMORY: u32 = 64 * 1024;
+const DEFAULT_ITERATIONS: u32 = 3;
+const DEFAULT_PARALLELISM: u32 = 1;
+
+const MAX_MEMORY: u32 = 4 * 1024 * 1024;
+const MAX_ITERATIONS: u32 = 64;
+const MAX_PARALLELISM: u32 = 16;
+
+static PROVIDER: RwLock<Option<PassphraseProvider>> = RwLock::new(None);
+
+/// Set how the passphrase of a vault is obtained.
+///
+/// Opening a vault encrypted with a passphrase calls this function to
+/// retrieve it, for instance by prompting the user. No passphrase can be
+/// obtained until a provider is set.
+///
+/// # Arguments
+///
+///  * `provider` - the function returning the passphrase.
+pub fn set_passphrase_provider(provider: PassphraseProvider) {
+  if let Ok(mut current) = PROVIDER.write() {
+    *current = Some(provider);
+  }
+}
+
+/// Encryption backend relying on a key derived from a passphrase.
+///
+/// The key is derived with Argon2id, whose parameters and salt are stored
+/// unencrypted in the `_knox.header` file next to the metadata, and data is
+/// encrypted with ChaCha20-Poly1305, in chunks so that blobs can be
+/// processed as streams. The header is authenticated along with every chunk,
+/// so data cannot be decrypted with a modified header. A vault using this
+/// backend has no identities.
+pub struct Passphrase {
+  key: Key,
+  header: Vec<u8>,
+}
+
+impl Passphrase {
+  /// Derive a key from a passphrase, with a new salt and the default KDF
+  /// parameters.
+  ///
+  /// # Arguments
+  ///
+  ///  * `passphrase` - the passphrase protecting the vault.
+  pub fn create(passphrase: &str) -> Result<Self, Box<dyn Error>> {
+    let mut salt = vec![0; SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+
+    let header = Header {
+      kdf: KdfKind::ARGON2ID,
+      salt,
+      memory: DEFAULT_MEMORY,
+      iterations: DEFAULT_ITERATIONS,
+      parallelism: DEFAULT_PARALLELISM,
+      ..Header::default()
+    };
+
+    Self::derive(passphrase, header)
+  }
+
+  /// Derive the key of an existing vault, asking the passphrase provider for
+  /// its passphrase.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - filesystem path where the vault is located.
+  pub fn open<P>(path: P) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let header = fs::read(path.as_ref().join(util::HEADER_FILE)).map_err(|err| VaultError::throw(&format!("could not read the passphrase header of the vault: {}", err)))?;
+
+    let provider = PROVIDER.read().ok().and_then(|provider| *provider);
+    let passphrase = match provider {
+      Some(provider) => provider()?,
+      None => return Err(VaultError::throw("the vault is encrypted with a passphrase, but none was provided")),
+    };
+
+    Self::derive_from_bytes(&passphrase, header)
+  }
+
+  /// Derive a key from a passphrase and the KDF parameters of a header.
+  ///
+  /// Parameters above the supported bounds are rejected, so that a modified
+  /// header cannot exhaust the memory of the machine.
+  ///
+  /// # Arguments
+  ///
+  ///  * `passphrase` - the passphrase protecting the vault.
+  ///  * `header`     - the KDF parameters and salt.
+  pub fn derive(passphrase: &str, header: Header) -> Result<Self, Box<dyn Error>> {
+    Self::derive_from_bytes(passphrase, header.write_to_bytes()?)
+  }
+
+  // The header is kept as serialized in the header file, since it is
+  // authenticated as is.
+  fn derive_from_bytes(passphrase: &str, bytes: Vec<u8>) -> Result<Self, Box<dyn Error>> {
+    if passphrase.is_empty() {
+      return Err(VaultError::throw("the passphrase cannot be empty"));
+    }
+
+    let header = parse_from_bytes::<Header>(&bytes)?;
+
+    if header.memory > MAX_MEMORY || header.iterations > MAX_ITERATIONS || header.parallelism > MAX_PARALLELISM {
+      return Err(VaultError::throw(&format!(
+        "the KDF parameters of the vault exceed the supported bounds ({} KiB of memory, {} iterations and {} lanes)",
+        MAX_MEMORY, MAX_ITERATIONS, MAX_PARALLELISM
+      )));
+    }
+
+    let params = Params::new(header.memory, header.iterations, header.parallelism, Some(32)).map_err(|err| VaultError::throw(&format!("invalid KDF parameters: {}", err)))?;
+    let mut key = Key::default();
+
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+      .hash_password_into(passphrase.as_bytes(), header.get_salt(), &mut key)
+      .map_err(|err| VaultError::throw(&format!("could not derive key from passphrase: {}", err)))?;
+
+    Ok(Self { key, header: bytes })
+  }
+}
+
+impl Backend for Passphrase {
+  fn kind(&self) -> BackendKind {
+    BackendKind::PASSPHRASE
+  }
+
+  fn check_recipient(&self, _recipient: &str) -> Result<(), Box<dyn Error>> {
+    Err(VaultError::throw("vaults encrypted with a passphrase do not have identities"))
+  }
+
+  fn get_author(&self, _recipients: &[String]) -> Option<String> {
+    None
+  }
+
+  fn header(&self) -> Option<Vec<u8>> {
+    Some(self.header.clone())
+  }
+
+  fn encrypt(&self, recipients: &[String], plaintext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut ciphertext = Vec::new();
+
+    self.encrypt_stream(recipients, &mut &plaintext[..], &mut ciphertext)?;
+
+    Ok(ciphertext)
+  }
+
+  fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let mut plaintext = Vec::new();
+
+    self.decrypt_stream(&mut &ciphertext[..], &mut plaintext)?;
+
+    Ok(plaintext)
+  }
+
+  fn encrypt_stream(&self, _recipients: &[String], reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>> {
+    let mut nonce = [0; NONCE_SIZE];
+    OsRng.fill_bytes(&mut nonce);
+
+    writer.write_all(CIPHERTEXT_HEADER)?;
+    writer.write_all(&nonce)?;
+
+    let mut encryptor = EncryptorBE32::from_aead(ChaCha20Poly1305::new(&self.key), (&nonce).into());
+    let mut current = read_chunk(reader, CHUNK_SIZE)?;
+
+    loop {
+      let next = read_chunk(reader, CHUNK_SIZE)?;
+
+      if next.is_empty() {
+        writer.write_all(&encryptor.encrypt_last(self.payload(&current)).map_err(|_| VaultError::throw("could not encrypt data"))?)?;
+
+        return Ok(());
+      }
+
+      writer.write_all(&encryptor.encrypt_next(self.payload(&current)).map_err(|_| VaultError::throw("could not encrypt data"))?)?;
+      current = next;
+    }
+  }
+
+  fn decrypt_stream(&self, reader: &mut (dyn Read + Send), writer: &mut (dyn Write + Send)) -> Result<(), Box<dyn Error>> {
+    let mut header = [0; CIPHERTEXT_HEADER.len() + NONCE_SIZE];
+    reader.read_exact(&mut header).map_err(|_| VaultError::throw("data was not encrypted with a passphrase"))?;
+
+    if !is_ciphertext(&header) {
+      return Err(VaultError::throw("data was not encrypted with a passphrase"));
+    }
+
+    let mut decryptor = DecryptorBE32::from_aead(ChaCha20Poly1305::new(&self.key), header[CIPHERTEXT_HEADER.len()..].into());
+    let mut current = read_chunk(reader, CHUNK_SIZE + TAG_SIZE)?;
+
+    loop {
+      let next = read_chunk(reader, CHUNK_SIZE + TAG_SIZE)?;
+
+      if next.is_empty() {
+        writer.write_all(
+          &decryptor
+            .decrypt_last(self.payload(&current))
+            .map_err(|_| VaultError::throw("could not decrypt data, the passphrase may be incorrect"))?,
+        )?;
+
+        return Ok(());
+      }
+
+      writer.write_all(
+        &decryptor
+          .decrypt_next(self.payload(&current))
+          .map_err(|_| VaultError::throw("could not decrypt data, the passphrase may be incorrect"))?,
+      )?;
+      current = next;
+    }
+  }
+}
+
+impl Passphrase {
+  fn payload<'a>(&'a self, chunk: &'a [u8]) -> Payload<'a, 'a> {
+    Payload { msg: chunk, aad: &self.header }
+  }
+}
+
+pub(crate) fn is_ciphertext(data: &[u8]) -> bool {
+  data.starts_with(CIPHERTEXT_HEADER)
+}
+
+fn read_chunk(reader: &mut (dyn Read + Send), size: usize) -> io::Result<Vec<u8>> {
+  let mut chunk = Vec::with_capacity(size);
+  reader.take(size as u64).read_to_end(&mut chunk)?;
+
+  Ok(chunk)
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use protobuf::parse_from_bytes;
+
+  use super::Passphrase;
+  use crate::backend::{self, Backend};
+  use crate::*;
+
+  fn get_backend(passphrase: &str) -> Passphrase {
+    let header = Header {
+      salt: b"knoxknoxknoxknox".to_vec(),
+      memory: 1024,
+      iterations: 1,
+      parallelism: 1,
+      ..Header::default()
+    };
+
+    Passphrase::derive(passphrase, header).expect("could not derive key")
+  }
+
+  #[test]
+  fn encrypt_and_decrypt() {
+    let backend = get_backend("correct horse battery staple");
+
+    for size in &[0, 10, 64 * 1024, 200 * 1024] {
+      let data = vec![42; *size];
+      let ciphertext = backend.encrypt(&[], &data).expect("could not encrypt data");
+
+      assert_eq!(backend::detect(&ciphertext), BackendKind::PASSPHRASE);
+      assert_eq!(backend.decrypt(&ciphertext).expect("could not decrypt data"), data);
+      assert!(backend.decrypt(&ciphertext[..ciphertext.len() - 1]).is_err());
+    }
+  }
+
+  #[test]
+  fn wrong_passphrase() {
+    let ciphertext = get_backend("foo").encrypt(&[], b"secret").expect("could not encrypt data");
+
+    assert!(get_backend("bar").decrypt(&ciphertext).is_err());
+    assert!(Passphrase::derive("", Header::default()).is_err());
+  }
+
+  #[test]
+  fn header() {
+    let backend = get_backend("foo");
+    let ciphertext = backend.encrypt(&[], b"secret").expect("could not encrypt data");
+
+    let mut header = parse_from_bytes::<Header>(&backend.header().expect("could not get header")).expect("could not parse header");
+    header.set_memory(u32::MAX);
+
+    assert!(Passphrase::derive("foo", header).is_err());
+
+    let key = backend.key;
+    let tampered = Passphrase { key, header: b"tampered".to_vec() };
+
+    assert!(tampered.decrypt(&ciphertext).is_err());
+  }
+
+  #[test]
+  fn vault() {
+    let tmp = spec::setup();
+    super::set_passphrase_provider(|| Ok("correct horse battery staple".to_string()));
+
+    let mut context = VaultContext::create_with_passphrase(tmp.path(), "correct horse battery staple").expect("could not create vault");
+    context.write().expect("could not write vault");
+
+    assert!(tmp.path().join(util::HEADER_FILE).exists());
+
+    let mut context = VaultContext::open(tmp.path()).expect("could not open vault");
+    let mut entry = Entry::default();
+    entry.add_confidential_attribute("password", "foobar");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.get_backend(), BackendKind::PASSPHRASE);
+    assert!(retrieved.vault.get_identities().is_empty());
+    assert_eq!(retrieved.read_entry("foo/bar").expect("could not read entry").get_attributes(), entry.get_attributes());
+  }
+}
@@ -29,7 +29,8 @@
 //! information about what is stored inside the secrets.
 //!
 //! All files are marshalled with _Protocol Buffers_ and encrypted through
-//! _gpg-agent_, producing armored ciphertext.
+//! _gpg-agent_, producing armored ciphertext. Vaults can also be encrypted
+//! with _age_ keys, see the [backend](backend/index.html) module.
 //!
 //! # Example
 //!
@@ -75,12 +76,14 @@
 //! }
 //! ```
 
-pub mod gpg;
+pub mod backend;
 mod pb;
 mod util;
 
 mod vault;
 
+pub use crate::backend::gpg;
+pub use crate::backend::passphrase::set_passphrase_provider;
 pub use vault::*;
 
 #[cfg(test)]
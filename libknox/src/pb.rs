@@ -31,6 +31,12 @@ pub struct Vault {
     // message fields
     pub identities: ::protobuf::RepeatedField<::std::string::String>,
     pub index: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    pub version: u32,
+    pub tags: ::std::collections::HashMap<::std::string::String, Tags>,
+    pub templates: ::std::collections::HashMap<::std::string::String, Template>,
+    pub expirations: ::std::collections::HashMap<::std::string::String, u64>,
+    pub backend: BackendKind,
+    pub generation: u64,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
@@ -96,10 +102,2526 @@ impl Vault {
     pub fn take_index(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
         ::std::mem::replace(&mut self.index, ::std::collections::HashMap::new())
     }
+
+    // uint32 version = 3;
+
+
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+    pub fn clear_version(&mut self) {
+        self.version = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_version(&mut self, v: u32) {
+        self.version = v;
+    }
+
+    // repeated .Vault.TagsEntry tags = 4;
+
+
+    pub fn get_tags(&self) -> &::std::collections::HashMap<::std::string::String, Tags> {
+        &self.tags
+    }
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tags(&mut self, v: ::std::collections::HashMap<::std::string::String, Tags>) {
+        self.tags = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tags(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, Tags> {
+        &mut self.tags
+    }
+
+    // Take field
+    pub fn take_tags(&mut self) -> ::std::collections::HashMap<::std::string::String, Tags> {
+        ::std::mem::replace(&mut self.tags, ::std::collections::HashMap::new())
+    }
+
+    // repeated .Vault.TemplatesEntry templates = 5;
+
+
+    pub fn get_templates(&self) -> &::std::collections::HashMap<::std::string::String, Template> {
+        &self.templates
+    }
+    pub fn clear_templates(&mut self) {
+        self.templates.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_templates(&mut self, v: ::std::collections::HashMap<::std::string::String, Template>) {
+        self.templates = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_templates(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, Template> {
+        &mut self.templates
+    }
+
+    // Take field
+    pub fn take_templates(&mut self) -> ::std::collections::HashMap<::std::string::String, Template> {
+        ::std::mem::replace(&mut self.templates, ::std::collections::HashMap::new())
+    }
+
+    // repeated .Vault.ExpirationsEntry expirations = 6;
+
+
+    pub fn get_expirations(&self) -> &::std::collections::HashMap<::std::string::String, u64> {
+        &self.expirations
+    }
+    pub fn clear_expirations(&mut self) {
+        self.expirations.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expirations(&mut self, v: ::std::collections::HashMap<::std::string::String, u64>) {
+        self.expirations = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_expirations(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, u64> {
+        &mut self.expirations
+    }
+
+    // Take field
+    pub fn take_expirations(&mut self) -> ::std::collections::HashMap<::std::string::String, u64> {
+        ::std::mem::replace(&mut self.expirations, ::std::collections::HashMap::new())
+    }
+
+    // .BackendKind backend = 7;
+
+
+    pub fn get_backend(&self) -> BackendKind {
+        self.backend
+    }
+    pub fn clear_backend(&mut self) {
+        self.backend = BackendKind::GPG;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_backend(&mut self, v: BackendKind) {
+        self.backend = v;
+    }
+
+    // uint64 generation = 8;
+
+
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+    pub fn clear_generation(&mut self) {
+        self.generation = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_generation(&mut self, v: u64) {
+        self.generation = v;
+    }
+}
+
+impl ::protobuf::Message for Vault {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.identities)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.index)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.version = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Tags>>(wire_type, is, &mut self.tags)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Template>>(wire_type, is, &mut self.templates)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint64>(wire_type, is, &mut self.expirations)?;
+                },
+                7 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.backend, 7, &mut self.unknown_fields)?
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.generation = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.identities {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.index);
+        if self.version != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.version, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Tags>>(4, &self.tags);
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Template>>(5, &self.templates);
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint64>(6, &self.expirations);
+        if self.backend != BackendKind::GPG {
+            my_size += ::protobuf::rt::enum_size(7, self.backend);
+        }
+        if self.generation != 0 {
+            my_size += ::protobuf::rt::value_size(8, self.generation, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.identities {
+            os.write_string(1, &v)?;
+        };
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.index, os)?;
+        if self.version != 0 {
+            os.write_uint32(3, self.version)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Tags>>(4, &self.tags, os)?;
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Template>>(5, &self.templates, os)?;
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint64>(6, &self.expirations, os)?;
+        if self.backend != BackendKind::GPG {
+            os.write_enum(7, self.backend.value())?;
+        }
+        if self.generation != 0 {
+            os.write_uint64(8, self.generation)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Vault {
+        Vault::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "identities",
+                    |m: &Vault| { &m.identities },
+                    |m: &mut Vault| { &mut m.identities },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "index",
+                    |m: &Vault| { &m.index },
+                    |m: &mut Vault| { &mut m.index },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "version",
+                    |m: &Vault| { &m.version },
+                    |m: &mut Vault| { &mut m.version },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Tags>>(
+                    "tags",
+                    |m: &Vault| { &m.tags },
+                    |m: &mut Vault| { &mut m.tags },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Template>>(
+                    "templates",
+                    |m: &Vault| { &m.templates },
+                    |m: &mut Vault| { &mut m.templates },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeUint64>(
+                    "expirations",
+                    |m: &Vault| { &m.expirations },
+                    |m: &mut Vault| { &mut m.expirations },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<BackendKind>>(
+                    "backend",
+                    |m: &Vault| { &m.backend },
+                    |m: &mut Vault| { &mut m.backend },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "generation",
+                    |m: &Vault| { &m.generation },
+                    |m: &mut Vault| { &mut m.generation },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Vault>(
+                    "Vault",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Vault {
+        static mut instance: ::protobuf::lazy::Lazy<Vault> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(Vault::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Vault {
+    fn clear(&mut self) {
+        self.identities.clear();
+        self.index.clear();
+        self.version = 0;
+        self.tags.clear();
+        self.templates.clear();
+        self.expirations.clear();
+        self.backend = BackendKind::GPG;
+        self.generation = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Vault {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Vault {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Header {
+    // message fields
+    pub kdf: KdfKind,
+    pub salt: ::std::vec::Vec<u8>,
+    pub memory: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Header {
+    fn default() -> &'a Header {
+        <Header as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Header {
+    pub fn new() -> Header {
+        ::std::default::Default::default()
+    }
+
+    // .KdfKind kdf = 1;
+
+
+    pub fn get_kdf(&self) -> KdfKind {
+        self.kdf
+    }
+    pub fn clear_kdf(&mut self) {
+        self.kdf = KdfKind::ARGON2ID;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_kdf(&mut self, v: KdfKind) {
+        self.kdf = v;
+    }
+
+    // bytes salt = 2;
+
+
+    pub fn get_salt(&self) -> &[u8] {
+        &self.salt
+    }
+    pub fn clear_salt(&mut self) {
+        self.salt.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_salt(&mut self, v: ::std::vec::Vec<u8>) {
+        self.salt = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_salt(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.salt
+    }
+
+    // Take field
+    pub fn take_salt(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.salt, ::std::vec::Vec::new())
+    }
+
+    // uint32 memory = 3;
+
+
+    pub fn get_memory(&self) -> u32 {
+        self.memory
+    }
+    pub fn clear_memory(&mut self) {
+        self.memory = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memory(&mut self, v: u32) {
+        self.memory = v;
+    }
+
+    // uint32 iterations = 4;
+
+
+    pub fn get_iterations(&self) -> u32 {
+        self.iterations
+    }
+    pub fn clear_iterations(&mut self) {
+        self.iterations = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_iterations(&mut self, v: u32) {
+        self.iterations = v;
+    }
+
+    // uint32 parallelism = 5;
+
+
+    pub fn get_parallelism(&self) -> u32 {
+        self.parallelism
+    }
+    pub fn clear_parallelism(&mut self) {
+        self.parallelism = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_parallelism(&mut self, v: u32) {
+        self.parallelism = v;
+    }
+}
+
+impl ::protobuf::Message for Header {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.kdf, 1, &mut self.unknown_fields)?
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.salt)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.memory = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.iterations = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.parallelism = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if self.kdf != KdfKind::ARGON2ID {
+            my_size += ::protobuf::rt::enum_size(1, self.kdf);
+        }
+        if !self.salt.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(2, &self.salt);
+        }
+        if self.memory != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.memory, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.iterations != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.iterations, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.parallelism != 0 {
+            my_size += ::protobuf::rt::value_size(5, self.parallelism, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if self.kdf != KdfKind::ARGON2ID {
+            os.write_enum(1, self.kdf.value())?;
+        }
+        if !self.salt.is_empty() {
+            os.write_bytes(2, &self.salt)?;
+        }
+        if self.memory != 0 {
+            os.write_uint32(3, self.memory)?;
+        }
+        if self.iterations != 0 {
+            os.write_uint32(4, self.iterations)?;
+        }
+        if self.parallelism != 0 {
+            os.write_uint32(5, self.parallelism)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Header {
+        Header::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<KdfKind>>(
+                    "kdf",
+                    |m: &Header| { &m.kdf },
+                    |m: &mut Header| { &mut m.kdf },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "salt",
+                    |m: &Header| { &m.salt },
+                    |m: &mut Header| { &mut m.salt },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "memory",
+                    |m: &Header| { &m.memory },
+                    |m: &mut Header| { &mut m.memory },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "iterations",
+                    |m: &Header| { &m.iterations },
+                    |m: &mut Header| { &mut m.iterations },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "parallelism",
+                    |m: &Header| { &m.parallelism },
+                    |m: &mut Header| { &mut m.parallelism },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Header>(
+                    "Header",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Header {
+        static mut instance: ::protobuf::lazy::Lazy<Header> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(Header::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Header {
+    fn clear(&mut self) {
+        self.kdf = KdfKind::ARGON2ID;
+        self.salt.clear();
+        self.memory = 0;
+        self.iterations = 0;
+        self.parallelism = 0;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Header {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Header {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct LocalConfig {
+    // message fields
+    pub trusted_signers: ::protobuf::RepeatedField<::std::string::String>,
+    pub signature_policy: SignaturePolicy,
+    pub generation: u64,
+    pub rollback_policy: RollbackPolicy,
+    pub git: ::protobuf::SingularPtrField<GitConfig>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a LocalConfig {
+    fn default() -> &'a LocalConfig {
+        <LocalConfig as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl LocalConfig {
+    pub fn new() -> LocalConfig {
+        ::std::default::Default::default()
+    }
+
+    // repeated string trusted_signers = 1;
+
+
+    pub fn get_trusted_signers(&self) -> &[::std::string::String] {
+        &self.trusted_signers
+    }
+    pub fn clear_trusted_signers(&mut self) {
+        self.trusted_signers.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_trusted_signers(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.trusted_signers = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_trusted_signers(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.trusted_signers
+    }
+
+    // Take field
+    pub fn take_trusted_signers(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.trusted_signers, ::protobuf::RepeatedField::new())
+    }
+
+    // .SignaturePolicy signature_policy = 2;
+
+
+    pub fn get_signature_policy(&self) -> SignaturePolicy {
+        self.signature_policy
+    }
+    pub fn clear_signature_policy(&mut self) {
+        self.signature_policy = SignaturePolicy::WARN_UNSIGNED;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_signature_policy(&mut self, v: SignaturePolicy) {
+        self.signature_policy = v;
+    }
+
+    // uint64 generation = 3;
+
+
+    pub fn get_generation(&self) -> u64 {
+        self.generation
+    }
+    pub fn clear_generation(&mut self) {
+        self.generation = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_generation(&mut self, v: u64) {
+        self.generation = v;
+    }
+
+    // .RollbackPolicy rollback_policy = 4;
+
+
+    pub fn get_rollback_policy(&self) -> RollbackPolicy {
+        self.rollback_policy
+    }
+    pub fn clear_rollback_policy(&mut self) {
+        self.rollback_policy = RollbackPolicy::WARN_ROLLBACK;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rollback_policy(&mut self, v: RollbackPolicy) {
+        self.rollback_policy = v;
+    }
+
+    // .GitConfig git = 5;
+
+
+    pub fn get_git(&self) -> &GitConfig {
+        self.git.as_ref().unwrap_or_else(|| GitConfig::default_instance())
+    }
+    pub fn clear_git(&mut self) {
+        self.git.clear();
+    }
+
+    pub fn has_git(&self) -> bool {
+        self.git.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_git(&mut self, v: GitConfig) {
+        self.git = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_git(&mut self) -> &mut GitConfig {
+        if self.git.is_none() {
+            self.git.set_default();
+        }
+        self.git.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_git(&mut self) -> GitConfig {
+        self.git.take().unwrap_or_else(|| GitConfig::new())
+    }
+}
+
+impl ::protobuf::Message for LocalConfig {
+    fn is_initialized(&self) -> bool {
+        for v in &self.git {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.trusted_signers)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.signature_policy, 2, &mut self.unknown_fields)?
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.generation = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.rollback_policy, 4, &mut self.unknown_fields)?
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.git)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.trusted_signers {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        if self.signature_policy != SignaturePolicy::WARN_UNSIGNED {
+            my_size += ::protobuf::rt::enum_size(2, self.signature_policy);
+        }
+        if self.generation != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.generation, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.rollback_policy != RollbackPolicy::WARN_ROLLBACK {
+            my_size += ::protobuf::rt::enum_size(4, self.rollback_policy);
+        }
+        if let Some(ref v) = self.git.as_ref() {
+            let len = v.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.trusted_signers {
+            os.write_string(1, &v)?;
+        };
+        if self.signature_policy != SignaturePolicy::WARN_UNSIGNED {
+            os.write_enum(2, self.signature_policy.value())?;
+        }
+        if self.generation != 0 {
+            os.write_uint64(3, self.generation)?;
+        }
+        if self.rollback_policy != RollbackPolicy::WARN_ROLLBACK {
+            os.write_enum(4, self.rollback_policy.value())?;
+        }
+        if let Some(ref v) = self.git.as_ref() {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> LocalConfig {
+        LocalConfig::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "trusted_signers",
+                    |m: &LocalConfig| { &m.trusted_signers },
+                    |m: &mut LocalConfig| { &mut m.trusted_signers },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<SignaturePolicy>>(
+                    "signature_policy",
+                    |m: &LocalConfig| { &m.signature_policy },
+                    |m: &mut LocalConfig| { &mut m.signature_policy },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "generation",
+                    |m: &LocalConfig| { &m.generation },
+                    |m: &mut LocalConfig| { &mut m.generation },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<RollbackPolicy>>(
+                    "rollback_policy",
+                    |m: &LocalConfig| { &m.rollback_policy },
+                    |m: &mut LocalConfig| { &mut m.rollback_policy },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<GitConfig>>(
+                    "git",
+                    |m: &LocalConfig| { &m.git },
+                    |m: &mut LocalConfig| { &mut m.git },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<LocalConfig>(
+                    "LocalConfig",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static LocalConfig {
+        static mut instance: ::protobuf::lazy::Lazy<LocalConfig> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(LocalConfig::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for LocalConfig {
+    fn clear(&mut self) {
+        self.trusted_signers.clear();
+        self.signature_policy = SignaturePolicy::WARN_UNSIGNED;
+        self.generation = 0;
+        self.rollback_policy = RollbackPolicy::WARN_ROLLBACK;
+        self.git.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for LocalConfig {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for LocalConfig {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct GitConfig {
+    // message fields
+    pub remote: ::std::string::String,
+    pub branch: ::std::string::String,
+    pub auth: GitAuth,
+    pub credential: ::std::string::String,
+    pub username: ::std::string::String,
+    pub attribute: ::std::string::String,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a GitConfig {
+    fn default() -> &'a GitConfig {
+        <GitConfig as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl GitConfig {
+    pub fn new() -> GitConfig {
+        ::std::default::Default::default()
+    }
+
+    // string remote = 1;
+
+
+    pub fn get_remote(&self) -> &str {
+        &self.remote
+    }
+    pub fn clear_remote(&mut self) {
+        self.remote.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_remote(&mut self, v: ::std::string::String) {
+        self.remote = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_remote(&mut self) -> &mut ::std::string::String {
+        &mut self.remote
+    }
+
+    // Take field
+    pub fn take_remote(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.remote, ::std::string::String::new())
+    }
+
+    // string branch = 2;
+
+
+    pub fn get_branch(&self) -> &str {
+        &self.branch
+    }
+    pub fn clear_branch(&mut self) {
+        self.branch.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_branch(&mut self, v: ::std::string::String) {
+        self.branch = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_branch(&mut self) -> &mut ::std::string::String {
+        &mut self.branch
+    }
+
+    // Take field
+    pub fn take_branch(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.branch, ::std::string::String::new())
+    }
+
+    // .GitAuth auth = 3;
+
+
+    pub fn get_auth(&self) -> GitAuth {
+        self.auth
+    }
+    pub fn clear_auth(&mut self) {
+        self.auth = GitAuth::AGENT;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_auth(&mut self, v: GitAuth) {
+        self.auth = v;
+    }
+
+    // string credential = 4;
+
+
+    pub fn get_credential(&self) -> &str {
+        &self.credential
+    }
+    pub fn clear_credential(&mut self) {
+        self.credential.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_credential(&mut self, v: ::std::string::String) {
+        self.credential = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_credential(&mut self) -> &mut ::std::string::String {
+        &mut self.credential
+    }
+
+    // Take field
+    pub fn take_credential(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.credential, ::std::string::String::new())
+    }
+
+    // string username = 5;
+
+
+    pub fn get_username(&self) -> &str {
+        &self.username
+    }
+    pub fn clear_username(&mut self) {
+        self.username.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_username(&mut self, v: ::std::string::String) {
+        self.username = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_username(&mut self) -> &mut ::std::string::String {
+        &mut self.username
+    }
+
+    // Take field
+    pub fn take_username(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.username, ::std::string::String::new())
+    }
+
+    // string attribute = 6;
+
+
+    pub fn get_attribute(&self) -> &str {
+        &self.attribute
+    }
+    pub fn clear_attribute(&mut self) {
+        self.attribute.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_attribute(&mut self, v: ::std::string::String) {
+        self.attribute = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_attribute(&mut self) -> &mut ::std::string::String {
+        &mut self.attribute
+    }
+
+    // Take field
+    pub fn take_attribute(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.attribute, ::std::string::String::new())
+    }
+}
+
+impl ::protobuf::Message for GitConfig {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.remote)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.branch)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.auth, 3, &mut self.unknown_fields)?
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.credential)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.username)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.attribute)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.remote.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.remote);
+        }
+        if !self.branch.is_empty() {
+            my_size += ::protobuf::rt::string_size(2, &self.branch);
+        }
+        if self.auth != GitAuth::AGENT {
+            my_size += ::protobuf::rt::enum_size(3, self.auth);
+        }
+        if !self.credential.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.credential);
+        }
+        if !self.username.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.username);
+        }
+        if !self.attribute.is_empty() {
+            my_size += ::protobuf::rt::string_size(6, &self.attribute);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.remote.is_empty() {
+            os.write_string(1, &self.remote)?;
+        }
+        if !self.branch.is_empty() {
+            os.write_string(2, &self.branch)?;
+        }
+        if self.auth != GitAuth::AGENT {
+            os.write_enum(3, self.auth.value())?;
+        }
+        if !self.credential.is_empty() {
+            os.write_string(4, &self.credential)?;
+        }
+        if !self.username.is_empty() {
+            os.write_string(5, &self.username)?;
+        }
+        if !self.attribute.is_empty() {
+            os.write_string(6, &self.attribute)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> GitConfig {
+        GitConfig::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "remote",
+                    |m: &GitConfig| { &m.remote },
+                    |m: &mut GitConfig| { &mut m.remote },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "branch",
+                    |m: &GitConfig| { &m.branch },
+                    |m: &mut GitConfig| { &mut m.branch },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<GitAuth>>(
+                    "auth",
+                    |m: &GitConfig| { &m.auth },
+                    |m: &mut GitConfig| { &mut m.auth },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "credential",
+                    |m: &GitConfig| { &m.credential },
+                    |m: &mut GitConfig| { &mut m.credential },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "username",
+                    |m: &GitConfig| { &m.username },
+                    |m: &mut GitConfig| { &mut m.username },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "attribute",
+                    |m: &GitConfig| { &m.attribute },
+                    |m: &mut GitConfig| { &mut m.attribute },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<GitConfig>(
+                    "GitConfig",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static GitConfig {
+        static mut instance: ::protobuf::lazy::Lazy<GitConfig> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(GitConfig::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for GitConfig {
+    fn clear(&mut self) {
+        self.remote.clear();
+        self.branch.clear();
+        self.auth = GitAuth::AGENT;
+        self.credential.clear();
+        self.username.clear();
+        self.attribute.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for GitConfig {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GitConfig {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Template {
+    // message fields
+    pub description: ::std::string::String,
+    pub fields: ::protobuf::RepeatedField<TemplateField>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Template {
+    fn default() -> &'a Template {
+        <Template as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Template {
+    pub fn new() -> Template {
+        ::std::default::Default::default()
+    }
+
+    // string description = 1;
+
+
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+    pub fn clear_description(&mut self) {
+        self.description.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_description(&mut self, v: ::std::string::String) {
+        self.description = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_description(&mut self) -> &mut ::std::string::String {
+        &mut self.description
+    }
+
+    // Take field
+    pub fn take_description(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.description, ::std::string::String::new())
+    }
+
+    // repeated .TemplateField fields = 2;
+
+
+    pub fn get_fields(&self) -> &[TemplateField] {
+        &self.fields
+    }
+    pub fn clear_fields(&mut self) {
+        self.fields.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_fields(&mut self, v: ::protobuf::RepeatedField<TemplateField>) {
+        self.fields = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_fields(&mut self) -> &mut ::protobuf::RepeatedField<TemplateField> {
+        &mut self.fields
+    }
+
+    // Take field
+    pub fn take_fields(&mut self) -> ::protobuf::RepeatedField<TemplateField> {
+        ::std::mem::replace(&mut self.fields, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for Template {
+    fn is_initialized(&self) -> bool {
+        for v in &self.fields {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.description)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.fields)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.description.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.description);
+        }
+        for value in &self.fields {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.description.is_empty() {
+            os.write_string(1, &self.description)?;
+        }
+        for v in &self.fields {
+            os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Template {
+        Template::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "description",
+                    |m: &Template| { &m.description },
+                    |m: &mut Template| { &mut m.description },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<TemplateField>>(
+                    "fields",
+                    |m: &Template| { &m.fields },
+                    |m: &mut Template| { &mut m.fields },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Template>(
+                    "Template",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Template {
+        static mut instance: ::protobuf::lazy::Lazy<Template> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(Template::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Template {
+    fn clear(&mut self) {
+        self.description.clear();
+        self.fields.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Template {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Template {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct TemplateField {
+    // message fields
+    pub name: ::std::string::String,
+    pub kind: AttributeKind,
+    pub optional: bool,
+    pub confidential: bool,
+    pub generate: bool,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a TemplateField {
+    fn default() -> &'a TemplateField {
+        <TemplateField as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl TemplateField {
+    pub fn new() -> TemplateField {
+        ::std::default::Default::default()
+    }
+
+    // string name = 1;
+
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        &mut self.name
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.name, ::std::string::String::new())
+    }
+
+    // .AttributeKind kind = 2;
+
+
+    pub fn get_kind(&self) -> AttributeKind {
+        self.kind
+    }
+    pub fn clear_kind(&mut self) {
+        self.kind = AttributeKind::GENERIC;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_kind(&mut self, v: AttributeKind) {
+        self.kind = v;
+    }
+
+    // bool optional = 3;
+
+
+    pub fn get_optional(&self) -> bool {
+        self.optional
+    }
+    pub fn clear_optional(&mut self) {
+        self.optional = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_optional(&mut self, v: bool) {
+        self.optional = v;
+    }
+
+    // bool confidential = 4;
+
+
+    pub fn get_confidential(&self) -> bool {
+        self.confidential
+    }
+    pub fn clear_confidential(&mut self) {
+        self.confidential = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_confidential(&mut self, v: bool) {
+        self.confidential = v;
+    }
+
+    // bool generate = 5;
+
+
+    pub fn get_generate(&self) -> bool {
+        self.generate
+    }
+    pub fn clear_generate(&mut self) {
+        self.generate = false;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_generate(&mut self, v: bool) {
+        self.generate = v;
+    }
+}
+
+impl ::protobuf::Message for TemplateField {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.kind, 2, &mut self.unknown_fields)?
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.optional = tmp;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.confidential = tmp;
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.generate = tmp;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if !self.name.is_empty() {
+            my_size += ::protobuf::rt::string_size(1, &self.name);
+        }
+        if self.kind != AttributeKind::GENERIC {
+            my_size += ::protobuf::rt::enum_size(2, self.kind);
+        }
+        if self.optional != false {
+            my_size += 2;
+        }
+        if self.confidential != false {
+            my_size += 2;
+        }
+        if self.generate != false {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        if !self.name.is_empty() {
+            os.write_string(1, &self.name)?;
+        }
+        if self.kind != AttributeKind::GENERIC {
+            os.write_enum(2, self.kind.value())?;
+        }
+        if self.optional != false {
+            os.write_bool(3, self.optional)?;
+        }
+        if self.confidential != false {
+            os.write_bool(4, self.confidential)?;
+        }
+        if self.generate != false {
+            os.write_bool(5, self.generate)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> TemplateField {
+        TemplateField::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "name",
+                    |m: &TemplateField| { &m.name },
+                    |m: &mut TemplateField| { &mut m.name },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<AttributeKind>>(
+                    "kind",
+                    |m: &TemplateField| { &m.kind },
+                    |m: &mut TemplateField| { &mut m.kind },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "optional",
+                    |m: &TemplateField| { &m.optional },
+                    |m: &mut TemplateField| { &mut m.optional },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "confidential",
+                    |m: &TemplateField| { &m.confidential },
+                    |m: &mut TemplateField| { &mut m.confidential },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "generate",
+                    |m: &TemplateField| { &m.generate },
+                    |m: &mut TemplateField| { &mut m.generate },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<TemplateField>(
+                    "TemplateField",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static TemplateField {
+        static mut instance: ::protobuf::lazy::Lazy<TemplateField> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(TemplateField::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for TemplateField {
+    fn clear(&mut self) {
+        self.name.clear();
+        self.kind = AttributeKind::GENERIC;
+        self.optional = false;
+        self.confidential = false;
+        self.generate = false;
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for TemplateField {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for TemplateField {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Tags {
+    // message fields
+    pub tags: ::protobuf::RepeatedField<::std::string::String>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Tags {
+    fn default() -> &'a Tags {
+        <Tags as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Tags {
+    pub fn new() -> Tags {
+        ::std::default::Default::default()
+    }
+
+    // repeated string tags = 1;
+
+
+    pub fn get_tags(&self) -> &[::std::string::String] {
+        &self.tags
+    }
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tags(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.tags = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tags(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.tags
+    }
+
+    // Take field
+    pub fn take_tags(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.tags, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for Tags {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.tags)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.tags {
+            my_size += ::protobuf::rt::string_size(1, &value);
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.tags {
+            os.write_string(1, &v)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Tags {
+        Tags::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "tags",
+                    |m: &Tags| { &m.tags },
+                    |m: &mut Tags| { &mut m.tags },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Tags>(
+                    "Tags",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Tags {
+        static mut instance: ::protobuf::lazy::Lazy<Tags> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(Tags::new)
+        }
+    }
+}
+
+impl ::protobuf::Clear for Tags {
+    fn clear(&mut self) {
+        self.tags.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Tags {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Tags {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Entry {
+    // message fields
+    pub attributes: ::std::collections::HashMap<::std::string::String, Attribute>,
+    pub created: u64,
+    pub modified: u64,
+    pub author: ::std::string::String,
+    pub history: ::std::collections::HashMap<::std::string::String, History>,
+    pub tags: ::protobuf::RepeatedField<::std::string::String>,
+    pub expires: u64,
+    pub location: ::std::string::String,
+    pub totp: ::protobuf::SingularPtrField<TotpConfig>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a Entry {
+    fn default() -> &'a Entry {
+        <Entry as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl Entry {
+    pub fn new() -> Entry {
+        ::std::default::Default::default()
+    }
+
+    // repeated .Entry.AttributesEntry attributes = 1;
+
+
+    pub fn get_attributes(&self) -> &::std::collections::HashMap<::std::string::String, Attribute> {
+        &self.attributes
+    }
+    pub fn clear_attributes(&mut self) {
+        self.attributes.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_attributes(&mut self, v: ::std::collections::HashMap<::std::string::String, Attribute>) {
+        self.attributes = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_attributes(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, Attribute> {
+        &mut self.attributes
+    }
+
+    // Take field
+    pub fn take_attributes(&mut self) -> ::std::collections::HashMap<::std::string::String, Attribute> {
+        ::std::mem::replace(&mut self.attributes, ::std::collections::HashMap::new())
+    }
+
+    // uint64 created = 2;
+
+
+    pub fn get_created(&self) -> u64 {
+        self.created
+    }
+    pub fn clear_created(&mut self) {
+        self.created = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_created(&mut self, v: u64) {
+        self.created = v;
+    }
+
+    // uint64 modified = 3;
+
+
+    pub fn get_modified(&self) -> u64 {
+        self.modified
+    }
+    pub fn clear_modified(&mut self) {
+        self.modified = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_modified(&mut self, v: u64) {
+        self.modified = v;
+    }
+
+    // string author = 4;
+
+
+    pub fn get_author(&self) -> &str {
+        &self.author
+    }
+    pub fn clear_author(&mut self) {
+        self.author.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_author(&mut self, v: ::std::string::String) {
+        self.author = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_author(&mut self) -> &mut ::std::string::String {
+        &mut self.author
+    }
+
+    // Take field
+    pub fn take_author(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.author, ::std::string::String::new())
+    }
+
+    // repeated .Entry.HistoryEntry history = 5;
+
+
+    pub fn get_history(&self) -> &::std::collections::HashMap<::std::string::String, History> {
+        &self.history
+    }
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_history(&mut self, v: ::std::collections::HashMap<::std::string::String, History>) {
+        self.history = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_history(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, History> {
+        &mut self.history
+    }
+
+    // Take field
+    pub fn take_history(&mut self) -> ::std::collections::HashMap<::std::string::String, History> {
+        ::std::mem::replace(&mut self.history, ::std::collections::HashMap::new())
+    }
+
+    // repeated string tags = 6;
+
+
+    pub fn get_tags(&self) -> &[::std::string::String] {
+        &self.tags
+    }
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tags(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.tags = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tags(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.tags
+    }
+
+    // Take field
+    pub fn take_tags(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.tags, ::protobuf::RepeatedField::new())
+    }
+
+    // uint64 expires = 7;
+
+
+    pub fn get_expires(&self) -> u64 {
+        self.expires
+    }
+    pub fn clear_expires(&mut self) {
+        self.expires = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expires(&mut self, v: u64) {
+        self.expires = v;
+    }
+
+    // string location = 8;
+
+
+    pub fn get_location(&self) -> &str {
+        &self.location
+    }
+    pub fn clear_location(&mut self) {
+        self.location.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_location(&mut self, v: ::std::string::String) {
+        self.location = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_location(&mut self) -> &mut ::std::string::String {
+        &mut self.location
+    }
+
+    // Take field
+    pub fn take_location(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.location, ::std::string::String::new())
+    }
+
+    // .TotpConfig totp = 100;
+
+
+    pub fn get_totp(&self) -> &TotpConfig {
+        self.totp.as_ref().unwrap_or_else(|| TotpConfig::default_instance())
+    }
+    pub fn clear_totp(&mut self) {
+        self.totp.clear();
+    }
+
+    pub fn has_totp(&self) -> bool {
+        self.totp.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_totp(&mut self, v: TotpConfig) {
+        self.totp = ::protobuf::SingularPtrField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_totp(&mut self) -> &mut TotpConfig {
+        if self.totp.is_none() {
+            self.totp.set_default();
+        }
+        self.totp.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_totp(&mut self) -> TotpConfig {
+        self.totp.take().unwrap_or_else(|| TotpConfig::new())
+    }
+}
+
+impl ::protobuf::Message for Entry {
+    fn is_initialized(&self) -> bool {
+        for v in &self.totp {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(wire_type, is, &mut self.attributes)?;
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.created = tmp;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.modified = tmp;
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.author)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<History>>(wire_type, is, &mut self.history)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.tags)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.expires = tmp;
+                },
+                8 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.location)?;
+                },
+                100 => {
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.totp)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(1, &self.attributes);
+        if self.created != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.created, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if self.modified != 0 {
+            my_size += ::protobuf::rt::value_size(3, self.modified, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.author.is_empty() {
+            my_size += ::protobuf::rt::string_size(4, &self.author);
+        }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<History>>(5, &self.history);
+        for value in &self.tags {
+            my_size += ::protobuf::rt::string_size(6, &value);
+        };
+        if self.expires != 0 {
+            my_size += ::protobuf::rt::value_size(7, self.expires, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.location.is_empty() {
+            my_size += ::protobuf::rt::string_size(8, &self.location);
+        }
+        if let Some(ref v) = self.totp.as_ref() {
+            let len = v.compute_size();
+            my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(1, &self.attributes, os)?;
+        if self.created != 0 {
+            os.write_uint64(2, self.created)?;
+        }
+        if self.modified != 0 {
+            os.write_uint64(3, self.modified)?;
+        }
+        if !self.author.is_empty() {
+            os.write_string(4, &self.author)?;
+        }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<History>>(5, &self.history, os)?;
+        for v in &self.tags {
+            os.write_string(6, &v)?;
+        };
+        if self.expires != 0 {
+            os.write_uint64(7, self.expires)?;
+        }
+        if !self.location.is_empty() {
+            os.write_string(8, &self.location)?;
+        }
+        if let Some(ref v) = self.totp.as_ref() {
+            os.write_tag(100, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &dyn (::std::any::Any) {
+        self as &dyn (::std::any::Any)
+    }
+    fn as_any_mut(&mut self) -> &mut dyn (::std::any::Any) {
+        self as &mut dyn (::std::any::Any)
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<dyn (::std::any::Any)> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        Self::descriptor_static()
+    }
+
+    fn new() -> Entry {
+        Entry::new()
+    }
+
+    fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(
+                    "attributes",
+                    |m: &Entry| { &m.attributes },
+                    |m: &mut Entry| { &mut m.attributes },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "created",
+                    |m: &Entry| { &m.created },
+                    |m: &mut Entry| { &mut m.created },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "modified",
+                    |m: &Entry| { &m.modified },
+                    |m: &mut Entry| { &mut m.modified },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "author",
+                    |m: &Entry| { &m.author },
+                    |m: &mut Entry| { &mut m.author },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<History>>(
+                    "history",
+                    |m: &Entry| { &m.history },
+                    |m: &mut Entry| { &mut m.history },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "tags",
+                    |m: &Entry| { &m.tags },
+                    |m: &mut Entry| { &mut m.tags },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "expires",
+                    |m: &Entry| { &m.expires },
+                    |m: &mut Entry| { &mut m.expires },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "location",
+                    |m: &Entry| { &m.location },
+                    |m: &mut Entry| { &mut m.location },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<TotpConfig>>(
+                    "totp",
+                    |m: &Entry| { &m.totp },
+                    |m: &mut Entry| { &mut m.totp },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Entry>(
+                    "Entry",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+
+    fn default_instance() -> &'static Entry {
+        static mut instance: ::protobuf::lazy::Lazy<Entry> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            instance.get(Entry::new)
+        }
+    }
 }
 
-impl ::protobuf::Message for Vault {
+impl ::protobuf::Clear for Entry {
+    fn clear(&mut self) {
+        self.attributes.clear();
+        self.created = 0;
+        self.modified = 0;
+        self.author.clear();
+        self.history.clear();
+        self.tags.clear();
+        self.expires = 0;
+        self.location.clear();
+        self.totp.clear();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Entry {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Entry {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct History {
+    // message fields
+    pub revisions: ::protobuf::RepeatedField<Revision>,
+    // special fields
+    pub unknown_fields: ::protobuf::UnknownFields,
+    pub cached_size: ::protobuf::CachedSize,
+}
+
+impl<'a> ::std::default::Default for &'a History {
+    fn default() -> &'a History {
+        <History as ::protobuf::Message>::default_instance()
+    }
+}
+
+impl History {
+    pub fn new() -> History {
+        ::std::default::Default::default()
+    }
+
+    // repeated .Revision revisions = 1;
+
+
+    pub fn get_revisions(&self) -> &[Revision] {
+        &self.revisions
+    }
+    pub fn clear_revisions(&mut self) {
+        self.revisions.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_revisions(&mut self, v: ::protobuf::RepeatedField<Revision>) {
+        self.revisions = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_revisions(&mut self) -> &mut ::protobuf::RepeatedField<Revision> {
+        &mut self.revisions
+    }
+
+    // Take field
+    pub fn take_revisions(&mut self) -> ::protobuf::RepeatedField<Revision> {
+        ::std::mem::replace(&mut self.revisions, ::protobuf::RepeatedField::new())
+    }
+}
+
+impl ::protobuf::Message for History {
     fn is_initialized(&self) -> bool {
+        for v in &self.revisions {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
         true
     }
 
@@ -108,10 +2630,7 @@ impl ::protobuf::Message for Vault {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.identities)?;
-                },
-                2 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.index)?;
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.revisions)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -125,20 +2644,21 @@ impl ::protobuf::Message for Vault {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        for value in &self.identities {
-            my_size += ::protobuf::rt::string_size(1, &value);
+        for value in &self.revisions {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         };
-        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.index);
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        for v in &self.identities {
-            os.write_string(1, &v)?;
+        for v in &self.revisions {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
         };
-        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(2, &self.index, os)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -169,8 +2689,8 @@ impl ::protobuf::Message for Vault {
         Self::descriptor_static()
     }
 
-    fn new() -> Vault {
-        Vault::new()
+    fn new() -> History {
+        History::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -178,18 +2698,13 @@ impl ::protobuf::Message for Vault {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
-                    "identities",
-                    |m: &Vault| { &m.identities },
-                    |m: &mut Vault| { &mut m.identities },
-                ));
-                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
-                    "index",
-                    |m: &Vault| { &m.index },
-                    |m: &mut Vault| { &mut m.index },
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Revision>>(
+                    "revisions",
+                    |m: &History| { &m.revisions },
+                    |m: &mut History| { &mut m.revisions },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Vault>(
-                    "Vault",
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<History>(
+                    "History",
                     fields,
                     file_descriptor_proto()
                 )
@@ -197,117 +2712,133 @@ impl ::protobuf::Message for Vault {
         }
     }
 
-    fn default_instance() -> &'static Vault {
-        static mut instance: ::protobuf::lazy::Lazy<Vault> = ::protobuf::lazy::Lazy::INIT;
+    fn default_instance() -> &'static History {
+        static mut instance: ::protobuf::lazy::Lazy<History> = ::protobuf::lazy::Lazy::INIT;
         unsafe {
-            instance.get(Vault::new)
+            instance.get(History::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Vault {
+impl ::protobuf::Clear for History {
     fn clear(&mut self) {
-        self.identities.clear();
-        self.index.clear();
+        self.revisions.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Vault {
+impl ::std::fmt::Debug for History {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Vault {
+impl ::protobuf::reflect::ProtobufValue for History {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct Entry {
+pub struct Revision {
     // message fields
-    pub attributes: ::std::collections::HashMap<::std::string::String, Attribute>,
-    pub totp: ::protobuf::SingularPtrField<TotpConfig>,
+    pub attribute: ::protobuf::SingularPtrField<Attribute>,
+    pub timestamp: u64,
+    pub author: ::std::string::String,
     // special fields
     pub unknown_fields: ::protobuf::UnknownFields,
     pub cached_size: ::protobuf::CachedSize,
 }
 
-impl<'a> ::std::default::Default for &'a Entry {
-    fn default() -> &'a Entry {
-        <Entry as ::protobuf::Message>::default_instance()
+impl<'a> ::std::default::Default for &'a Revision {
+    fn default() -> &'a Revision {
+        <Revision as ::protobuf::Message>::default_instance()
     }
 }
 
-impl Entry {
-    pub fn new() -> Entry {
+impl Revision {
+    pub fn new() -> Revision {
         ::std::default::Default::default()
     }
 
-    // repeated .Entry.AttributesEntry attributes = 1;
+    // .Attribute attribute = 1;
 
 
-    pub fn get_attributes(&self) -> &::std::collections::HashMap<::std::string::String, Attribute> {
-        &self.attributes
+    pub fn get_attribute(&self) -> &Attribute {
+        self.attribute.as_ref().unwrap_or_else(|| Attribute::default_instance())
     }
-    pub fn clear_attributes(&mut self) {
-        self.attributes.clear();
+    pub fn clear_attribute(&mut self) {
+        self.attribute.clear();
+    }
+
+    pub fn has_attribute(&self) -> bool {
+        self.attribute.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_attributes(&mut self, v: ::std::collections::HashMap<::std::string::String, Attribute>) {
-        self.attributes = v;
+    pub fn set_attribute(&mut self, v: Attribute) {
+        self.attribute = ::protobuf::SingularPtrField::some(v);
     }
 
     // Mutable pointer to the field.
-    pub fn mut_attributes(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, Attribute> {
-        &mut self.attributes
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_attribute(&mut self) -> &mut Attribute {
+        if self.attribute.is_none() {
+            self.attribute.set_default();
+        }
+        self.attribute.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_attributes(&mut self) -> ::std::collections::HashMap<::std::string::String, Attribute> {
-        ::std::mem::replace(&mut self.attributes, ::std::collections::HashMap::new())
+    pub fn take_attribute(&mut self) -> Attribute {
+        self.attribute.take().unwrap_or_else(|| Attribute::new())
     }
 
-    // .TotpConfig totp = 100;
+    // uint64 timestamp = 2;
 
 
-    pub fn get_totp(&self) -> &TotpConfig {
-        self.totp.as_ref().unwrap_or_else(|| TotpConfig::default_instance())
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
     }
-    pub fn clear_totp(&mut self) {
-        self.totp.clear();
+    pub fn clear_timestamp(&mut self) {
+        self.timestamp = 0;
     }
 
-    pub fn has_totp(&self) -> bool {
-        self.totp.is_some()
+    // Param is passed by value, moved
+    pub fn set_timestamp(&mut self, v: u64) {
+        self.timestamp = v;
+    }
+
+    // string author = 3;
+
+
+    pub fn get_author(&self) -> &str {
+        &self.author
+    }
+    pub fn clear_author(&mut self) {
+        self.author.clear();
     }
 
     // Param is passed by value, moved
-    pub fn set_totp(&mut self, v: TotpConfig) {
-        self.totp = ::protobuf::SingularPtrField::some(v);
+    pub fn set_author(&mut self, v: ::std::string::String) {
+        self.author = v;
     }
 
     // Mutable pointer to the field.
     // If field is not initialized, it is initialized with default value first.
-    pub fn mut_totp(&mut self) -> &mut TotpConfig {
-        if self.totp.is_none() {
-            self.totp.set_default();
-        }
-        self.totp.as_mut().unwrap()
+    pub fn mut_author(&mut self) -> &mut ::std::string::String {
+        &mut self.author
     }
 
     // Take field
-    pub fn take_totp(&mut self) -> TotpConfig {
-        self.totp.take().unwrap_or_else(|| TotpConfig::new())
+    pub fn take_author(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.author, ::std::string::String::new())
     }
 }
 
-impl ::protobuf::Message for Entry {
+impl ::protobuf::Message for Revision {
     fn is_initialized(&self) -> bool {
-        for v in &self.totp {
+        for v in &self.attribute {
             if !v.is_initialized() {
                 return false;
             }
@@ -320,10 +2851,17 @@ impl ::protobuf::Message for Entry {
             let (field_number, wire_type) = is.read_tag_unpack()?;
             match field_number {
                 1 => {
-                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(wire_type, is, &mut self.attributes)?;
+                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.attribute)?;
                 },
-                100 => {
-                    ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.totp)?;
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.timestamp = tmp;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.author)?;
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -337,10 +2875,15 @@ impl ::protobuf::Message for Entry {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(1, &self.attributes);
-        if let Some(ref v) = self.totp.as_ref() {
+        if let Some(ref v) = self.attribute.as_ref() {
             let len = v.compute_size();
-            my_size += 2 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        }
+        if self.timestamp != 0 {
+            my_size += ::protobuf::rt::value_size(2, self.timestamp, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.author.is_empty() {
+            my_size += ::protobuf::rt::string_size(3, &self.author);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -348,12 +2891,17 @@ impl ::protobuf::Message for Entry {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream<'_>) -> ::protobuf::ProtobufResult<()> {
-        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(1, &self.attributes, os)?;
-        if let Some(ref v) = self.totp.as_ref() {
-            os.write_tag(100, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+        if let Some(ref v) = self.attribute.as_ref() {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        if self.timestamp != 0 {
+            os.write_uint64(2, self.timestamp)?;
+        }
+        if !self.author.is_empty() {
+            os.write_string(3, &self.author)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -384,8 +2932,8 @@ impl ::protobuf::Message for Entry {
         Self::descriptor_static()
     }
 
-    fn new() -> Entry {
-        Entry::new()
+    fn new() -> Revision {
+        Revision::new()
     }
 
     fn descriptor_static() -> &'static ::protobuf::reflect::MessageDescriptor {
@@ -393,18 +2941,23 @@ impl ::protobuf::Message for Entry {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeMessage<Attribute>>(
-                    "attributes",
-                    |m: &Entry| { &m.attributes },
-                    |m: &mut Entry| { &mut m.attributes },
+                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Attribute>>(
+                    "attribute",
+                    |m: &Revision| { &m.attribute },
+                    |m: &mut Revision| { &mut m.attribute },
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_ptr_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<TotpConfig>>(
-                    "totp",
-                    |m: &Entry| { &m.totp },
-                    |m: &mut Entry| { &mut m.totp },
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "timestamp",
+                    |m: &Revision| { &m.timestamp },
+                    |m: &mut Revision| { &mut m.timestamp },
                 ));
-                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Entry>(
-                    "Entry",
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "author",
+                    |m: &Revision| { &m.author },
+                    |m: &mut Revision| { &mut m.author },
+                ));
+                ::protobuf::reflect::MessageDescriptor::new_pb_name::<Revision>(
+                    "Revision",
                     fields,
                     file_descriptor_proto()
                 )
@@ -412,29 +2965,30 @@ impl ::protobuf::Message for Entry {
         }
     }
 
-    fn default_instance() -> &'static Entry {
-        static mut instance: ::protobuf::lazy::Lazy<Entry> = ::protobuf::lazy::Lazy::INIT;
+    fn default_instance() -> &'static Revision {
+        static mut instance: ::protobuf::lazy::Lazy<Revision> = ::protobuf::lazy::Lazy::INIT;
         unsafe {
-            instance.get(Entry::new)
+            instance.get(Revision::new)
         }
     }
 }
 
-impl ::protobuf::Clear for Entry {
+impl ::protobuf::Clear for Revision {
     fn clear(&mut self) {
-        self.attributes.clear();
-        self.totp.clear();
+        self.attribute.clear();
+        self.timestamp = 0;
+        self.author.clear();
         self.unknown_fields.clear();
     }
 }
 
-impl ::std::fmt::Debug for Entry {
+impl ::std::fmt::Debug for Revision {
     fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
         ::protobuf::text_format::fmt(self, f)
     }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Entry {
+impl ::protobuf::reflect::ProtobufValue for Revision {
     fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
         ::protobuf::reflect::ReflectValueRef::Message(self)
     }
@@ -445,6 +2999,11 @@ pub struct Attribute {
     // message fields
     pub value: ::std::string::String,
     pub bytes_value: ::std::vec::Vec<u8>,
+    pub kind: AttributeKind,
+    pub expires: u64,
+    pub blob: ::std::string::String,
+    pub size: u64,
+    pub digest: ::std::vec::Vec<u8>,
     pub confidential: bool,
     pub file: bool,
     // special fields
@@ -515,6 +3074,103 @@ impl Attribute {
         ::std::mem::replace(&mut self.bytes_value, ::std::vec::Vec::new())
     }
 
+    // .AttributeKind kind = 3;
+
+
+    pub fn get_kind(&self) -> AttributeKind {
+        self.kind
+    }
+    pub fn clear_kind(&mut self) {
+        self.kind = AttributeKind::GENERIC;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_kind(&mut self, v: AttributeKind) {
+        self.kind = v;
+    }
+
+    // uint64 expires = 4;
+
+
+    pub fn get_expires(&self) -> u64 {
+        self.expires
+    }
+    pub fn clear_expires(&mut self) {
+        self.expires = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_expires(&mut self, v: u64) {
+        self.expires = v;
+    }
+
+    // string blob = 5;
+
+
+    pub fn get_blob(&self) -> &str {
+        &self.blob
+    }
+    pub fn clear_blob(&mut self) {
+        self.blob.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_blob(&mut self, v: ::std::string::String) {
+        self.blob = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_blob(&mut self) -> &mut ::std::string::String {
+        &mut self.blob
+    }
+
+    // Take field
+    pub fn take_blob(&mut self) -> ::std::string::String {
+        ::std::mem::replace(&mut self.blob, ::std::string::String::new())
+    }
+
+    // uint64 size = 6;
+
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+    pub fn clear_size(&mut self) {
+        self.size = 0;
+    }
+
+    // Param is passed by value, moved
+    pub fn set_size(&mut self, v: u64) {
+        self.size = v;
+    }
+
+    // bytes digest = 7;
+
+
+    pub fn get_digest(&self) -> &[u8] {
+        &self.digest
+    }
+    pub fn clear_digest(&mut self) {
+        self.digest.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: ::std::vec::Vec<u8>) {
+        self.digest = v;
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_digest(&mut self) -> &mut ::std::vec::Vec<u8> {
+        &mut self.digest
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> ::std::vec::Vec<u8> {
+        ::std::mem::replace(&mut self.digest, ::std::vec::Vec::new())
+    }
+
     // bool confidential = 100;
 
 
@@ -561,6 +3217,29 @@ impl ::protobuf::Message for Attribute {
                 2 => {
                     ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.bytes_value)?;
                 },
+                3 => {
+                    ::protobuf::rt::read_proto3_enum_with_unknown_fields_into(wire_type, is, &mut self.kind, 3, &mut self.unknown_fields)?
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.expires = tmp;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_proto3_string_into(wire_type, is, &mut self.blob)?;
+                },
+                6 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.size = tmp;
+                },
+                7 => {
+                    ::protobuf::rt::read_singular_proto3_bytes_into(wire_type, is, &mut self.digest)?;
+                },
                 100 => {
                     if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
@@ -593,6 +3272,21 @@ impl ::protobuf::Message for Attribute {
         if !self.bytes_value.is_empty() {
             my_size += ::protobuf::rt::bytes_size(2, &self.bytes_value);
         }
+        if self.kind != AttributeKind::GENERIC {
+            my_size += ::protobuf::rt::enum_size(3, self.kind);
+        }
+        if self.expires != 0 {
+            my_size += ::protobuf::rt::value_size(4, self.expires, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.blob.is_empty() {
+            my_size += ::protobuf::rt::string_size(5, &self.blob);
+        }
+        if self.size != 0 {
+            my_size += ::protobuf::rt::value_size(6, self.size, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if !self.digest.is_empty() {
+            my_size += ::protobuf::rt::bytes_size(7, &self.digest);
+        }
         if self.confidential != false {
             my_size += 3;
         }
@@ -611,6 +3305,21 @@ impl ::protobuf::Message for Attribute {
         if !self.bytes_value.is_empty() {
             os.write_bytes(2, &self.bytes_value)?;
         }
+        if self.kind != AttributeKind::GENERIC {
+            os.write_enum(3, self.kind.value())?;
+        }
+        if self.expires != 0 {
+            os.write_uint64(4, self.expires)?;
+        }
+        if !self.blob.is_empty() {
+            os.write_string(5, &self.blob)?;
+        }
+        if self.size != 0 {
+            os.write_uint64(6, self.size)?;
+        }
+        if !self.digest.is_empty() {
+            os.write_bytes(7, &self.digest)?;
+        }
         if self.confidential != false {
             os.write_bool(100, self.confidential)?;
         }
@@ -666,6 +3375,31 @@ impl ::protobuf::Message for Attribute {
                     |m: &Attribute| { &m.bytes_value },
                     |m: &mut Attribute| { &mut m.bytes_value },
                 ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeEnum<AttributeKind>>(
+                    "kind",
+                    |m: &Attribute| { &m.kind },
+                    |m: &mut Attribute| { &mut m.kind },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "expires",
+                    |m: &Attribute| { &m.expires },
+                    |m: &mut Attribute| { &mut m.expires },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "blob",
+                    |m: &Attribute| { &m.blob },
+                    |m: &mut Attribute| { &mut m.blob },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "size",
+                    |m: &Attribute| { &m.size },
+                    |m: &mut Attribute| { &mut m.size },
+                ));
+                fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "digest",
+                    |m: &Attribute| { &m.digest },
+                    |m: &mut Attribute| { &mut m.digest },
+                ));
                 fields.push(::protobuf::reflect::accessor::make_simple_field_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
                     "confidential",
                     |m: &Attribute| { &m.confidential },
@@ -697,6 +3431,11 @@ impl ::protobuf::Clear for Attribute {
     fn clear(&mut self) {
         self.value.clear();
         self.bytes_value.clear();
+        self.kind = AttributeKind::GENERIC;
+        self.expires = 0;
+        self.blob.clear();
+        self.size = 0;
+        self.digest.clear();
         self.confidential = false;
         self.file = false;
         self.unknown_fields.clear();
@@ -1034,24 +3773,425 @@ impl ::protobuf::reflect::ProtobufValue for TotpConfig_Hash {
     }
 }
 
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum BackendKind {
+    GPG = 0,
+    AGE = 1,
+    PASSPHRASE = 2,
+}
+
+impl ::protobuf::ProtobufEnum for BackendKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<BackendKind> {
+        match value {
+            0 => ::std::option::Option::Some(BackendKind::GPG),
+            1 => ::std::option::Option::Some(BackendKind::AGE),
+            2 => ::std::option::Option::Some(BackendKind::PASSPHRASE),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [BackendKind] = &[
+            BackendKind::GPG,
+            BackendKind::AGE,
+            BackendKind::PASSPHRASE,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new_pb_name::<BackendKind>("BackendKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for BackendKind {
+}
+
+impl ::std::default::Default for BackendKind {
+    fn default() -> Self {
+        BackendKind::GPG
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for BackendKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum KdfKind {
+    ARGON2ID = 0,
+}
+
+impl ::protobuf::ProtobufEnum for KdfKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<KdfKind> {
+        match value {
+            0 => ::std::option::Option::Some(KdfKind::ARGON2ID),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [KdfKind] = &[
+            KdfKind::ARGON2ID,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new_pb_name::<KdfKind>("KdfKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for KdfKind {
+}
+
+impl ::std::default::Default for KdfKind {
+    fn default() -> Self {
+        KdfKind::ARGON2ID
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for KdfKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum SignaturePolicy {
+    WARN_UNSIGNED = 0,
+    REFUSE_UNSIGNED = 1,
+}
+
+impl ::protobuf::ProtobufEnum for SignaturePolicy {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<SignaturePolicy> {
+        match value {
+            0 => ::std::option::Option::Some(SignaturePolicy::WARN_UNSIGNED),
+            1 => ::std::option::Option::Some(SignaturePolicy::REFUSE_UNSIGNED),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [SignaturePolicy] = &[
+            SignaturePolicy::WARN_UNSIGNED,
+            SignaturePolicy::REFUSE_UNSIGNED,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new_pb_name::<SignaturePolicy>("SignaturePolicy", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for SignaturePolicy {
+}
+
+impl ::std::default::Default for SignaturePolicy {
+    fn default() -> Self {
+        SignaturePolicy::WARN_UNSIGNED
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for SignaturePolicy {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum RollbackPolicy {
+    WARN_ROLLBACK = 0,
+    REFUSE_ROLLBACK = 1,
+}
+
+impl ::protobuf::ProtobufEnum for RollbackPolicy {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<RollbackPolicy> {
+        match value {
+            0 => ::std::option::Option::Some(RollbackPolicy::WARN_ROLLBACK),
+            1 => ::std::option::Option::Some(RollbackPolicy::REFUSE_ROLLBACK),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [RollbackPolicy] = &[
+            RollbackPolicy::WARN_ROLLBACK,
+            RollbackPolicy::REFUSE_ROLLBACK,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new_pb_name::<RollbackPolicy>("RollbackPolicy", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for RollbackPolicy {
+}
+
+impl ::std::default::Default for RollbackPolicy {
+    fn default() -> Self {
+        RollbackPolicy::WARN_ROLLBACK
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RollbackPolicy {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum GitAuth {
+    AGENT = 0,
+    KEY_FILE = 1,
+    TOKEN_VARIABLE = 2,
+    TOKEN_ENTRY = 3,
+}
+
+impl ::protobuf::ProtobufEnum for GitAuth {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<GitAuth> {
+        match value {
+            0 => ::std::option::Option::Some(GitAuth::AGENT),
+            1 => ::std::option::Option::Some(GitAuth::KEY_FILE),
+            2 => ::std::option::Option::Some(GitAuth::TOKEN_VARIABLE),
+            3 => ::std::option::Option::Some(GitAuth::TOKEN_ENTRY),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [GitAuth] = &[
+            GitAuth::AGENT,
+            GitAuth::KEY_FILE,
+            GitAuth::TOKEN_VARIABLE,
+            GitAuth::TOKEN_ENTRY,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new_pb_name::<GitAuth>("GitAuth", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for GitAuth {
+}
+
+impl ::std::default::Default for GitAuth {
+    fn default() -> Self {
+        GitAuth::AGENT
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for GitAuth {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum AttributeKind {
+    GENERIC = 0,
+    PASSWORD = 1,
+    URL = 2,
+    USERNAME = 3,
+    EMAIL = 4,
+    NOTE = 5,
+    PIN = 6,
+    SSH_KEY = 7,
+    CERTIFICATE = 8,
+    CARD_NUMBER = 9,
+}
+
+impl ::protobuf::ProtobufEnum for AttributeKind {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<AttributeKind> {
+        match value {
+            0 => ::std::option::Option::Some(AttributeKind::GENERIC),
+            1 => ::std::option::Option::Some(AttributeKind::PASSWORD),
+            2 => ::std::option::Option::Some(AttributeKind::URL),
+            3 => ::std::option::Option::Some(AttributeKind::USERNAME),
+            4 => ::std::option::Option::Some(AttributeKind::EMAIL),
+            5 => ::std::option::Option::Some(AttributeKind::NOTE),
+            6 => ::std::option::Option::Some(AttributeKind::PIN),
+            7 => ::std::option::Option::Some(AttributeKind::SSH_KEY),
+            8 => ::std::option::Option::Some(AttributeKind::CERTIFICATE),
+            9 => ::std::option::Option::Some(AttributeKind::CARD_NUMBER),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [AttributeKind] = &[
+            AttributeKind::GENERIC,
+            AttributeKind::PASSWORD,
+            AttributeKind::URL,
+            AttributeKind::USERNAME,
+            AttributeKind::EMAIL,
+            AttributeKind::NOTE,
+            AttributeKind::PIN,
+            AttributeKind::SSH_KEY,
+            AttributeKind::CERTIFICATE,
+            AttributeKind::CARD_NUMBER,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static() -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy::INIT;
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new_pb_name::<AttributeKind>("AttributeKind", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for AttributeKind {
+}
+
+impl ::std::default::Default for AttributeKind {
+    fn default() -> Self {
+        AttributeKind::GENERIC
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for AttributeKind {
+    fn as_ref(&self) -> ::protobuf::reflect::ReflectValueRef {
+        ::protobuf::reflect::ReflectValueRef::Enum(self.descriptor())
+    }
+}
+
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x08pb.proto\"\x8a\x01\n\x05Vault\x12\x1e\n\nidentities\x18\x01\x20\
+    \n\x08pb.proto\"\xcb\x04\n\x05Vault\x12\x1e\n\nidentities\x18\x01\x20\
     \x03(\tR\nidentities\x12'\n\x05index\x18\x02\x20\x03(\x0b2\x11.Vault.Ind\
-    exEntryR\x05index\x1a8\n\nIndexEntry\x12\x10\n\x03key\x18\x01\x20\x01(\t\
-    R\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"\xab\
-    \x01\n\x05Entry\x126\n\nattributes\x18\x01\x20\x03(\x0b2\x16.Entry.Attri\
-    butesEntryR\nattributes\x12\x1f\n\x04totp\x18d\x20\x01(\x0b2\x0b.TotpCon\
-    figR\x04totp\x1aI\n\x0fAttributesEntry\x12\x10\n\x03key\x18\x01\x20\x01(\
-    \tR\x03key\x12\x20\n\x05value\x18\x02\x20\x01(\x0b2\n.AttributeR\x05valu\
-    e:\x028\x01\"z\n\tAttribute\x12\x14\n\x05value\x18\x01\x20\x01(\tR\x05va\
-    lue\x12\x1f\n\x0bbytes_value\x18\x02\x20\x01(\x0cR\nbytesValue\x12\"\n\
-    \x0cconfidential\x18d\x20\x01(\x08R\x0cconfidential\x12\x12\n\x04file\
-    \x18e\x20\x01(\x08R\x04file\"\xa8\x01\n\nTotpConfig\x12\x16\n\x06secret\
-    \x18\x01\x20\x01(\x0cR\x06secret\x12\x1a\n\x08interval\x18\x02\x20\x01(\
-    \x04R\x08interval\x12$\n\x04hash\x18\x03\x20\x01(\x0e2\x10.TotpConfig.Ha\
-    shR\x04hash\x12\x16\n\x06length\x18\x04\x20\x01(\rR\x06length\"(\n\x04Ha\
-    sh\x12\x08\n\x04SHA1\x10\0\x12\n\n\x06SHA256\x10\x01\x12\n\n\x06SHA512\
-    \x10\x02b\x06proto3\
+    exEntryR\x05index\x12\x18\n\x07version\x18\x03\x20\x01(\rR\x07version\
+    \x12$\n\x04tags\x18\x04\x20\x03(\x0b2\x10.Vault.TagsEntryR\x04tags\x123\
+    \n\ttemplates\x18\x05\x20\x03(\x0b2\x15.Vault.TemplatesEntryR\ttemplates\
+    \x129\n\x0bexpirations\x18\x06\x20\x03(\x0b2\x17.Vault.ExpirationsEntryR\
+    \x0bexpirations\x12&\n\x07backend\x18\x07\x20\x01(\x0e2\x0c.BackendKindR\
+    \x07backend\x12\x1e\n\ngeneration\x18\x08\x20\x01(\x04R\ngeneration\x1a8\
+    \n\nIndexEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05\
+    value\x18\x02\x20\x01(\tR\x05value:\x028\x01\x1a>\n\tTagsEntry\x12\x10\n\
+    \x03key\x18\x01\x20\x01(\tR\x03key\x12\x1b\n\x05value\x18\x02\x20\x01(\
+    \x0b2\x05.TagsR\x05value:\x028\x01\x1aG\n\x0eTemplatesEntry\x12\x10\n\
+    \x03key\x18\x01\x20\x01(\tR\x03key\x12\x1f\n\x05value\x18\x02\x20\x01(\
+    \x0b2\t.TemplateR\x05value:\x028\x01\x1a>\n\x10ExpirationsEntry\x12\x10\
+    \n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\
+    \x04R\x05value:\x028\x01\"\x92\x01\n\x06Header\x12\x1a\n\x03kdf\x18\x01\
+    \x20\x01(\x0e2\x08.KdfKindR\x03kdf\x12\x12\n\x04salt\x18\x02\x20\x01(\
+    \x0cR\x04salt\x12\x16\n\x06memory\x18\x03\x20\x01(\rR\x06memory\x12\x1e\
+    \n\niterations\x18\x04\x20\x01(\rR\niterations\x12\x20\n\x0bparallelism\
+    \x18\x05\x20\x01(\rR\x0bparallelism\"\xeb\x01\n\x0bLocalConfig\x12'\n\
+    \x0ftrusted_signers\x18\x01\x20\x03(\tR\x0etrustedSigners\x12;\n\x10sign\
+    ature_policy\x18\x02\x20\x01(\x0e2\x10.SignaturePolicyR\x0fsignaturePoli\
+    cy\x12\x1e\n\ngeneration\x18\x03\x20\x01(\x04R\ngeneration\x128\n\x0frol\
+    lback_policy\x18\x04\x20\x01(\x0e2\x0f.RollbackPolicyR\x0erollbackPolicy\
+    \x12\x1c\n\x03git\x18\x05\x20\x01(\x0b2\n.GitConfigR\x03git\"\xb3\x01\n\
+    \tGitConfig\x12\x16\n\x06remote\x18\x01\x20\x01(\tR\x06remote\x12\x16\n\
+    \x06branch\x18\x02\x20\x01(\tR\x06branch\x12\x1c\n\x04auth\x18\x03\x20\
+    \x01(\x0e2\x08.GitAuthR\x04auth\x12\x1e\n\ncredential\x18\x04\x20\x01(\t\
+    R\ncredential\x12\x1a\n\x08username\x18\x05\x20\x01(\tR\x08username\x12\
+    \x1c\n\tattribute\x18\x06\x20\x01(\tR\tattribute\"T\n\x08Template\x12\
+    \x20\n\x0bdescription\x18\x01\x20\x01(\tR\x0bdescription\x12&\n\x06field\
+    s\x18\x02\x20\x03(\x0b2\x0e.TemplateFieldR\x06fields\"\xa3\x01\n\rTempla\
+    teField\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\"\n\x04kind\
+    \x18\x02\x20\x01(\x0e2\x0e.AttributeKindR\x04kind\x12\x1a\n\x08optional\
+    \x18\x03\x20\x01(\x08R\x08optional\x12\"\n\x0cconfidential\x18\x04\x20\
+    \x01(\x08R\x0cconfidential\x12\x1a\n\x08generate\x18\x05\x20\x01(\x08R\
+    \x08generate\"\x1a\n\x04Tags\x12\x12\n\x04tags\x18\x01\x20\x03(\tR\x04ta\
+    gs\"\xb8\x03\n\x05Entry\x126\n\nattributes\x18\x01\x20\x03(\x0b2\x16.Ent\
+    ry.AttributesEntryR\nattributes\x12\x18\n\x07created\x18\x02\x20\x01(\
+    \x04R\x07created\x12\x1a\n\x08modified\x18\x03\x20\x01(\x04R\x08modified\
+    \x12\x16\n\x06author\x18\x04\x20\x01(\tR\x06author\x12-\n\x07history\x18\
+    \x05\x20\x03(\x0b2\x13.Entry.HistoryEntryR\x07history\x12\x12\n\x04tags\
+    \x18\x06\x20\x03(\tR\x04tags\x12\x18\n\x07expires\x18\x07\x20\x01(\x04R\
+    \x07expires\x12\x1a\n\x08location\x18\x08\x20\x01(\tR\x08location\x12\
+    \x1f\n\x04totp\x18d\x20\x01(\x0b2\x0b.TotpConfigR\x04totp\x1aI\n\x0fAttr\
+    ibutesEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x20\n\x05val\
+    ue\x18\x02\x20\x01(\x0b2\n.AttributeR\x05value:\x028\x01\x1aD\n\x0cHisto\
+    ryEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x1e\n\x05value\
+    \x18\x02\x20\x01(\x0b2\x08.HistoryR\x05value:\x028\x01\"2\n\x07History\
+    \x12'\n\trevisions\x18\x01\x20\x03(\x0b2\t.RevisionR\trevisions\"j\n\x08\
+    Revision\x12(\n\tattribute\x18\x01\x20\x01(\x0b2\n.AttributeR\tattribute\
+    \x12\x1c\n\ttimestamp\x18\x02\x20\x01(\x04R\ttimestamp\x12\x16\n\x06auth\
+    or\x18\x03\x20\x01(\tR\x06author\"\xf8\x01\n\tAttribute\x12\x14\n\x05val\
+    ue\x18\x01\x20\x01(\tR\x05value\x12\x1f\n\x0bbytes_value\x18\x02\x20\x01\
+    (\x0cR\nbytesValue\x12\"\n\x04kind\x18\x03\x20\x01(\x0e2\x0e.AttributeKi\
+    ndR\x04kind\x12\x18\n\x07expires\x18\x04\x20\x01(\x04R\x07expires\x12\
+    \x12\n\x04blob\x18\x05\x20\x01(\tR\x04blob\x12\x12\n\x04size\x18\x06\x20\
+    \x01(\x04R\x04size\x12\x16\n\x06digest\x18\x07\x20\x01(\x0cR\x06digest\
+    \x12\"\n\x0cconfidential\x18d\x20\x01(\x08R\x0cconfidential\x12\x12\n\
+    \x04file\x18e\x20\x01(\x08R\x04file\"\xa8\x01\n\nTotpConfig\x12\x16\n\
+    \x06secret\x18\x01\x20\x01(\x0cR\x06secret\x12\x1a\n\x08interval\x18\x02\
+    \x20\x01(\x04R\x08interval\x12$\n\x04hash\x18\x03\x20\x01(\x0e2\x10.Totp\
+    Config.HashR\x04hash\x12\x16\n\x06length\x18\x04\x20\x01(\rR\x06length\"\
+    (\n\x04Hash\x12\x08\n\x04SHA1\x10\0\x12\n\n\x06SHA256\x10\x01\x12\n\n\
+    \x06SHA512\x10\x02*/\n\x0bBackendKind\x12\x07\n\x03GPG\x10\0\x12\x07\n\
+    \x03AGE\x10\x01\x12\x0e\n\nPASSPHRASE\x10\x02*\x17\n\x07KdfKind\x12\x0c\
+    \n\x08ARGON2ID\x10\0*9\n\x0fSignaturePolicy\x12\x11\n\rWARN_UNSIGNED\x10\
+    \0\x12\x13\n\x0fREFUSE_UNSIGNED\x10\x01*8\n\x0eRollbackPolicy\x12\x11\n\
+    \rWARN_ROLLBACK\x10\0\x12\x13\n\x0fREFUSE_ROLLBACK\x10\x01*G\n\x07GitAut\
+    h\x12\t\n\x05AGENT\x10\0\x12\x0c\n\x08KEY_FILE\x10\x01\x12\x12\n\x0eTOKE\
+    N_VARIABLE\x10\x02\x12\x0f\n\x0bTOKEN_ENTRY\x10\x03*\x8e\x01\n\rAttribut\
+    eKind\x12\x0b\n\x07GENERIC\x10\0\x12\x0c\n\x08PASSWORD\x10\x01\x12\x07\n\
+    \x03URL\x10\x02\x12\x0c\n\x08USERNAME\x10\x03\x12\t\n\x05EMAIL\x10\x04\
+    \x12\x08\n\x04NOTE\x10\x05\x12\x07\n\x03PIN\x10\x06\x12\x0b\n\x07SSH_KEY\
+    \x10\x07\x12\x0f\n\x0bCERTIFICATE\x10\x08\x12\x0f\n\x0bCARD_NUMBER\x10\t\
+    b\x06proto3\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy::INIT;
@@ -10,7 +10,7 @@ pub(crate) fn get_test_vault<P>(path: P) -> Result<VaultContext, Box<dyn Error>>
 where
   P: AsRef<Path>,
 {
-  let context = VaultContext::create(&path, &spec::get_test_identities())?;
+  let mut context = VaultContext::create(&path, &spec::get_test_identities())?;
   context.write()?;
 
   Ok(context)
@@ -0,0 +1,107 @@
+use std::error::Error;
+use std::fs::{create_dir_all, remove_file, rename, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use uuid::Uuid;
+
+use crate::*;
+
+pub(crate) const TEMPORARY_DIRECTORY: &str = "_knox.tmp";
+pub(crate) const QUARANTINE_DIRECTORY: &str = "_knox.quarantine";
+pub(crate) const BLOB_DIRECTORY: &str = "_knox.blobs";
+
+/// Replace a file under the vault's directory without ever exposing a
+/// partially-written version of it.
+///
+/// The data is first written and synced to a temporary file inside the
+/// vault, which is then renamed over its final destination. A crash at any
+/// point leaves either the previous or the new version of the file in place.
+pub(crate) fn write_atomic<T>(context: &VaultContext, path: &T, data: &[u8]) -> Result<(), Box<dyn Error>>
+where
+  T: AsRef<Path>,
+{
+  write_atomic_in(&context.path, path, data)
+}
+
+/// Replace a file under the directory of a vault that is not opened.
+///
+/// See [write_atomic](fn.write_atomic.html).
+pub(crate) fn write_atomic_in<P, T>(vault: P, path: &T, data: &[u8]) -> Result<(), Box<dyn Error>>
+where
+  P: AsRef<Path>,
+  T: AsRef<Path>,
+{
+  write_stream_atomic_in(vault, path, |file| Ok(file.write_all(data)?))
+}
+
+/// Replace a file under the vault's directory with data produced as a
+/// stream.
+///
+/// See [write_atomic](fn.write_atomic.html). The provided closure writes the
+/// content to the temporary file.
+pub(crate) fn write_stream_atomic<T, F>(context: &VaultContext, path: &T, write: F) -> Result<(), Box<dyn Error>>
+where
+  T: AsRef<Path>,
+  F: FnOnce(&mut File) -> Result<(), Box<dyn Error>>,
+{
+  write_stream_atomic_in(&context.path, path, write)
+}
+
+fn write_stream_atomic_in<P, T, F>(vault: P, path: &T, write: F) -> Result<(), Box<dyn Error>>
+where
+  P: AsRef<Path>,
+  T: AsRef<Path>,
+  F: FnOnce(&mut File) -> Result<(), Box<dyn Error>>,
+{
+  let directory = format!("{}/{}", vault.as_ref().display(), TEMPORARY_DIRECTORY);
+  let temporary = format!("{}/{}", directory, Uuid::new_v4().to_hyphenated());
+  let destination = format!("{}/{}", vault.as_ref().display(), path.as_ref().display());
+
+  create_dir_all(&directory)?;
+
+  if let Err(err) = persist(&temporary, &destination, write) {
+    let _ = remove_file(&temporary);
+
+    return Err(err);
+  }
+
+  Ok(())
+}
+
+fn persist<F>(temporary: &str, destination: &str, write: F) -> Result<(), Box<dyn Error>>
+where
+  F: FnOnce(&mut File) -> Result<(), Box<dyn Error>>,
+{
+  let mut file = OpenOptions::new().create_new(true).write(true).open(temporary)?;
+
+  write(&mut file)?;
+  file.sync_all()?;
+
+  rename(temporary, destination)?;
+
+  if let Some(parent) = Path::new(destination).parent() {
+    File::open(parent)?.sync_all()?;
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use knox_testing::spec;
+
+  #[test]
+  fn write_atomic() {
+    let tmp = spec::setup();
+    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    super::write_atomic(&context, &"file", b"loremipsumdolor").expect("could not write file");
+    super::write_atomic(&context, &"file", b"lorem").expect("could not write file");
+
+    assert_eq!(fs::read(tmp.path().join("file")).expect("could not read file"), b"lorem");
+    assert_eq!(fs::read_dir(tmp.path().join(super::TEMPORARY_DIRECTORY)).expect("could not read directory").count(), 0);
+  }
+}
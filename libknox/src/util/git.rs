@@ -1,8 +1,47 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::env;
 use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use git2::build::{CheckoutBuilder, RepoBuilder};
+use git2::{
+  Commit, Config, ConfigLevel, Cred, Direction, FetchOptions, IndexAddOption, IndexEntry, ObjectType, Oid, PushOptions, Remote, RemoteCallbacks, Repository, Signature, Sort, StatusOptions, Tree,
+  TreeWalkMode, TreeWalkResult,
+};
+
+use crate::{
+  pb::*,
+  util::{self, VaultError},
+  AttributeValue, Conflict, Purge, Resolution, VaultContext,
+};
+
+const ATTRIBUTES_FILE: &str = ".gitattributes";
+const MERGE_DRIVER: &str = "knox git merge-driver %O %A %B";
+const TEXTCONV_DRIVER: &str = "knox git textconv";
+const INDEX_ENTRY_STAGE_MASK: u16 = 0x3000;
+const BACKUP_REFERENCE: &str = "refs/knox/backup";
+
+const DEFAULT_REMOTE: &str = "origin";
+const DEFAULT_BRANCH: &str = "master";
+const DEFAULT_USERNAME: &str = "git";
+const DEFAULT_TOKEN_ATTRIBUTE: &str = "password";
+
+/// State of the vault at a commit of its repository.
+pub(crate) struct Snapshot {
+  pub id: Oid,
+  pub parent: Option<Oid>,
+  pub summary: String,
+  pub author: String,
+  pub timestamp: u64,
+  pub metadata: Option<Vec<u8>>,
+}
 
-use git2::{Commit, Config, Cred, IndexAddOption, ObjectType, PushOptions, RemoteCallbacks, Repository, Signature};
-
-use crate::{util::VaultError, VaultContext};
+enum Credentials {
+  Agent,
+  KeyFile(PathBuf),
+  Token(String),
+}
 
 pub fn exists(vault: &VaultContext) -> bool {
   vault.has_pack(".git")
@@ -14,13 +53,30 @@ fn last_commit(repo: &Repository) -> Result<Commit, git2::Error> {
   object.into_commit().map_err(|_| git2::Error::from_str("could not find latest commit"))
 }
 
+pub(crate) fn is_tracked(path: &Path) -> bool {
+  !path.starts_with(util::TEMPORARY_DIRECTORY) && !path.starts_with(util::QUARANTINE_DIRECTORY) && path != Path::new(util::LOCK_FILE) && path != Path::new(util::LOCAL_FILE)
+}
+
 pub(crate) fn init(vault: &VaultContext) -> Result<(), Box<dyn Error>> {
   match Repository::init(&vault.path) {
-    Ok(_) => commit(&vault, "Initialized knox repository."),
+    Ok(repo) => {
+      util::write_atomic(vault, &ATTRIBUTES_FILE, format!("{} merge=knox\n??/* diff=knox\n", util::METADATA_FILE).as_bytes())?;
+      set_drivers(&repo)?;
+
+      commit(vault, "Initialized knox repository.")
+    }
     Err(_) => Err(VaultError::throw("could not init git repository")),
   }
 }
 
+pub(crate) fn set_drivers(repo: &Repository) -> Result<(), git2::Error> {
+  let mut config = repo.config()?.open_level(ConfigLevel::Local)?;
+
+  config.set_str("merge.knox.name", "knox vault metadata")?;
+  config.set_str("merge.knox.driver", MERGE_DRIVER)?;
+  config.set_str("diff.knox.textconv", TEXTCONV_DRIVER)
+}
+
 pub(crate) fn commit(vault: &VaultContext, message: &str) -> Result<(), Box<dyn Error>> {
   if !exists(&vault) {
     return Ok(());
@@ -28,16 +84,6 @@ pub(crate) fn commit(vault: &VaultContext, message: &str) -> Result<(), Box<dyn
 
   match Repository::open(&vault.path) {
     Ok(repo) => {
-      let (name, email) = Config::open_default()?
-        .snapshot()
-        .map(|c| {
-          (
-            c.get_string("user.name").unwrap_or_else(|_| "Knox".to_string()),
-            c.get_string("user.email").unwrap_or_else(|_| "N/A".to_string()),
-          )
-        })
-        .unwrap_or(("Knox".to_string(), "N/A".to_string()));
-
       let last_commit = last_commit(&repo).ok();
       let parent = match last_commit {
         Some(ref commit) => vec![commit],
@@ -45,11 +91,12 @@ pub(crate) fn commit(vault: &VaultContext, message: &str) -> Result<(), Box<dyn
       };
 
       let mut index = repo.index()?;
-      index.add_all(&["*"], IndexAddOption::DEFAULT, None)?;
+      index.add_all(&["*"], IndexAddOption::DEFAULT, Some(&mut |path: &Path, _: &[u8]| if is_tracked(path) { 0 } else { 1 }))?;
+      index.update_all(["*"], None)?;
 
       let tree = repo.find_tree(index.write_tree()?)?;
 
-      let author = Signature::now(&name, &email)?;
+      let author = author()?;
 
       repo.commit(Some("HEAD"), &author, &author, message, &tree, &parent)?;
       index.write()?;
@@ -68,42 +115,555 @@ pub(crate) fn set_origin(vault: &VaultContext, origin: &str) -> Result<(), Box<d
   }
 
   let repo = Repository::open(&vault.path)?;
+  let config = vault.get_local_config();
 
-  repo.remote_set_url("origin", origin)?;
+  repo.remote_set_url(&remote_name(config.get_git()), origin)?;
 
   Ok(())
 }
 
-pub(crate) fn push(vault: &VaultContext) -> Result<(), Box<dyn Error>> {
+pub(crate) fn push(vault: &VaultContext, force: bool) -> Result<(), Box<dyn Error>> {
   if !exists(&vault) {
     return Err(VaultError::throw("local git repository does not exist"));
   }
 
   let repo = Repository::open(&vault.path)?;
-  let mut remote = repo.find_remote("origin")?;
+  let config = vault.get_local_config();
+  let branch = branch_name(config.get_git(), &repo);
+  let mut remote = repo.find_remote(&remote_name(config.get_git()))?;
+
+  if force {
+    check_lease(&repo, &mut remote, config.get_git(), vault, &branch)?;
+  }
+
+  let mut options = PushOptions::new();
+  options.remote_callbacks(remote_callbacks(config.get_git(), Some(vault))?);
+
+  let force = if force { "+" } else { "" };
+
+  // The configured branch is the remote one, and may not exist locally.
+  remote.push(&[format!("{}HEAD:refs/heads/{}", force, branch)], Some(&mut options))?;
+
+  Ok(())
+}
+
+/// Refuse to replace the remote branch if it moved since it was last fetched,
+/// as its new commits would be lost.
+fn check_lease(repo: &Repository, remote: &mut Remote, config: &GitConfig, vault: &VaultContext, branch: &str) -> Result<(), Box<dyn Error>> {
+  let expected = repo.refname_to_id(&format!("refs/remotes/{}/{}", remote_name(config), branch)).ok();
+  let connection = remote.connect_auth(Direction::Push, Some(remote_callbacks(config, Some(vault))?), None)?;
+  let reference = format!("refs/heads/{}", branch);
+  let current = connection.list()?.iter().find(|head| head.name() == reference).map(|head| head.oid());
+
+  if current.is_some() && current != expected {
+    return Err(VaultError::throw(
+      "the remote branch was modified since it was last pulled, refusing to replace its history, pull the vault and purge it again",
+    ));
+  }
+
+  Ok(())
+}
+
+pub(crate) fn clone(url: &str, path: &Path, config: &GitConfig) -> Result<(), Box<dyn Error>> {
+  let remote = remote_name(config);
+
+  let mut options = FetchOptions::new();
+  options.remote_callbacks(remote_callbacks(config, None)?);
+
+  let mut builder = RepoBuilder::new();
+  builder.fetch_options(options);
+  builder.remote_create(move |repo, _, url| repo.remote(&remote, url));
+
+  if !config.get_branch().is_empty() {
+    builder.branch(config.get_branch());
+  }
+
+  set_drivers(&builder.clone(url, path)?)?;
+
+  Ok(())
+}
+
+pub(crate) fn pull(vault: &VaultContext, resolution: Resolution) -> Result<Vec<Conflict>, Box<dyn Error>> {
+  if !exists(vault) {
+    return Err(VaultError::throw("local git repository does not exist"));
+  }
+
+  let repo = Repository::open(&vault.path)?;
+
+  // Merging checks out the result over the working tree.
+  if is_dirty(&repo)? {
+    return Err(VaultError::throw("the vault has uncommitted modifications, please commit them before pulling"));
+  }
+
+  let config = vault.get_local_config();
+  let branch = branch_name(config.get_git(), &repo);
+  let upstream = format!("refs/remotes/{}/{}", remote_name(config.get_git()), branch);
+  let mut remote = repo.find_remote(&remote_name(config.get_git()))?;
+
+  let mut options = FetchOptions::new();
+  options.remote_callbacks(remote_callbacks(config.get_git(), Some(vault))?);
+
+  remote.fetch(&[format!("refs/heads/{}:{}", branch, upstream)], Some(&mut options), None)?;
+
+  let upstream = repo.reference_to_annotated_commit(&repo.find_reference(&upstream)?)?;
+  let (analysis, _) = repo.merge_analysis(&[&upstream])?;
+
+  if analysis.is_up_to_date() {
+    return Ok(Vec::new());
+  }
+
+  if analysis.is_fast_forward() {
+    repo.head()?.set_target(upstream.id(), "knox: fast-forward")?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+
+    return Ok(Vec::new());
+  }
+
+  let ours = last_commit(&repo)?;
+  let theirs = repo.find_commit(upstream.id())?;
+  let mut index = repo.merge_commits(&ours, &theirs, None)?;
+  let mut conflicts = Vec::new();
+
+  let unmerged = index.conflicts()?.collect::<Result<Vec<_>, _>>()?;
+  let packs = conflicting_packs(vault, &upstream.id(), &unmerged)?;
+
+  for conflict in unmerged {
+    let content = |entry: &Option<IndexEntry>| -> Result<Vec<u8>, git2::Error> {
+      match entry {
+        Some(entry) => Ok(repo.find_blob(entry.id)?.content().to_vec()),
+        None => Ok(Vec::new()),
+      }
+    };
+
+    let path = match conflict.our.as_ref().or(conflict.their.as_ref()) {
+      Some(entry) => String::from_utf8_lossy(&entry.path).to_string(),
+      None => continue,
+    };
+
+    if path != util::METADATA_FILE {
+      let entry = packs.get(&path).ok_or_else(|| VaultError::throw(&format!("could not merge {} with the upstream vault", path)))?;
+
+      // Entries modified on one side and deleted on the other are deleted by
+      // the merge of the metadata, since their pack is left unchanged.
+      let kept = match (conflict.our, conflict.their) {
+        (Some(ours), Some(theirs)) => Some(if resolution == Resolution::Ours { ours } else { theirs }),
+        _ => None,
+      };
+
+      index.remove_path(Path::new(&path))?;
+
+      if let Some(kept) = &kept {
+        index.add(&IndexEntry {
+          flags: kept.flags & !INDEX_ENTRY_STAGE_MASK,
+          path: kept.path.clone(),
+          ..*kept
+        })?;
+      }
+
+      conflicts.push(Conflict::Pack {
+        path: entry.clone(),
+        deleted: kept.is_none(),
+      });
+
+      continue;
+    }
+
+    let (base, ours, theirs) = (content(&conflict.ancestor)?, content(&conflict.our)?, content(&conflict.their)?);
+    let entry = conflict.our.or(conflict.their).unwrap();
+
+    let (metadata, found) = VaultContext::merge_metadata(&vault.path, &base, &ours, &theirs)?;
+
+    index.remove_path(Path::new(util::METADATA_FILE))?;
+    index.add(&IndexEntry {
+      id: repo.blob(&metadata)?,
+      file_size: metadata.len() as u32,
+      flags: entry.flags & !INDEX_ENTRY_STAGE_MASK,
+      ..entry
+    })?;
+
+    conflicts.extend(found);
+  }
+
+  let tree = repo.find_tree(index.write_tree_to(&repo)?)?;
+  let author = author()?;
+
+  repo.commit(Some("HEAD"), &author, &author, "Merged upstream changes.", &tree, &[&ours, &theirs])?;
+  repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+
+  Ok(conflicts)
+}
+
+fn is_dirty(repo: &Repository) -> Result<bool, git2::Error> {
+  let mut options = StatusOptions::new();
+  options.include_untracked(true).recurse_untracked_dirs(true);
+
+  Ok(repo.statuses(Some(&mut options))?.iter().any(|status| status.path().is_none_or(|path| is_tracked(Path::new(path)))))
+}
+
+/// Map the packs conflicting in a merge to the virtual path of their entry,
+/// as found in our index or in the index of the upstream metadata.
+fn conflicting_packs(vault: &VaultContext, upstream: &Oid, unmerged: &[git2::IndexConflict]) -> Result<HashMap<String, String>, Box<dyn Error>> {
+  let mut packs = HashMap::new();
+  let metadata = |entry: &Option<IndexEntry>| entry.as_ref().is_some_and(|entry| entry.path == util::METADATA_FILE.as_bytes());
+
+  if unmerged.iter().all(|conflict| metadata(&conflict.our) || metadata(&conflict.their)) {
+    return Ok(packs);
+  }
+
+  let theirs = vault.decrypt_revision(&read_file(vault, &upstream.to_string(), util::METADATA_FILE)?)?;
+
+  for (path, pack) in theirs.get_index().iter().chain(vault.vault.get_index()) {
+    packs.insert(pack.clone(), path.clone());
+  }
+
+  Ok(packs)
+}
+
+pub(crate) fn head(vault: &VaultContext) -> Result<Option<Oid>, Box<dyn Error>> {
+  if !exists(vault) {
+    return Ok(None);
+  }
+
+  Ok(last_commit(&Repository::open(&vault.path)?).ok().map(|commit| commit.id()))
+}
+
+pub(crate) fn history(vault: &VaultContext) -> Result<Vec<Snapshot>, Box<dyn Error>> {
+  if !exists(vault) {
+    return Err(VaultError::throw("local git repository does not exist"));
+  }
+
+  let repo = Repository::open(&vault.path)?;
+  let mut revwalk = repo.revwalk()?;
+  let mut snapshots = Vec::new();
+
+  revwalk.push_head()?;
+  revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+  for id in revwalk {
+    let commit = repo.find_commit(id?)?;
+    let metadata = match commit.tree()?.get_path(Path::new(util::METADATA_FILE)) {
+      Ok(entry) => Some(repo.find_blob(entry.id())?.content().to_vec()),
+      Err(_) => None,
+    };
+
+    snapshots.push(Snapshot {
+      id: commit.id(),
+      parent: commit.parent_id(0).ok(),
+      summary: commit.summary().unwrap_or_default().to_string(),
+      author: commit.author().name().unwrap_or_default().to_string(),
+      timestamp: commit.time().seconds() as u64,
+      metadata,
+    });
+  }
+
+  Ok(snapshots)
+}
+
+/// Map the virtual paths of an index to the blobs of their packs at a
+/// commit, so that entries modified in place can be told apart.
+pub(crate) fn packs(vault: &VaultContext, commit: Option<Oid>, index: &HashMap<String, String>) -> Result<HashMap<String, Oid>, Box<dyn Error>> {
+  let commit = match commit {
+    Some(commit) => commit,
+    None => return Ok(index.keys().map(|path| (path.clone(), Oid::zero())).collect()),
+  };
+
+  let repo = Repository::open(&vault.path)?;
+  let tree = repo.find_commit(commit)?.tree()?;
+
+  Ok(
+    index
+      .iter()
+      .map(|(path, pack)| (path.clone(), tree.get_path(Path::new(pack)).map(|entry| entry.id()).unwrap_or_else(|_| Oid::zero())))
+      .collect(),
+  )
+}
+
+pub(crate) fn read_file(vault: &VaultContext, revision: &str, path: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+  if !exists(vault) {
+    return Err(VaultError::throw("local git repository does not exist"));
+  }
+
+  let repo = Repository::open(&vault.path)?;
+  let commit = repo
+    .revparse_single(revision)
+    .and_then(|object| object.peel_to_commit())
+    .map_err(|_| VaultError::throw(&format!("revision {} does not exist", revision)))?;
+
+  let entry = commit
+    .tree()?
+    .get_path(Path::new(path))
+    .map_err(|_| VaultError::throw(&format!("{} does not exist at revision {}", path, revision)))?;
+
+  let content = repo.find_blob(entry.id())?.content().to_vec();
+
+  Ok(content)
+}
+
+/// Rewrite the history of the current branch without the files that do not
+/// exist in its last commit anymore, optionally squashing the commits made
+/// before a given time into a single root commit.
+///
+/// The previous history is kept under a backup reference, unless nothing
+/// was rewritten or this is a dry run.
+pub(crate) fn purge(vault: &VaultContext, before: Option<u64>, dry_run: bool) -> Result<Purge, Box<dyn Error>> {
+  if !exists(vault) {
+    return Err(VaultError::throw("local git repository does not exist"));
+  }
+
+  let repo = Repository::open(&vault.path)?;
+  let head = repo.head()?;
+
+  let branch = match head.name() {
+    Some(branch) if head.is_branch() => branch.to_string(),
+    _ => return Err(VaultError::throw("cannot purge the history of a detached HEAD")),
+  };
+
+  let last = last_commit(&repo)?;
+  let mut kept = HashSet::new();
+
+  last.tree()?.walk(TreeWalkMode::PreOrder, |root, entry| {
+    if entry.kind() == Some(ObjectType::Blob) {
+      kept.insert(format!("{}{}", root, entry.name().unwrap_or_default()));
+    }
+
+    TreeWalkResult::Ok
+  })?;
+
+  let mut revwalk = repo.revwalk()?;
+  revwalk.push(last.id())?;
+  revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+
+  let ids = revwalk.collect::<Result<Vec<Oid>, git2::Error>>()?;
+  let mut commits = Vec::new();
+
+  for id in ids {
+    commits.push(repo.find_commit(id)?);
+  }
+
+  // History is squashed up to the most recent commit of the current branch
+  // made before the date, along with all of its ancestors, so that the new
+  // root commit holds the vault as it was at that commit.
+  let cut = match before {
+    Some(before) => squash_point(&last, before)?,
+    None => None,
+  };
+
+  let ancestors = match &cut {
+    Some(cut) => {
+      let mut revwalk = repo.revwalk()?;
+      revwalk.push(cut.id())?;
+
+      revwalk.collect::<Result<HashSet<Oid>, git2::Error>>()?
+    }
+    None => HashSet::new(),
+  };
+
+  let (squashed, commits): (Vec<Commit>, Vec<Commit>) = commits.into_iter().partition(|commit| ancestors.contains(&commit.id()));
+
+  let mut removed = BTreeSet::new();
+  let mut rewritten = HashMap::new();
+  let mut count = 0;
+
+  if let Some(cut) = &cut {
+    let tree = repo.find_tree(filter_tree(&repo, &cut.tree()?, "", &kept, &mut removed)?)?;
+    let root = repo.commit(None, &cut.author(), &cut.committer(), "Squashed previous history.", &tree, &[])?;
+
+    for commit in &squashed {
+      rewritten.insert(commit.id(), root);
+    }
+  }
+
+  for commit in &commits {
+    let tree = filter_tree(&repo, &commit.tree()?, "", &kept, &mut removed)?;
+    let mut parents = Vec::new();
+
+    for parent in commit.parent_ids() {
+      let parent = rewritten.get(&parent).cloned().unwrap_or(parent);
+
+      if !parents.contains(&parent) {
+        parents.push(parent);
+      }
+    }
+
+    if tree == commit.tree_id() && parents.iter().eq(commit.parent_ids().collect::<Vec<Oid>>().iter()) {
+      continue;
+    }
+
+    let tree = repo.find_tree(tree)?;
+    let parents = parents.into_iter().map(|parent| repo.find_commit(parent)).collect::<Result<Vec<Commit>, git2::Error>>()?;
+
+    let id = repo.commit(
+      None,
+      &commit.author(),
+      &commit.committer(),
+      commit.message().unwrap_or_default(),
+      &tree,
+      &parents.iter().collect::<Vec<&Commit>>(),
+    )?;
+
+    rewritten.insert(commit.id(), id);
+    count += 1;
+  }
+
+  let head = rewritten.get(&last.id()).cloned().unwrap_or_else(|| last.id());
+  let mut backup = None;
+
+  if head != last.id() && !dry_run {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs()).unwrap_or_default();
+    let name = format!("{}/{}-{:.8}", BACKUP_REFERENCE, now, last.id());
+
+    repo.reference(&name, last.id(), false, "knox: history before purge")?;
+    repo.reference(&branch, head, true, "knox: purged history")?;
+
+    backup = Some(name);
+  }
+
+  Ok(Purge {
+    files: removed.into_iter().collect(),
+    commits: count,
+    squashed: squashed.len(),
+    backup,
+  })
+}
+
+/// Find the most recent commit made before `before` on the first-parent
+/// history of `last`.
+fn squash_point<'a>(last: &Commit<'a>, before: u64) -> Result<Option<Commit<'a>>, git2::Error> {
+  let mut commit = last.clone();
+
+  loop {
+    if (commit.time().seconds() as u64) < before {
+      return Ok(Some(commit));
+    }
+
+    if commit.parent_count() == 0 {
+      return Ok(None);
+    }
+
+    commit = commit.parent(0)?;
+  }
+}
+
+/// Rebuild a tree with only the files listed in `kept`, dropping the
+/// directories left empty.
+fn filter_tree(repo: &Repository, tree: &Tree, prefix: &str, kept: &HashSet<String>, removed: &mut BTreeSet<String>) -> Result<Oid, git2::Error> {
+  let mut builder = repo.treebuilder(Some(tree))?;
+
+  for entry in tree.iter() {
+    let name = entry.name().unwrap_or_default();
+    let path = format!("{}{}", prefix, name);
+
+    if entry.kind() == Some(ObjectType::Tree) {
+      let subtree = repo.find_tree(entry.id())?;
+      let filtered = filter_tree(repo, &subtree, &format!("{}/", path), kept, removed)?;
+
+      if repo.find_tree(filtered)?.is_empty() {
+        builder.remove(name)?;
+      } else if filtered != entry.id() {
+        builder.insert(name, filtered, entry.filemode())?;
+      }
+    } else if !kept.contains(&path) {
+      removed.insert(path);
+      builder.remove(name)?;
+    }
+  }
+
+  builder.write()
+}
+
+fn author() -> Result<Signature<'static>, git2::Error> {
+  let (name, email) = Config::open_default()?
+    .snapshot()
+    .map(|c| {
+      (
+        c.get_string("user.name").unwrap_or_else(|_| "Knox".to_string()),
+        c.get_string("user.email").unwrap_or_else(|_| "N/A".to_string()),
+      )
+    })
+    .unwrap_or(("Knox".to_string(), "N/A".to_string()));
+
+  Signature::now(&name, &email)
+}
+
+fn remote_name(config: &GitConfig) -> String {
+  match config.get_remote() {
+    "" => DEFAULT_REMOTE.to_string(),
+    remote => remote.to_string(),
+  }
+}
+
+fn branch_name(config: &GitConfig, repo: &Repository) -> String {
+  if !config.get_branch().is_empty() {
+    return config.get_branch().to_string();
+  }
+
+  repo
+    .find_reference("HEAD")
+    .ok()
+    .and_then(|head| head.symbolic_target().and_then(|target| target.strip_prefix("refs/heads/")).map(String::from))
+    .unwrap_or_else(|| DEFAULT_BRANCH.to_string())
+}
+
+fn credentials(config: &GitConfig, vault: Option<&VaultContext>) -> Result<Credentials, Box<dyn Error>> {
+  let credential = config.get_credential();
+
+  match config.get_auth() {
+    GitAuth::AGENT => Ok(Credentials::Agent),
+    GitAuth::KEY_FILE => Ok(Credentials::KeyFile(PathBuf::from(credential))),
+    GitAuth::TOKEN_VARIABLE => match env::var(credential) {
+      Ok(token) => Ok(Credentials::Token(token)),
+      Err(_) => Err(VaultError::throw(&format!("the git token should be provided in the {} environment variable", credential))),
+    },
+    GitAuth::TOKEN_ENTRY => {
+      let attribute = match config.get_attribute() {
+        "" => DEFAULT_TOKEN_ATTRIBUTE,
+        attribute => attribute,
+      };
+
+      let vault = vault.ok_or_else(|| VaultError::throw("a token stored in an entry cannot be used before the vault is cloned"))?;
+
+      match vault.read_entry(credential)?.get_attributes().get(attribute).map(|attribute| attribute.value()) {
+        Some(AttributeValue::String(token)) => Ok(Credentials::Token(token)),
+        _ => Err(VaultError::throw(&format!("the git token should be in the {} attribute of {}", attribute, credential))),
+      }
+    }
+  }
+}
+
+fn remote_callbacks<'a>(config: &GitConfig, vault: Option<&VaultContext>) -> Result<RemoteCallbacks<'a>, Box<dyn Error>> {
+  let credentials = credentials(config, vault)?;
+  let username = match config.get_username() {
+    "" => DEFAULT_USERNAME.to_string(),
+    username => username.to_string(),
+  };
+
   let mut retry = false;
   let mut callbacks = RemoteCallbacks::new();
 
-  callbacks.credentials(|_, user, credentials| {
+  callbacks.credentials(move |_, user, allowed| {
+    let user = user.unwrap_or(&username);
+
     if retry {
-      return Err(git2::Error::from_str("ssh agent did not provide valid public key"));
+      return Err(git2::Error::from_str(match credentials {
+        Credentials::Agent => "ssh agent did not provide valid public key",
+        _ => "the git remote refused the configured credentials",
+      }));
     }
 
-    if credentials.contains(git2::CredentialType::USERNAME) {
-      return Cred::username(user.unwrap_or("git"));
+    if allowed.contains(git2::CredentialType::USERNAME) {
+      return Cred::username(user);
     }
 
     retry = true;
 
-    Cred::ssh_key_from_agent("git")
+    match &credentials {
+      Credentials::Agent => Cred::ssh_key_from_agent(user),
+      Credentials::KeyFile(path) => Cred::ssh_key(user, None, path, None),
+      Credentials::Token(token) => Cred::userpass_plaintext(user, token),
+    }
   });
 
-  let mut options = PushOptions::new();
-  options.remote_callbacks(callbacks);
-
-  remote.push(&["refs/heads/master:refs/heads/master"], Some(&mut options))?;
-
-  Ok(())
+  Ok(callbacks)
 }
 
 #[cfg(test)]
@@ -117,10 +677,7 @@ mod tests {
   #[test]
   fn init() {
     let tmp = spec::setup();
-    let context = VaultContext {
-      path: tmp.path().to_str().unwrap().to_string(),
-      vault: Vault::new(),
-    };
+    let context = VaultContext::create(tmp.path(), &[]).expect("could not create vault");
 
     git::init(&context).expect("could not initialize git repository");
 
@@ -138,6 +695,23 @@ mod tests {
 
       assert_eq!(commit.summary().unwrap_or(""), "Initialized knox repository.");
     }
+
+    let config = repo.config().expect("could not get repository configuration");
+
+    assert_eq!(
+      repo
+        .get_attr(std::path::Path::new(util::METADATA_FILE), "merge", git2::AttrCheckFlags::FILE_THEN_INDEX)
+        .expect("could not get attribute"),
+      Some("knox")
+    );
+    assert_eq!(config.get_string("merge.knox.driver").expect("could not get merge driver"), "knox git merge-driver %O %A %B");
+    assert_eq!(
+      repo
+        .get_attr(std::path::Path::new("ab/abcdef"), "diff", git2::AttrCheckFlags::FILE_THEN_INDEX)
+        .expect("could not get attribute"),
+      Some("knox")
+    );
+    assert_eq!(config.get_string("diff.knox.textconv").expect("could not get textconv driver"), "knox git textconv");
   }
 
   #[test]
@@ -176,4 +750,40 @@ mod tests {
 
     assert_eq!(remote.url().unwrap_or(""), "https://git.example.com");
   }
+
+  #[test]
+  fn configuration() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let repo = Repository::open(tmp.path()).expect("could not open repository");
+
+    assert_eq!(super::remote_name(context.get_local_config().get_git()), "origin");
+    assert_eq!(super::branch_name(context.get_local_config().get_git(), &repo), "master");
+
+    let mut entry = Entry::default();
+    entry.add_confidential_attribute("token", "foobar");
+    context.write_entry("forge", &entry).expect("could not write entry");
+
+    let mut config = context.get_local_config();
+    config.mut_git().set_remote("upstream".to_string());
+    config.mut_git().set_branch("main".to_string());
+    config.mut_git().set_auth(GitAuth::TOKEN_ENTRY);
+    config.mut_git().set_credential("forge".to_string());
+    context.write_local_config(config.clone()).expect("could not write local config");
+
+    assert_eq!(super::remote_name(context.get_local_config().get_git()), "upstream");
+    assert_eq!(super::branch_name(context.get_local_config().get_git(), &repo), "main");
+    assert!(super::credentials(config.get_git(), None).is_err());
+    assert!(super::credentials(config.get_git(), Some(&context)).is_err());
+
+    config.mut_git().set_attribute("token".to_string());
+    context.write_local_config(config).expect("could not write local config");
+
+    match super::credentials(context.get_local_config().get_git(), Some(&context)).expect("could not get credentials") {
+      super::Credentials::Token(token) => assert_eq!(token, "foobar"),
+      _ => panic!("credentials should be a token"),
+    }
+  }
 }
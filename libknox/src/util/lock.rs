@@ -0,0 +1,181 @@
+//! Advisory locking of a vault directory
+
+use std::collections::hash_map::DefaultHasher;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::hash::Hasher;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::process;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use fs2::FileExt;
+
+use crate::util::VaultError;
+
+pub(crate) const LOCK_FILE: &str = "_knox.lock";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+static TIMEOUT: AtomicU64 = AtomicU64::new(10_000);
+
+/// Set how long to wait for a lock held by another process.
+///
+/// Opening or modifying a vault that is locked by another process will wait
+/// for the lock to be released for at most this duration, after which an
+/// error naming the holding process is returned. Defaults to ten seconds.
+///
+/// # Arguments
+///
+///  * `timeout` - the maximum duration to wait for.
+pub fn set_lock_timeout(timeout: Duration) {
+  TIMEOUT.store(timeout.as_millis() as u64, Ordering::SeqCst);
+}
+
+/// Fingerprint the content of a file, in order to detect concurrent
+/// modifications.
+pub(crate) fn digest<P>(path: P) -> Result<Option<u64>, Box<dyn Error>>
+where
+  P: AsRef<Path>,
+{
+  if !path.as_ref().exists() {
+    return Ok(None);
+  }
+
+  Ok(Some(digest_bytes(&fs::read(path)?)))
+}
+
+pub(crate) fn digest_bytes(data: &[u8]) -> u64 {
+  let mut hasher = DefaultHasher::new();
+  hasher.write(data);
+  hasher.finish()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum LockMode {
+  Shared,
+  Exclusive,
+}
+
+pub(crate) struct Lock {
+  file: File,
+  mode: LockMode,
+}
+
+impl Lock {
+  pub(crate) fn acquire<P>(path: P, mode: LockMode) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    Self::acquire_within(path, mode, Duration::from_millis(TIMEOUT.load(Ordering::SeqCst)))
+  }
+
+  /// Acquire the lock, waiting for at most `timeout` instead of the timeout
+  /// set with [set_lock_timeout](fn.set_lock_timeout.html).
+  pub(crate) fn acquire_within<P>(path: P, mode: LockMode, timeout: Duration) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path.as_ref().join(LOCK_FILE))?;
+    let mut lock = Self { file, mode: LockMode::Shared };
+
+    lock.wait(mode, timeout)?;
+
+    Ok(lock)
+  }
+
+  fn wait(&mut self, mode: LockMode, timeout: Duration) -> Result<(), Box<dyn Error>> {
+    let start = Instant::now();
+
+    loop {
+      let result = match mode {
+        LockMode::Shared => FileExt::try_lock_shared(&self.file),
+        LockMode::Exclusive => FileExt::try_lock_exclusive(&self.file),
+      };
+
+      match result {
+        Ok(()) => break,
+
+        Err(ref err) if err.kind() == fs2::lock_contended_error().kind() => {
+          if start.elapsed() >= timeout {
+            return Err(self.contended(timeout));
+          }
+
+          thread::sleep(POLL_INTERVAL);
+        }
+
+        Err(err) => return Err(Box::new(err)),
+      }
+    }
+
+    self.mode = mode;
+
+    // While we hold the lock, nobody else holds it exclusively, so any
+    // leftover process ID is stale and can be replaced.
+    self.file.set_len(0)?;
+
+    if mode == LockMode::Exclusive {
+      self.file.seek(SeekFrom::Start(0))?;
+      self.file.write_all(process::id().to_string().as_bytes())?;
+      self.file.sync_all()?;
+    }
+
+    Ok(())
+  }
+
+  fn contended(&mut self, timeout: Duration) -> Box<dyn Error> {
+    let mut holder = String::new();
+
+    let _ = self.file.seek(SeekFrom::Start(0));
+    let _ = self.file.read_to_string(&mut holder);
+
+    match holder.trim() {
+      "" => VaultError::throw(&format!("the vault is locked by another process, gave up after {}s", timeout.as_secs())),
+      pid => VaultError::throw(&format!("the vault is locked by process {}, gave up after {}s", pid, timeout.as_secs())),
+    }
+  }
+}
+
+impl Drop for Lock {
+  fn drop(&mut self) {
+    if self.mode == LockMode::Exclusive {
+      let _ = self.file.set_len(0);
+    }
+
+    let _ = FileExt::unlock(&self.file);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::time::Duration;
+
+  use knox_testing::spec;
+
+  use super::{Lock, LockMode};
+
+  #[test]
+  fn acquire() {
+    let tmp = spec::setup();
+    let timeout = Duration::from_millis(0);
+
+    let first = Lock::acquire_within(tmp.path(), LockMode::Shared, timeout).expect("could not acquire shared lock");
+    let second = Lock::acquire_within(tmp.path(), LockMode::Shared, timeout).expect("could not acquire shared lock");
+
+    assert!(Lock::acquire_within(tmp.path(), LockMode::Exclusive, timeout).is_err());
+
+    drop(first);
+    drop(second);
+
+    let exclusive = Lock::acquire_within(tmp.path(), LockMode::Exclusive, timeout).expect("could not acquire exclusive lock");
+    let error = Lock::acquire_within(tmp.path(), LockMode::Shared, timeout).err().expect("could acquire contended lock");
+
+    assert_eq!(error.to_string(), format!("the vault is locked by process {}, gave up after 0s", std::process::id()));
+
+    drop(exclusive);
+
+    assert!(Lock::acquire_within(tmp.path(), LockMode::Exclusive, timeout).is_ok());
+  }
+}
@@ -1,7 +1,12 @@
 mod error;
+mod file;
 pub mod git;
+mod lock;
 mod path;
 pub mod totp;
 
 pub use self::error::*;
+pub(crate) use self::file::*;
+pub use self::lock::set_lock_timeout;
+pub(crate) use self::lock::{digest, digest_bytes, Lock, LockMode, LOCK_FILE};
 pub(crate) use self::path::*;
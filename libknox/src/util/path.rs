@@ -7,6 +7,8 @@ use uuid::Uuid;
 use crate::*;
 
 pub(crate) const METADATA_FILE: &str = "_knox.meta";
+pub(crate) const HEADER_FILE: &str = "_knox.header";
+pub(crate) const LOCAL_FILE: &str = "_knox.local";
 
 pub(crate) fn create_parents<T>(context: &VaultContext, path: &T) -> Result<(), Box<dyn Error>>
 where
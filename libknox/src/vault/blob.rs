@@ -0,0 +1,467 @@
+//! Store large file [Attributes](struct.Attribute.html) outside of their
+//! [Entry](struct.Entry.html).
+//!
+//! The content of a blob attribute is encrypted as a stream into its own file
+//! under the `_knox.blobs` directory, and the attribute only references it.
+//! Reading an entry therefore does not require decrypting its attachments,
+//! and attachments are never held in memory as a whole. The attribute records
+//! the SHA-256 digest of the encrypted blob, so that the signature of the
+//! entry covers the blob as well.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fs::{create_dir_all, remove_file, File};
+use std::io::{self, Cursor, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+use std::thread;
+
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use super::attribute::AttributeValue;
+use super::context::VaultContext;
+use crate::pb::*;
+use crate::util::{self, git, VaultError};
+
+/// Size above which file attributes should be stored as blobs, in bytes.
+pub const BLOB_THRESHOLD: u64 = 64 * 1024;
+
+impl Attribute {
+  /// Check if the content of an `Attribute` is stored in a separate blob.
+  pub fn is_blob(&self) -> bool {
+    !self.blob.is_empty()
+  }
+}
+
+impl Entry {
+  /// List the blobs referenced by the attributes of an `Entry` and their
+  /// history.
+  pub fn get_blobs(&self) -> BTreeSet<&str> {
+    self.blob_attributes().map(Attribute::get_blob).collect()
+  }
+
+  /// List the blobs referenced by an `Entry` along with their recorded
+  /// digest, which is empty for blobs written before digests were recorded.
+  pub(crate) fn get_blob_digests(&self) -> BTreeMap<String, Vec<u8>> {
+    let mut digests: BTreeMap<String, Vec<u8>> = BTreeMap::new();
+
+    for attribute in self.blob_attributes() {
+      let digest = digests.entry(attribute.get_blob().to_string()).or_default();
+
+      if digest.is_empty() {
+        *digest = attribute.get_digest().to_vec();
+      }
+    }
+
+    digests
+  }
+
+  /// Record the digest of a blob in all the attributes referencing it.
+  pub(crate) fn set_blob_digest(&mut self, blob: &str, digest: &[u8]) {
+    let current = self.attributes.values_mut();
+    let previous = self.history.values_mut().flat_map(|history| history.mut_revisions().iter_mut().map(Revision::mut_attribute));
+
+    for attribute in current.chain(previous).filter(|attribute| attribute.get_blob() == blob) {
+      attribute.set_digest(digest.to_vec());
+    }
+  }
+
+  /// Point all the attributes referencing a blob to its `replacement`.
+  pub(crate) fn replace_blob(&mut self, blob: &str, replacement: &str, digest: &[u8]) {
+    let current = self.attributes.values_mut();
+    let previous = self.history.values_mut().flat_map(|history| history.mut_revisions().iter_mut().map(Revision::mut_attribute));
+
+    for attribute in current.chain(previous).filter(|attribute| attribute.get_blob() == blob) {
+      attribute.set_blob(replacement.to_string());
+      attribute.set_digest(digest.to_vec());
+    }
+  }
+
+  fn blob_attributes(&self) -> impl Iterator<Item = &Attribute> {
+    let current = self.get_attributes().values();
+    let previous = self.get_history().values().flat_map(|history| history.get_revisions().iter().map(Revision::get_attribute));
+
+    current.chain(previous).filter(|attribute| attribute.is_blob())
+  }
+}
+
+impl VaultContext {
+  /// Encrypt a stream into a new blob.
+  ///
+  /// Returns a file attribute referencing the blob, which should then be
+  /// added to an [Entry](struct.Entry.html). Blobs that end up not being
+  /// referenced by any entry are reported by
+  /// [VaultContext::check](struct.VaultContext.html#method.check).
+  ///
+  /// This requires the public keys of all identities used in the vault.
+  ///
+  /// # Arguments
+  ///
+  ///  * `reader` - the stream providing the content of the blob.
+  pub fn write_blob<R>(&self, reader: R) -> Result<Attribute, Box<dyn Error>>
+  where
+    R: Read + Send,
+  {
+    let blob = Uuid::new_v4().to_simple().to_string();
+    let mut reader = CountingReader { inner: reader, count: 0 };
+    let mut digest = Sha256::new();
+
+    self.lock_exclusive()?;
+
+    create_dir_all(util::normalize_path(self, &util::BLOB_DIRECTORY))?;
+    util::write_stream_atomic(self, &blob_path(&blob), |file| {
+      let mut writer = DigestWriter { inner: file, digest: &mut digest };
+
+      self.backend().encrypt_stream(self.vault.get_identities(), &mut reader, &mut writer)
+    })?;
+
+    Ok(Attribute {
+      file: true,
+      blob,
+      size: reader.count,
+      digest: digest.finalize().to_vec(),
+      ..Attribute::default()
+    })
+  }
+
+  /// Write the content of a file attribute to a stream.
+  ///
+  /// Blobs are decrypted as a stream, while the content of other attributes
+  /// is written as is.
+  ///
+  /// # Arguments
+  ///
+  ///  * `attribute` - the attribute to be read.
+  ///  * `writer`    - the stream receiving the content of the attribute.
+  pub fn read_blob<W>(&self, attribute: &Attribute, mut writer: W) -> Result<(), Box<dyn Error>>
+  where
+    W: Write + Send,
+  {
+    if !attribute.is_blob() {
+      match attribute.value() {
+        AttributeValue::String(string) => writer.write_all(string.as_bytes())?,
+        AttributeValue::Binary(bytes) => writer.write_all(&bytes)?,
+      }
+
+      return Ok(());
+    }
+
+    let mut file = self.open_blob(attribute.get_blob(), attribute.get_digest())?;
+
+    self.backend().decrypt_stream(&mut file, &mut writer)
+  }
+
+  /// Write the content of a file attribute to a stream, as it was at a
+  /// previous revision.
+  ///
+  /// Behaves like [VaultContext::read_blob](struct.VaultContext.html#method.read_blob),
+  /// but blobs are read from the git repository at that revision, so that
+  /// they can be read after being removed or encrypted again.
+  ///
+  /// # Arguments
+  ///
+  ///  * `revision`  - the revision of the git repository to read from.
+  ///  * `attribute` - the attribute to be read, as it was at that revision.
+  ///  * `writer`    - the stream receiving the content of the attribute.
+  pub fn read_blob_at<W>(&self, revision: &str, attribute: &Attribute, mut writer: W) -> Result<(), Box<dyn Error>>
+  where
+    W: Write + Send,
+  {
+    if !attribute.is_blob() {
+      return self.read_blob(attribute, writer);
+    }
+
+    let ciphertext = self.read_blob_revision(revision, attribute.get_blob(), attribute.get_digest())?;
+
+    self.backend().decrypt_stream(&mut Cursor::new(ciphertext), &mut writer)
+  }
+
+  /// Encrypt a blob again for the current identities of the vault, after
+  /// checking it against its recorded `digest`.
+  ///
+  /// The blob is written under a new identifier, leaving the existing one
+  /// untouched until no entry references it anymore. Returns the identifier
+  /// and the digest of the new blob.
+  pub(crate) fn reencrypt_blob(&self, blob: &str, digest: &[u8]) -> Result<(String, Vec<u8>), Box<dyn Error>> {
+    let file = self.open_blob(blob, digest)?;
+
+    self.reencrypt_stream(blob, file)
+  }
+
+  /// Decrypt the encrypted content of a blob from a stream, and encrypt it
+  /// again into a new blob. Returns the identifier and the digest of the new
+  /// blob.
+  pub(crate) fn reencrypt_stream<R>(&self, blob: &str, mut reader: R) -> Result<(String, Vec<u8>), Box<dyn Error>>
+  where
+    R: Read + Send + 'static,
+  {
+    let replacement = Uuid::new_v4().to_simple().to_string();
+    let (sender, receiver) = sync_channel(16);
+    let backend = self.backend();
+    let mut digest = Sha256::new();
+
+    self.lock_exclusive()?;
+
+    let decrypt = thread::spawn(move || backend.decrypt_stream(&mut reader, &mut ChannelWriter(sender)).map_err(|err| err.to_string()));
+
+    create_dir_all(util::normalize_path(self, &util::BLOB_DIRECTORY))?;
+    util::write_stream_atomic(self, &blob_path(&replacement), |file| {
+      let mut reader = ChannelReader {
+        receiver,
+        buffer: Vec::new(),
+        position: 0,
+      };
+
+      self
+        .backend()
+        .encrypt_stream(self.vault.get_identities(), &mut reader, &mut DigestWriter { inner: file, digest: &mut digest })?;
+
+      match decrypt.join() {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(err)) => Err(VaultError::throw(&format!("could not decrypt blob {}: {}", blob, err))),
+        Err(_) => Err(VaultError::throw(&format!("could not decrypt blob {}", blob))),
+      }
+    })?;
+
+    Ok((replacement, digest.finalize().to_vec()))
+  }
+
+  /// Read the encrypted content of a blob as it was at a previous revision,
+  /// after checking it against its recorded `digest`.
+  pub(crate) fn read_blob_revision(&self, revision: &str, blob: &str, digest: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+    let ciphertext = git::read_file(self, revision, &blob_path(blob)).map_err(|err| VaultError::throw(&format!("could not find blob {} at revision {}: {}", blob, revision, err)))?;
+
+    if digest.is_empty() {
+      self.report_unverified(&format!("blob {} does not record its digest", blob))?;
+    } else if Sha256::digest(&ciphertext).as_slice() != digest {
+      return Err(VaultError::throw(&format!("blob {} does not match its digest at revision {}", blob, revision)));
+    }
+
+    Ok(ciphertext)
+  }
+
+  /// Compute the digest of an existing blob, if it exists.
+  pub(crate) fn digest_blob(&self, blob: &str) -> Result<Option<Vec<u8>>, Box<dyn Error>> {
+    match File::open(util::normalize_path(self, &blob_path(blob))) {
+      Ok(mut file) => Ok(Some(digest_file(&mut file)?)),
+      Err(ref err) if err.kind() == ErrorKind::NotFound => Ok(None),
+      Err(err) => Err(VaultError::throw(&format!("could not open blob {}: {}", blob, err))),
+    }
+  }
+
+  pub(crate) fn remove_blob(&self, blob: &str) -> Result<(), Box<dyn Error>> {
+    self.lock_exclusive()?;
+
+    match remove_file(util::normalize_path(self, &blob_path(blob))) {
+      Err(ref err) if err.kind() != ErrorKind::NotFound => Err(VaultError::throw(&format!("could not remove blob {}: {}", blob, err))),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl VaultContext {
+  fn open_blob(&self, blob: &str, digest: &[u8]) -> Result<File, Box<dyn Error>> {
+    let mut file = File::open(util::normalize_path(self, &blob_path(blob))).map_err(|err| VaultError::throw(&format!("could not open blob {}: {}", blob, err)))?;
+
+    if digest.is_empty() {
+      self.report_unverified(&format!("blob {} does not record its digest", blob))?;
+    } else if digest_file(&mut file)? != digest {
+      return Err(VaultError::throw(&format!("blob {} does not match its digest", blob)));
+    }
+
+    Ok(file)
+  }
+}
+
+pub(crate) fn blob_path(blob: &str) -> String {
+  format!("{}/{}", util::BLOB_DIRECTORY, blob)
+}
+
+fn digest_file(file: &mut File) -> io::Result<Vec<u8>> {
+  let mut digest = Sha256::new();
+
+  io::copy(file, &mut digest)?;
+  file.seek(SeekFrom::Start(0))?;
+
+  Ok(digest.finalize().to_vec())
+}
+
+struct CountingReader<R> {
+  inner: R,
+  count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+  fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+    let count = self.inner.read(buffer)?;
+    self.count += count as u64;
+
+    Ok(count)
+  }
+}
+
+struct DigestWriter<'a, W> {
+  inner: W,
+  digest: &'a mut Sha256,
+}
+
+impl<W: Write> Write for DigestWriter<'_, W> {
+  fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+    let count = self.inner.write(buffer)?;
+    self.digest.update(&buffer[..count]);
+
+    Ok(count)
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    self.inner.flush()
+  }
+}
+
+struct ChannelWriter(SyncSender<Vec<u8>>);
+
+impl Write for ChannelWriter {
+  fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+    self.0.send(buffer.to_vec()).map_err(|_| io::Error::new(ErrorKind::BrokenPipe, "blob stream was closed"))?;
+
+    Ok(buffer.len())
+  }
+
+  fn flush(&mut self) -> io::Result<()> {
+    Ok(())
+  }
+}
+
+struct ChannelReader {
+  receiver: Receiver<Vec<u8>>,
+  buffer: Vec<u8>,
+  position: usize,
+}
+
+impl Read for ChannelReader {
+  fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+    if self.position == self.buffer.len() {
+      match self.receiver.recv() {
+        Ok(chunk) => {
+          self.buffer = chunk;
+          self.position = 0;
+        }
+        Err(_) => return Ok(0),
+      }
+    }
+
+    let count = (&self.buffer[self.position..]).read(buffer)?;
+    self.position += count;
+
+    Ok(count)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn write_and_read() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let data = vec![42; 512 * 1024];
+    let attribute = context.write_blob(data.as_slice()).expect("could not write blob");
+
+    assert!(attribute.is_blob());
+    assert_eq!(attribute.get_size(), data.len() as u64);
+
+    let mut entry = Entry::default();
+    entry.attributes.insert("backup".to_string(), attribute);
+    entry.add_attribute("username", "bob");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let entry = context.read_entry("foo/bar").expect("could not read entry");
+    let attribute = entry.get_attributes().get("backup").expect("could not get attribute");
+    let mut content = Vec::new();
+
+    context.read_blob(attribute, &mut content).expect("could not read blob");
+
+    assert_eq!(content, data);
+    assert_eq!(entry.get_blobs().len(), 1);
+  }
+
+  #[test]
+  fn tampered() {
+    let tmp = spec::setup();
+    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let first = context.write_blob(&b"first"[..]).expect("could not write blob");
+    let second = context.write_blob(&b"second"[..]).expect("could not write blob");
+
+    fs::copy(
+      tmp.path().join(util::BLOB_DIRECTORY).join(second.get_blob()),
+      tmp.path().join(util::BLOB_DIRECTORY).join(first.get_blob()),
+    )
+    .expect("could not copy blob");
+
+    assert!(context.read_blob(&second, Vec::new()).is_ok());
+    assert!(context.read_blob(&first, Vec::new()).is_err());
+  }
+
+  #[test]
+  fn read_at() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.attributes.insert("backup".to_string(), context.write_blob(&b"content"[..]).expect("could not write blob"));
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    context.delete_entry("foo/bar").expect("could not delete entry");
+    context.commit("Deleted entry.").expect("could not commit changes");
+
+    let (entry, _) = context.read_entry_at("HEAD~1", "foo/bar").expect("could not read entry");
+    let attribute = entry.get_attributes().get("backup").expect("could not get attribute");
+    let mut content = Vec::new();
+
+    context.read_blob_at("HEAD~1", attribute, &mut content).expect("could not read blob");
+
+    assert_eq!(content, b"content");
+    assert!(context.read_blob(attribute, Vec::new()).is_err());
+    assert!(context.read_blob_at("HEAD", attribute, Vec::new()).is_err());
+  }
+
+  #[test]
+  fn discard() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    let attribute = context.write_blob(&b"first"[..]).expect("could not write blob");
+    let first = attribute.get_blob().to_string();
+    entry.attributes.insert("backup".to_string(), attribute);
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    for index in 0..=HISTORY_SIZE {
+      let mut entry = context.read_entry("foo/bar").expect("could not read entry");
+      let attribute = context.write_blob(index.to_string().as_bytes()).expect("could not write blob");
+      entry.attributes.insert("backup".to_string(), attribute);
+
+      context.write_entry("foo/bar", &entry).expect("could not write entry");
+    }
+
+    let blobs = tmp.path().join(util::BLOB_DIRECTORY);
+
+    assert!(!blobs.join(&first).exists());
+    assert_eq!(fs::read_dir(&blobs).expect("could not read blobs").count(), HISTORY_SIZE + 1);
+
+    context.delete_entry("foo/bar").expect("could not delete entry");
+
+    assert_eq!(fs::read_dir(&blobs).expect("could not read blobs").count(), 0);
+  }
+}
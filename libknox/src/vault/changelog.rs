@@ -0,0 +1,408 @@
+//! Browse and restore the history of the entries of a
+//! [Vault](struct.Vault.html) across the revisions of its git repository.
+//!
+//! Commits only touch pack files with random names, so the metadata of the
+//! vault is decrypted at every revision to map them back to virtual paths.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::io::Cursor;
+
+use log::*;
+use protobuf::parse_from_bytes;
+
+use super::context::VaultContext;
+use super::diff::Difference;
+use super::pack;
+use super::sync::{self, Change};
+use crate::backend::Signature;
+use crate::pb::*;
+use crate::util::{self, git, VaultError};
+
+type SignedEntry = (Entry, Option<Signature>);
+
+/// A commit that modified an entry, listed by
+/// [VaultContext::log](struct.VaultContext.html#method.log).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+  /// Identifier of the commit.
+  pub commit: String,
+  /// First line of the commit message.
+  pub summary: String,
+  /// Name of the author of the commit.
+  pub author: String,
+  /// Time of the commit, as a UNIX timestamp.
+  pub timestamp: u64,
+  /// How the entry was modified.
+  pub change: Change,
+}
+
+impl VaultContext {
+  /// List the commits that modified the entries of the vault, most recent
+  /// first.
+  ///
+  /// The metadata is decrypted at every revision, and compared with the one
+  /// of its first parent. Revisions whose metadata cannot be decrypted, for
+  /// instance because they predate the local identity being added to the
+  /// vault, are skipped.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - only list the commits that modified this entry.
+  pub fn log(&self, path: Option<&str>) -> Result<Vec<LogEntry>, Box<dyn Error>> {
+    let snapshots = git::history(self)?;
+    let mut indexes = HashMap::new();
+
+    for snapshot in &snapshots {
+      let index = match &snapshot.metadata {
+        Some(metadata) => match self.decrypt_revision(metadata) {
+          Ok(vault) => Some(vault.get_index().clone()),
+          Err(err) => {
+            debug!("could not decrypt the metadata at revision {}: {}", snapshot.id, err);
+            None
+          }
+        },
+        None => None,
+      };
+
+      indexes.insert(snapshot.id, index);
+    }
+
+    let mut log = Vec::new();
+
+    for snapshot in &snapshots {
+      let current = match indexes.get(&snapshot.id) {
+        Some(Some(index)) => git::packs(self, Some(snapshot.id), index)?,
+        _ => continue,
+      };
+
+      let previous = match snapshot.parent.map(|parent| indexes.get(&parent)) {
+        None => HashMap::new(),
+        Some(Some(Some(index))) => git::packs(self, snapshot.parent, index)?,
+        Some(_) => continue,
+      };
+
+      for change in sync::changes(&previous, &current) {
+        if let Some(path) = path {
+          if change.path() != path {
+            continue;
+          }
+        }
+
+        log.push(LogEntry {
+          commit: snapshot.id.to_string(),
+          summary: snapshot.summary.clone(),
+          author: snapshot.author.clone(),
+          timestamp: snapshot.timestamp,
+          change,
+        });
+      }
+    }
+
+    Ok(log)
+  }
+
+  /// Read an [Entry](struct.Entry.html) as it was at a previous revision.
+  ///
+  /// The revision can be anything git understands, such as a commit
+  /// identifier or `HEAD~2`. The signature of the entry is verified according
+  /// to the configured policy.
+  ///
+  /// # Arguments
+  ///
+  ///  * `revision` - the revision of the git repository.
+  ///  * `path`     - the virtual path to the entry.
+  pub fn read_entry_at(&self, revision: &str, path: &str) -> Result<(Entry, Option<Signature>), Box<dyn Error>> {
+    match self.find_entry_at(revision, path)? {
+      Some(entry) => Ok(entry),
+      None => Err(VaultError::throw(&format!("no entry was found at this path at revision {}", revision))),
+    }
+  }
+
+  /// List the differences between two versions of an
+  /// [Entry](struct.Entry.html).
+  ///
+  /// The entry is compared as it was at `from`, by default the last commit,
+  /// with its version at `to`, by default the one currently in the vault. An
+  /// entry missing from one of the versions is compared with an empty entry.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  ///  * `from` - the revision of the git repository of the older version.
+  ///  * `to`   - the revision of the git repository of the newer version.
+  pub fn diff_entry(&self, path: &str, from: Option<&str>, to: Option<&str>) -> Result<Vec<Difference>, Box<dyn Error>> {
+    let before = self.find_entry_at(from.unwrap_or("HEAD"), path)?.map(|(entry, _)| entry);
+
+    let after = match to {
+      Some(revision) => self.find_entry_at(revision, path)?.map(|(entry, _)| entry),
+      None if self.vault.get_index().contains_key(path) => Some(self.read_entry(path)?),
+      None => None,
+    };
+
+    if before.is_none() && after.is_none() {
+      return Err(VaultError::throw(&format!("no entry was found at {} in either version", path)));
+    }
+
+    Ok(before.unwrap_or_default().diff(&after.unwrap_or_default()))
+  }
+
+  /// Write an [Entry](struct.Entry.html) back as it was at a previous
+  /// revision.
+  ///
+  /// Without a revision, the entry must have been deleted, and is restored
+  /// from the most recent revision where it existed. The blobs it references
+  /// are restored from the same revision if they were removed or encrypted
+  /// again since, and encrypted again for the current identities into new
+  /// blobs. Restoring fails if one of them cannot be found at that revision.
+  ///
+  /// The entry is written with
+  /// [VaultContext::write_entry](struct.VaultContext.html#method.write_entry),
+  /// so its current attributes are kept in its history, and it keeps the
+  /// creation time it had at that revision. Returns the revision the entry
+  /// was restored from.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`     - the virtual path to the entry.
+  ///  * `revision` - the revision of the git repository to restore from.
+  pub fn restore_entry(&mut self, path: &str, revision: Option<&str>) -> Result<String, Box<dyn Error>> {
+    let revision = match revision {
+      Some(revision) => revision.to_string(),
+      None if self.vault.get_index().contains_key(path) => {
+        return Err(VaultError::throw("the entry still exists, a revision to restore it from is required"));
+      }
+      None => self.find_deleted(path)?,
+    };
+
+    let (mut entry, _) = self.read_entry_at(&revision, path)?;
+    let mut restored = Vec::new();
+
+    let result = self.restore_blobs(&revision, &mut entry, &mut restored).and_then(|_| self.write_entry(path, &entry));
+
+    if result.is_err() {
+      for blob in &restored {
+        if let Err(err) = self.remove_blob(blob) {
+          debug!("could not remove restored blob {}: {}", blob, err);
+        }
+      }
+    }
+
+    result.map(|_| revision)
+  }
+
+  fn restore_blobs(&self, revision: &str, entry: &mut Entry, restored: &mut Vec<String>) -> Result<(), Box<dyn Error>> {
+    for (blob, digest) in entry.get_blob_digests() {
+      // Blobs encrypted again since the revision do not match the digest it
+      // recorded anymore, and are restored from the revision as well.
+      match self.digest_blob(&blob)? {
+        Some(current) if digest.is_empty() || current == digest => continue,
+        _ => {}
+      }
+
+      let ciphertext = self.read_blob_revision(revision, &blob, &digest)?;
+      let (replacement, digest) = self.reencrypt_stream(&blob, Cursor::new(ciphertext))?;
+
+      restored.push(replacement.clone());
+      entry.replace_blob(&blob, &replacement, &digest);
+    }
+
+    Ok(())
+  }
+
+  fn find_deleted(&self, path: &str) -> Result<String, Box<dyn Error>> {
+    for snapshot in git::history(self)? {
+      let metadata = match &snapshot.metadata {
+        Some(metadata) => metadata,
+        None => continue,
+      };
+
+      match self.decrypt_revision(metadata) {
+        Ok(vault) if vault.get_index().contains_key(path) => return Ok(snapshot.id.to_string()),
+        Ok(_) => {}
+        Err(err) => debug!("could not decrypt the metadata at revision {}: {}", snapshot.id, err),
+      }
+    }
+
+    Err(VaultError::throw(&format!("no entry was ever found at {} in the history of the vault", path)))
+  }
+
+  fn find_entry_at(&self, revision: &str, path: &str) -> Result<Option<SignedEntry>, Box<dyn Error>> {
+    let vault = self.decrypt_revision(&git::read_file(self, revision, util::METADATA_FILE)?)?;
+
+    let pack = match vault.get_index().get(path) {
+      Some(pack) => pack,
+      None => return Ok(None),
+    };
+
+    let (ciphertext, signature) = self.backend().decrypt_and_verify(&git::read_file(self, revision, pack)?)?;
+    let subject = format!("entry {} at revision {}", path, revision);
+
+    self.verify_signature(&subject, signature.as_ref())?;
+
+    let entry = parse_from_bytes::<Entry>(&pack::decompress(&ciphertext)?)?;
+
+    self.verify_location(&subject, &entry, pack)?;
+
+    Ok(Some((entry, signature)))
+  }
+
+  pub(crate) fn decrypt_revision(&self, metadata: &[u8]) -> Result<Vault, Box<dyn Error>> {
+    Ok(parse_from_bytes::<Vault>(&pack::decompress(&self.backend().decrypt(metadata)?)?)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn log() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("username", "foo");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    entry.add_attribute("username", "bar");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Edited entry.").expect("could not commit changes");
+
+    context.write_entry("lorem", &Entry::default()).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    let log = context.log(Some("foo/bar")).expect("could not get log");
+
+    assert_eq!(log.len(), 2);
+    assert_eq!(log[0].summary, "Edited entry.");
+    assert_eq!(log[0].change, Change::Modified { path: "foo/bar".to_string() });
+    assert_eq!(log[1].change, Change::Added { path: "foo/bar".to_string() });
+    assert_eq!(context.log(None).expect("could not get log").len(), 3);
+
+    let (previous, _) = context.read_entry_at(&log[1].commit, "foo/bar").expect("could not read entry");
+
+    assert_eq!(previous.get_attributes().get("username").map(|attribute| attribute.get_value()), Some("foo"));
+    assert!(context.read_entry_at(&log[1].commit, "lorem").is_err());
+    assert!(context.read_entry_at("HEAD", "lorem").is_ok());
+  }
+
+  #[test]
+  fn restore_entry() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.set_created(1);
+    entry.add_attribute("username", "foo");
+    entry.mut_attributes().insert("file".to_string(), context.write_blob(&b"content"[..]).expect("could not write blob"));
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    entry.add_attribute("username", "bar");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Edited entry.").expect("could not commit changes");
+
+    context.delete_entry("foo/bar").expect("could not delete entry");
+    context.commit("Deleted entry.").expect("could not commit changes");
+
+    assert!(context.restore_entry("lorem", None).is_err());
+
+    context.restore_entry("foo/bar", None).expect("could not restore entry");
+
+    let restored = context.read_entry("foo/bar").expect("could not read entry");
+    let mut content = Vec::new();
+    context.read_blob(&restored.get_attributes()["file"], &mut content).expect("could not read blob");
+
+    assert_eq!(restored.get_attributes().get("username").map(|attribute| attribute.get_value()), Some("bar"));
+    assert_eq!(restored.get_created(), 1);
+    assert_eq!(content, b"content");
+    assert!(context.restore_entry("foo/bar", None).is_err());
+
+    context.restore_entry("foo/bar", Some("HEAD~2")).expect("could not restore entry");
+
+    let restored = context.read_entry("foo/bar").expect("could not read entry");
+
+    assert_eq!(restored.get_attributes().get("username").map(|attribute| attribute.get_value()), Some("foo"));
+    assert_eq!(restored.get_revisions("username")[0].get_attribute().get_value(), "bar");
+  }
+
+  #[test]
+  fn restore_missing_blob() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.mut_attributes().insert("file".to_string(), context.write_blob(&b"content"[..]).expect("could not write blob"));
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    std::fs::remove_dir_all(tmp.path().join(util::BLOB_DIRECTORY)).expect("could not remove blobs");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    context.delete_entry("foo/bar").expect("could not delete entry");
+    context.commit("Deleted entry.").expect("could not commit changes");
+
+    assert!(context.restore_entry("foo/bar", None).is_err());
+    assert!(!context.vault.get_index().contains_key("foo/bar"));
+  }
+
+  #[test]
+  fn restore_reencrypted_blob() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("username", "foo");
+    entry.mut_attributes().insert("file".to_string(), context.write_blob(&b"content"[..]).expect("could not write blob"));
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    let mut transaction = context.transaction();
+    transaction.reencrypt_entry("foo/bar").expect("could not reencrypt entry");
+    transaction.commit("Reencrypted entry.").expect("could not commit changes");
+
+    let blob = context.read_entry("foo/bar").expect("could not read entry").get_attributes()["file"].get_blob().to_string();
+    let other = context.write_blob(&b"content"[..]).expect("could not write blob");
+    std::fs::copy(tmp.path().join(util::BLOB_DIRECTORY).join(other.get_blob()), tmp.path().join(util::BLOB_DIRECTORY).join(&blob)).expect("could not replace blob");
+
+    for revision in &["HEAD~1", "HEAD"] {
+      context.restore_entry("foo/bar", Some(revision)).expect("could not restore entry");
+
+      let restored = context.read_entry("foo/bar").expect("could not read entry");
+      let mut content = Vec::new();
+      context.read_blob(&restored.get_attributes()["file"], &mut content).expect("could not read blob");
+
+      assert_eq!(content, b"content");
+    }
+  }
+
+  #[test]
+  fn diff_entry() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("username", "foo");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.commit("Created entry.").expect("could not commit changes");
+
+    entry.add_attribute("username", "bar");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    assert_eq!(context.diff_entry("foo/bar", None, None).expect("could not diff entry").len(), 1);
+    assert!(context.diff_entry("foo/bar", Some("HEAD"), Some("HEAD")).expect("could not diff entry").is_empty());
+    assert!(match &context.diff_entry("foo/bar", Some("HEAD~1"), Some("HEAD")).expect("could not diff entry")[..] {
+      [Difference::Added { name, .. }] => name == "username",
+      _ => false,
+    });
+    assert!(context.diff_entry("lorem", None, None).is_err());
+  }
+}
@@ -1,21 +1,39 @@
 //! Handle around a [Vault](struct.Vault.html) instance.
 
+use std::cell::{Cell, RefCell};
 use std::error::Error;
-use std::fs::{create_dir_all, read_dir, remove_dir, remove_file, File, OpenOptions};
-use std::io::Write;
+use std::fs::{self, create_dir_all, read_dir, remove_dir, remove_file, rename};
+use std::io::ErrorKind;
 use std::path::Path;
+use std::sync::Arc;
 
+use log::*;
 use protobuf::parse_from_bytes;
 
-use super::pack::Packing;
-use crate::gpg;
+use super::local;
+use super::migration::{Migration, MIGRATIONS, VAULT_VERSION};
+use super::pack::{self, Packing};
+use super::transaction::Transaction;
+use crate::backend::passphrase::Passphrase;
+use crate::backend::{self, Backend, Signature};
 use crate::pb::*;
-use crate::util::{self, git, VaultError};
+use crate::util::{self, git, Lock, LockMode, VaultError};
 
 /// Handle around a [Vault](struct.Vault.html) instance.
+///
+/// Access to the vault's directory is synchronized through advisory locks: a
+/// shared lock is held while its metadata is read, and an exclusive lock is
+/// held while it is modified. A handle refuses to write metadata that was
+/// modified by another process after it was read.
 pub struct VaultContext {
   pub path: String,
   pub vault: Vault,
+  backend: Arc<dyn Backend>,
+  pub(crate) local: RefCell<LocalConfig>,
+  lock: RefCell<Option<Lock>>,
+  exclusive: bool,
+  digest: Cell<Option<u64>>,
+  outdated: bool,
 }
 
 impl VaultContext {
@@ -31,6 +49,54 @@ impl VaultContext {
   ///  * `identities` - a slice if GPG identities, represented by their owner's
   ///                   email address.
   pub fn create<P>(path: P, identities: &[String]) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    Self::create_with_backend(path, BackendKind::GPG, identities)
+  }
+
+  /// Create a new vault using a specific encryption backend.
+  ///
+  /// Behaves like [VaultContext::create](struct.VaultContext.html#method.create),
+  /// but the format of the identities depends on the chosen
+  /// [backend](backend/index.html).
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`       - the filesystem path at which to create the vault.
+  ///  * `backend`    - the kind of backend encrypting the vault.
+  ///  * `identities` - a slice of recipients understood by the backend.
+  pub fn create_with_backend<P>(path: P, backend: BackendKind, identities: &[String]) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    if backend == BackendKind::PASSPHRASE {
+      return Err(VaultError::throw("vaults encrypted with a passphrase should be created with create_with_passphrase"));
+    }
+
+    let backend = backend::open(backend, &path)?;
+
+    Self::create_with(path, backend, identities)
+  }
+
+  /// Create a new vault encrypted with a passphrase.
+  ///
+  /// The key encrypting the vault is derived from the passphrase, and the
+  /// parameters used to derive it are written to the `_knox.header` file
+  /// along with the metadata. Such a vault has no identities.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`       - the filesystem path at which to create the vault.
+  ///  * `passphrase` - the passphrase protecting the vault.
+  pub fn create_with_passphrase<P>(path: P, passphrase: &str) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    Self::create_with(path, Arc::new(Passphrase::create(passphrase)?), &[])
+  }
+
+  fn create_with<P>(path: P, backend: Arc<dyn Backend>, identities: &[String]) -> Result<Self, Box<dyn Error>>
   where
     P: AsRef<Path>,
   {
@@ -55,8 +121,16 @@ impl VaultContext {
       path: format!("{}", path.display()),
       vault: Vault {
         identities: protobuf::RepeatedField::from(identities),
+        version: VAULT_VERSION,
+        backend: backend.kind(),
         ..Vault::default()
       },
+      backend,
+      local: RefCell::new(LocalConfig::default()),
+      lock: RefCell::new(None),
+      exclusive: false,
+      digest: Cell::new(None),
+      outdated: false,
     };
 
     Ok(vault)
@@ -66,13 +140,46 @@ impl VaultContext {
   ///
   /// Opens, decrypt the metadata of, and returns a handle that allows you to
   /// manipulate a [Vault](struct.Vault.html). This function will fail of the
-  /// given `path` is not a vault instance or if it cannot be decrypted with
-  /// an available GPG private key.
+  /// given `path` is not a vault instance, if it cannot be decrypted with
+  /// an available private key, or if it uses a storage format more recent
+  /// than the one supported by this version of libknox.
+  ///
+  /// If trusted signers are configured locally, the signature of the
+  /// metadata is verified according to the configured policy. Metadata older
+  /// than the latest version seen on this machine is handled according to
+  /// the configured rollback policy.
+  ///
+  /// If another process is modifying the vault, this waits for it to finish
+  /// before reading the metadata.
   ///
   /// # Arguments
   ///
   ///  * `path` - filesystem path where the vault is located.
   pub fn open<P>(path: P) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    Self::open_with_lock(path, LockMode::Shared, true)
+  }
+
+  /// Return a handle to a [Vault](struct.Vault.html) locked for writing.
+  ///
+  /// Behaves like [VaultContext::open](struct.VaultContext.html#method.open),
+  /// but locks the vault exclusively before reading its metadata, until the
+  /// handle is dropped. Use this when the handle is going to be used to modify
+  /// the vault, so that no other process can modify it in between.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - filesystem path where the vault is located.
+  pub fn open_exclusive<P>(path: P) -> Result<Self, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    Self::open_with_lock(path, LockMode::Exclusive, true)
+  }
+
+  pub(crate) fn open_with_lock<P>(path: P, mode: LockMode, check_rollback: bool) -> Result<Self, Box<dyn Error>>
   where
     P: AsRef<Path>,
   {
@@ -81,13 +188,56 @@ impl VaultContext {
       return Err(VaultError::throw(&format!("vault does not exist at {}, please initialize it", path.as_ref().display(),)));
     }
 
-    let pack = gpg::decrypt(&mut File::open(&metadata)?)?;
-    let vault = parse_from_bytes::<Vault>(&pack)?;
-
-    Ok(Self {
+    // Opening the backend can prompt for a passphrase and derive a key, which
+    // must not keep other processes waiting. The metadata is always replaced
+    // atomically, so its backend can be detected before the vault is locked.
+    let backend = backend::open(backend::detect(&fs::read(&metadata)?), &path)?;
+    let lock = Lock::acquire(&path, mode)?;
+    let ciphertext = fs::read(&metadata)?;
+    let local = local::read_local_config(&path)?;
+    let (vault, signature) = decrypt_metadata(&*backend, &ciphertext)?;
+    let exclusive = mode == LockMode::Exclusive;
+    let outdated = vault.get_version() < VAULT_VERSION;
+
+    let context = Self {
       path: format!("{}", path.as_ref().display()),
       vault,
-    })
+      backend,
+      local: RefCell::new(local),
+      lock: RefCell::new(if exclusive { Some(lock) } else { None }),
+      exclusive,
+      digest: Cell::new(Some(util::digest_bytes(&ciphertext))),
+      outdated,
+    };
+
+    context.verify_signature("the vault metadata", signature.as_ref())?;
+
+    if check_rollback {
+      context.check_rollback()?;
+    }
+
+    Ok(context)
+  }
+
+  /// Read the metadata of the vault again, after it was replaced on disk.
+  ///
+  /// The new metadata goes through the same verifications as when the vault
+  /// is opened. This must be called while holding the exclusive lock.
+  pub(crate) fn reload(&mut self) -> Result<(), Box<dyn Error>> {
+    let ciphertext = fs::read(util::normalize_path(self, &util::METADATA_FILE))?;
+    let (vault, signature) = decrypt_metadata(&*self.backend, &ciphertext)?;
+
+    self.outdated = vault.get_version() < VAULT_VERSION;
+    self.vault = vault;
+    self.digest.set(Some(util::digest_bytes(&ciphertext)));
+
+    self.verify_signature("the vault metadata", signature.as_ref())?;
+    self.check_rollback()
+  }
+
+  /// Get the [Backend](backend/trait.Backend.html) encrypting the vault.
+  pub fn backend(&self) -> Arc<dyn Backend> {
+    Arc::clone(&self.backend)
   }
 
   /// Write the vault metadata.
@@ -96,17 +246,97 @@ impl VaultContext {
   /// containing the encrypted mapping between virtual (user) secret paths and
   /// filesystem paths.
   ///
-  /// This requires the GPG public keys of all identities used in the vault.
-  pub fn write(&self) -> Result<(), Box<dyn Error>> {
-    create_dir_all(util::normalize_path(self, &""))?;
+  /// The metadata is compressed and encrypted in memory and atomically
+  /// swapped with the previous version, so an interrupted write never leaves
+  /// a truncated file. Every write bumps the generation of the metadata,
+  /// which protects it against being rolled back.
+  ///
+  /// Vaults using an outdated storage format cannot be written before being
+  /// migrated, see
+  /// [VaultContext::migrate](struct.VaultContext.html#method.migrate).
+  ///
+  /// This requires the public keys of all identities used in the vault.
+  pub fn write(&mut self) -> Result<(), Box<dyn Error>> {
+    let result = self.write_metadata();
+    self.unlock();
+    result
+  }
+
+  pub(crate) fn write_metadata(&mut self) -> Result<(), Box<dyn Error>> {
+    self.check_outdated()?;
+
+    let generation = self.next_generation();
+    let mut vault = self.vault.clone();
+    vault.set_generation(generation);
+
+    let ciphertext = self.backend().encrypt(vault.get_identities(), &pack::compress(&vault.pack()?)?)?;
+
+    self.lock_exclusive()?;
+
+    if let Some(header) = self.backend.header() {
+      if !Path::new(&util::normalize_path(self, &util::HEADER_FILE)).exists() {
+        util::write_atomic(self, &util::HEADER_FILE, &header)?;
+      }
+    }
+
+    util::write_atomic(self, &util::METADATA_FILE, &ciphertext)?;
+
+    self.vault = vault;
+    self.digest.set(Some(util::digest_bytes(&ciphertext)));
+
+    self.record_generation(generation)
+  }
+
+  /// Lock the vault for writing.
+  ///
+  /// Waits for other processes to release their locks on the vault. This
+  /// fails if the metadata was modified by another process since this handle
+  /// read it, as writing it would discard those modifications.
+  pub(crate) fn lock_exclusive(&self) -> Result<(), Box<dyn Error>> {
+    let mut guard = self.lock.borrow_mut();
+
+    if guard.is_some() {
+      return Ok(());
+    }
+
+    create_dir_all(&self.path)?;
+
+    let lock = Lock::acquire(&self.path, LockMode::Exclusive)?;
 
-    let mut file = OpenOptions::new().create(true).write(true).open(util::normalize_path(self, &util::METADATA_FILE))?;
+    if util::digest(util::normalize_path(self, &util::METADATA_FILE))? != self.digest.get() {
+      return Err(VaultError::throw("the vault was modified by another process since it was opened, please retry"));
+    }
 
-    file.write_all(&gpg::encrypt(&self.vault, &self.vault.pack()?)?)?;
+    *guard = Some(lock);
 
     Ok(())
   }
 
+  /// Refuse to write a vault whose metadata records an outdated storage
+  /// format, as the version would not describe what is on disk anymore.
+  fn check_outdated(&self) -> Result<(), Box<dyn Error>> {
+    if self.outdated {
+      return Err(VaultError::throw(&format!(
+        "the vault uses an outdated storage format (version {}), run 'knox migrate' before modifying it",
+        self.vault.get_version()
+      )));
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn is_locked(&self) -> bool {
+    self.lock.borrow().is_some()
+  }
+
+  /// Release the lock taken for writing, unless the vault was opened
+  /// exclusively.
+  pub(crate) fn unlock(&self) {
+    if !self.exclusive {
+      self.lock.borrow_mut().take();
+    }
+  }
+
   /// Add an [Entry](struct.Entry.html) to the index.
   ///
   /// Adds an [Entry](struct.Entry.html) to the index of a vault, allowing to
@@ -127,8 +357,9 @@ impl VaultContext {
   /// Remove an [Entry](struct.Entry.html) from the index.
   ///
   /// Removes an [Entry](struct.Entry.html) to the index of a vault, allowing
-  /// to retrieve a filesystem path from a virtual path. This does not manage
-  /// the secret itself.
+  /// to retrieve a filesystem path from a virtual path. The tags and the
+  /// expiration date indexed for the entry are removed as well. This does not
+  /// manage the secret itself.
   ///
   /// To persist the change, refer to
   /// [VaultContext::write](struct.VaultContext.html#method.write).
@@ -138,6 +369,8 @@ impl VaultContext {
   ///  * `path` - virtual path to the entry
   pub fn remove_index(&mut self, path: &str) {
     self.vault.mut_index().remove(path);
+    self.vault.index_tags(path, &[]);
+    self.vault.index_expiry(path, None);
   }
 
   /// Read an [Entry](struct.Entry.html).
@@ -149,14 +382,25 @@ impl VaultContext {
   ///
   ///  * `path` - the virtual path to the entry.
   pub fn read_entry(&self, path: &str) -> Result<Entry, Box<dyn Error>> {
+    Ok(self.read_signed_entry(path)?.0)
+  }
+
+  /// Read an [Entry](struct.Entry.html) along with its signature.
+  ///
+  /// Behaves like [VaultContext::read_entry](struct.VaultContext.html#method.read_entry),
+  /// but also returns the valid signature the entry carries, if any.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  pub fn read_signed_entry(&self, path: &str) -> Result<(Entry, Option<Signature>), Box<dyn Error>> {
     if !self.vault.get_index().contains_key(path) {
       return Err(VaultError::throw("no entry was found at this path"));
     }
 
-    let path = util::hash_path(self.vault.get_index().get(path));
-    let entry = Entry::read(self, path)?;
+    let pack = util::hash_path(self.vault.get_index().get(path));
 
-    Ok(entry)
+    Entry::read_signed(self, pack, path)
   }
 
   /// Persist an [Entry](struct.Entry.html).
@@ -165,25 +409,41 @@ impl VaultContext {
   /// location as described in the vault's index. The entry must exist in the
   /// index beforehand.
   ///
-  /// This requires the GPG public keys of all identities used in the vault.
+  /// The modification time and author of the entry are updated, as well as
+  /// its creation time if it was never written before.
+  ///
+  /// The pack is fully persisted before the metadata referencing it is
+  /// written, so the index never points to a partially-written pack. To write
+  /// several entries at once, refer to
+  /// [VaultContext::transaction](struct.VaultContext.html#method.transaction).
+  ///
+  /// This requires the public keys of all identities used in the vault.
   ///
   /// # Arguments
   ///
   ///  * `path`  - the virtual path to the entry.
   ///  * `entry` - the `Entry` to be written.
   pub fn write_entry(&mut self, path: &str, entry: &Entry) -> Result<(), Box<dyn Error>> {
-    let hash = util::hash_path(self.vault.get_index().get(path));
-
-    util::create_parents(self, &hash)?;
-
-    let mut file = OpenOptions::new().create(true).truncate(true).write(true).open(&util::normalize_path(self, &hash))?;
-
-    file.write_all(&gpg::encrypt(&self.vault, &entry.pack()?)?)?;
-
-    self.add_index(path, &hash);
-    self.write()?;
+    let mut transaction = self.transaction();
+    transaction.write_entry(path, entry)?;
+    transaction.flush()
+  }
 
-    Ok(())
+  /// Persist a modified [Entry](struct.Entry.html).
+  ///
+  /// Behaves like [VaultContext::write_entry](struct.VaultContext.html#method.write_entry),
+  /// but compares the entry to `previous`, the version the caller read before
+  /// modifying it, instead of decrypting the current version again.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`     - the virtual path to the entry.
+  ///  * `previous` - the `Entry` as it was read from the vault.
+  ///  * `entry`    - the `Entry` to be written.
+  pub fn update_entry(&mut self, path: &str, previous: &Entry, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    let mut transaction = self.transaction();
+    transaction.update_entry(path, previous, entry)?;
+    transaction.flush()
   }
 
   /// Delete an [Entry](struct.Entry.html).
@@ -192,29 +452,97 @@ impl VaultContext {
   /// location and from the index.
   ///
   /// This requires the GPG public keys of all identities used in the vault
-  /// because the index needs to be updated.
+  /// because the index needs to be updated. The index is written before the
+  /// pack is removed, so an interruption can only leave an unreferenced pack
+  /// behind.
   ///
   /// # Arguments
   ///
   ///  * `path` - the virtual path to the entry.
   pub fn delete_entry(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
-    if let Some(salt) = self.vault.get_index().get(path) {
-      let hash = util::hash_path(Some(salt));
+    let mut transaction = self.transaction();
+    transaction.delete_entry(path)?;
+    transaction.flush()
+  }
+
+  /// Rename an [Entry](struct.Entry.html).
+  ///
+  /// Moves an entry and its tags to another virtual path in the index, and
+  /// writes the metadata. This fails if no entry exists at `source` or if an
+  /// entry already exists at `destination`.
+  ///
+  /// # Arguments
+  ///
+  ///  * `source`      - the current virtual path to the entry.
+  ///  * `destination` - the new virtual path to the entry.
+  pub fn rename_entry(&mut self, source: &str, destination: &str) -> Result<(), Box<dyn Error>> {
+    let mut transaction = self.transaction();
+    transaction.rename_entry(source, destination)?;
+    transaction.flush()
+  }
+
+  /// Start a batch of modifications on the vault.
+  ///
+  /// Returns a [Transaction](struct.Transaction.html) collecting entry
+  /// writes, deletions and index changes, and only writing the metadata once
+  /// when it is flushed or committed.
+  pub fn transaction(&mut self) -> Transaction<'_> {
+    Transaction::new(self)
+  }
 
-      self.write()?;
-      remove_file(util::normalize_path(self, &hash))?;
+  pub(crate) fn write_pack(&self, hash: &str, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    self.check_outdated()?;
 
-      for directory in Path::new(&hash).ancestors() {
-        let _ = remove_dir(util::normalize_path(self, &format!("{}", directory.display())));
-      }
+    let mut entry = entry.clone();
+    entry.set_location(hash.to_string());
 
-      self.remove_index(&path);
-      self.write()?;
+    let ciphertext = self.backend().encrypt(self.vault.get_identities(), &pack::compress(&entry.pack()?)?)?;
 
-      return Ok(());
+    self.lock_exclusive()?;
+
+    util::create_parents(self, &hash)?;
+    util::write_atomic(self, &hash, &ciphertext)?;
+
+    Ok(())
+  }
+
+  pub(crate) fn remove_pack(&self, hash: &str) -> Result<(), Box<dyn Error>> {
+    self.lock_exclusive()?;
+
+    remove_file(util::normalize_path(self, &hash))?;
+
+    self.remove_empty_parents(hash);
+
+    Ok(())
+  }
+
+  pub(crate) fn quarantine_pack(&self, hash: &str) -> Result<(), Box<dyn Error>> {
+    self.lock_exclusive()?;
+
+    let name = Path::new(hash).file_name().ok_or_else(|| VaultError::throw(&format!("invalid pack path {}", hash)))?;
+    let destination = Path::new(util::QUARANTINE_DIRECTORY).join(name);
+
+    create_dir_all(util::normalize_path(self, &util::QUARANTINE_DIRECTORY))?;
+    rename(util::normalize_path(self, &hash), util::normalize_path(self, &destination))?;
+
+    self.remove_empty_parents(hash);
+
+    Ok(())
+  }
+
+  pub(crate) fn remove_temporary_file(&self, file: &str) -> Result<(), Box<dyn Error>> {
+    self.lock_exclusive()?;
+
+    match remove_file(util::normalize_path(self, &file)) {
+      Err(ref err) if err.kind() != ErrorKind::NotFound => Err(VaultError::throw(&format!("could not remove {}: {}", file, err))),
+      _ => Ok(()),
     }
+  }
 
-    Err(VaultError::throw("requested entry does not exist in the vault"))
+  fn remove_empty_parents(&self, hash: &str) {
+    for directory in Path::new(hash).ancestors() {
+      let _ = remove_dir(util::normalize_path(self, &format!("{}", directory.display())));
+    }
   }
 
   /// Check if a file exists under the vault's directory.
@@ -257,9 +585,56 @@ impl VaultContext {
     self.vault.set_identities(protobuf::RepeatedField::from(identities))
   }
 
+  /// List the migrations required to bring the vault to the current storage
+  /// format.
+  pub fn pending_migrations(&self) -> Vec<&'static Migration> {
+    MIGRATIONS.iter().filter(|migration| migration.version > self.vault.get_version()).collect()
+  }
+
+  /// Apply a migration to the vault.
+  ///
+  /// Upgrades the vault's storage format to the version of the provided
+  /// [Migration](struct.Migration.html), and records it as a git commit.
+  /// Migrations should be applied in the order returned by
+  /// [VaultContext::pending_migrations](struct.VaultContext.html#method.pending_migrations).
+  ///
+  /// # Arguments
+  ///
+  ///  * `migration` - the migration to be applied.
+  pub fn migrate(&mut self, migration: &Migration) -> Result<(), Box<dyn Error>> {
+    if migration.version != self.vault.get_version() + 1 {
+      return Err(VaultError::throw(&format!(
+        "cannot migrate a vault in version {} to version {}",
+        self.vault.get_version(),
+        migration.version
+      )));
+    }
+
+    let outdated = self.outdated;
+    self.outdated = false;
+
+    let result = self.apply_migration(migration);
+
+    self.outdated = match result {
+      Ok(_) => self.vault.get_version() < VAULT_VERSION,
+      Err(_) => outdated,
+    };
+
+    result
+  }
+
+  fn apply_migration(&mut self, migration: &Migration) -> Result<(), Box<dyn Error>> {
+    let mut transaction = self.transaction();
+
+    (migration.apply)(&mut transaction)?;
+
+    transaction.vault_mut().set_version(migration.version);
+    transaction.commit(&format!("Migrated vault to version {}.", migration.version))
+  }
+
   /// Initialize a local git repository
   pub fn git_init(&self) -> Result<(), Box<dyn Error>> {
-    git::init(&self)
+    git::init(self)
   }
 
   /// Commit all unstaged files to git repository
@@ -271,24 +646,65 @@ impl VaultContext {
   ///
   ///  * `message` - the commit message to be used for the commit
   pub fn commit(&self, message: &str) -> Result<(), Box<dyn Error>> {
-    git::commit(&self, message)
+    git::commit(self, message)
   }
 
   /// Set the URL of the remote git repository
   ///
   /// No particular check is performed on the validity of the provided URL.
+  /// The name of the remote is read from the local configuration of the
+  /// vault, and defaults to `origin`.
   ///
   /// # Arguments
   ///
   ///  * `origin` - the URL for the remote git repository
   pub fn set_git_origin(&self, origin: &str) -> Result<(), Box<dyn Error>> {
-    git::set_origin(&self, origin)
+    git::set_origin(self, origin)
   }
 
   /// Push all commited data to the remote git repository
+  ///
+  /// The remote, the branch and the way to authenticate are read from the
+  /// git section of the [local configuration](struct.LocalConfig.html) of the
+  /// vault. By default, the current branch is pushed to `origin`, with keys
+  /// provided by the SSH agent.
   pub fn push(&self) -> Result<(), Box<dyn Error>> {
-    git::push(&self)
+    git::push(self, false)
+  }
+
+  /// Push all commited data to the remote git repository, replacing its
+  /// history
+  ///
+  /// This is required after the local history was rewritten with
+  /// [VaultContext::purge_history](struct.VaultContext.html#method.purge_history).
+  /// The push is refused if the remote branch was modified since it was last
+  /// pulled, since those modifications would be lost.
+  pub fn force_push(&self) -> Result<(), Box<dyn Error>> {
+    git::push(self, true)
+  }
+}
+
+fn decrypt_metadata(backend: &dyn Backend, ciphertext: &[u8]) -> Result<(Vault, Option<Signature>), Box<dyn Error>> {
+  let (pack, signature) = backend.decrypt_and_verify(ciphertext)?;
+  let vault = parse_from_bytes::<Vault>(&pack::decompress(&pack)?)?;
+
+  if vault.get_version() > VAULT_VERSION {
+    return Err(VaultError::throw(&format!(
+      "the vault uses storage format version {}, but this version of knox only supports up to version {}",
+      vault.get_version(),
+      VAULT_VERSION
+    )));
+  }
+
+  if vault.get_version() < VAULT_VERSION {
+    warn!(
+      "the vault uses an outdated storage format (version {}), it must be migrated to version {} before being modified",
+      vault.get_version(),
+      VAULT_VERSION
+    );
   }
+
+  Ok((vault, signature))
 }
 
 #[cfg(test)]
@@ -314,7 +730,7 @@ mod tests {
   #[test]
   fn read_and_write() {
     let tmp = spec::setup();
-    let context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
 
     context.write().expect("could not write pack");
     let retrieved = VaultContext::open(tmp.path()).expect("could not read pack");
@@ -322,6 +738,27 @@ mod tests {
     assert_eq!(context.vault, retrieved.vault);
   }
 
+  #[test]
+  fn concurrent_write() {
+    let tmp = spec::setup();
+    crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut first = VaultContext::open(tmp.path()).expect("could not open vault");
+    let mut second = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    first.add_index("foo/bar", "lorem/ipsum");
+    first.write().expect("could not write metadata");
+
+    second.add_index("dolor/sit", "amet/consectetur");
+
+    assert!(second.write().is_err());
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert!(retrieved.vault.get_index().contains_key("foo/bar"));
+    assert!(!retrieved.vault.get_index().contains_key("dolor/sit"));
+  }
+
   #[test]
   fn add_index() {
     let tmp = spec::setup();
@@ -342,6 +779,8 @@ mod tests {
     let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
 
     context.vault.mut_index().insert("foo/bar".to_string(), "lorem/ipsum".to_string());
+    context.vault.index_tags("foo/bar", &["lorem".to_string()]);
+    context.vault.index_expiry("foo/bar", Some(1));
 
     context.write().expect("could not write metadata");
     context.remove_index("foo/bar");
@@ -350,6 +789,8 @@ mod tests {
     let retrieved = VaultContext::open(tmp.path()).expect("could not retrieve metadata");
 
     assert_eq!(None, retrieved.vault.get_index().get("foo/bar"));
+    assert!(retrieved.vault.get_tags().is_empty());
+    assert!(retrieved.vault.get_expirations().is_empty());
   }
 
   #[test]
@@ -385,6 +826,6 @@ mod tests {
     let retrieved = VaultContext::open(tmp.path()).expect("could not read vault").read_entry("foo/bar");
 
     assert_eq!(retrieved.is_ok(), true);
-    assert_eq!(retrieved.unwrap(), entry);
+    assert_eq!(retrieved.unwrap().get_attributes(), entry.get_attributes());
   }
 }
@@ -0,0 +1,164 @@
+//! Attribute-level differences between two versions of an
+//! [Entry](struct.Entry.html).
+//!
+//! Packs are encrypted, so git can only tell that an entry changed. Vaults
+//! initialized with git register `knox git textconv` as the diff driver of
+//! their packs, which decrypts them into a textual representation.
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::path::Path;
+
+use protobuf::parse_from_bytes;
+
+use super::context::VaultContext;
+use super::local;
+use super::pack;
+use crate::backend;
+use crate::pb::*;
+
+/// A difference between two versions of an [Entry](struct.Entry.html),
+/// listed by [Entry::diff](struct.Entry.html#method.diff).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+  /// An attribute was added.
+  Added { name: String, attribute: Attribute },
+  /// An attribute was removed.
+  Removed { name: String, attribute: Attribute },
+  /// The value or the settings of an attribute changed.
+  Changed { name: String, before: Attribute, after: Attribute },
+  /// The TOTP configuration was added, removed or changed.
+  Totp { before: Option<TotpConfig>, after: Option<TotpConfig> },
+}
+
+impl Entry {
+  /// List the attributes and TOTP configuration that differ from another
+  /// version of the entry, ordered by attribute name.
+  ///
+  /// # Arguments
+  ///
+  ///  * `other` - the newer version of the entry.
+  pub fn diff(&self, other: &Entry) -> Vec<Difference> {
+    let names: BTreeSet<&String> = self.get_attributes().keys().chain(other.get_attributes().keys()).collect();
+
+    let mut differences: Vec<Difference> = names
+      .into_iter()
+      .filter_map(|name| match (self.get_attributes().get(name), other.get_attributes().get(name)) {
+        (None, Some(attribute)) => Some(Difference::Added {
+          name: name.clone(),
+          attribute: attribute.clone(),
+        }),
+        (Some(attribute), None) => Some(Difference::Removed {
+          name: name.clone(),
+          attribute: attribute.clone(),
+        }),
+        (Some(before), Some(after)) if before != after => Some(Difference::Changed {
+          name: name.clone(),
+          before: before.clone(),
+          after: after.clone(),
+        }),
+        _ => None,
+      })
+      .collect();
+
+    let (before, after) = (totp_config(self), totp_config(other));
+
+    if before != after {
+      differences.push(Difference::Totp { before, after });
+    }
+
+    differences
+  }
+}
+
+impl VaultContext {
+  /// Decrypt a pack outside of an opened vault.
+  ///
+  /// This is used to show the content of packs read from the git repository,
+  /// for instance by `git diff`. The signature of the entry is verified
+  /// according to the configured policy.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`       - filesystem path where the vault is located.
+  ///  * `ciphertext` - the content of the pack.
+  pub fn decrypt_pack<P>(path: P, ciphertext: &[u8]) -> Result<Entry, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let backend = backend::open(backend::detect(ciphertext), &path)?;
+    let (pack, signature) = backend.decrypt_and_verify(ciphertext)?;
+
+    local::verify_signature(&local::read_local_config(&path)?, "the entry", signature.as_ref())?;
+
+    Ok(parse_from_bytes::<Entry>(&pack::decompress(&pack)?)?)
+  }
+}
+
+fn totp_config(entry: &Entry) -> Option<TotpConfig> {
+  if entry.has_totp() {
+    Some(entry.get_totp().clone())
+  } else {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use knox_testing::spec;
+
+  use super::Difference;
+  use crate::*;
+
+  #[test]
+  fn diff() {
+    let mut before = Entry::default();
+    before.add_attribute("username", "foo");
+    before.add_attribute("url", "https://example.com");
+    before.add_confidential_attribute("password", "first");
+
+    let mut after = before.clone();
+    after.add_confidential_attribute("password", "second");
+    after.add_attribute("email", "foo@example.com");
+    after.mut_attributes().remove("url");
+    after.mut_totp().set_secret(b"secret".to_vec());
+
+    let differences = before.diff(&after);
+
+    assert_eq!(differences.len(), 4);
+    assert!(match &differences[0] {
+      Difference::Added { name, .. } => name == "email",
+      _ => false,
+    });
+    assert!(match &differences[1] {
+      Difference::Changed { name, before, after } => name == "password" && before.get_value() == "first" && after.get_value() == "second",
+      _ => false,
+    });
+    assert!(match &differences[2] {
+      Difference::Removed { name, .. } => name == "url",
+      _ => false,
+    });
+    assert!(match &differences[3] {
+      Difference::Totp { before, after } => before.is_none() && after.is_some(),
+      _ => false,
+    });
+    assert!(after.diff(&after).is_empty());
+  }
+
+  #[test]
+  fn decrypt_pack() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("username", "foo");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let pack = fs::read(tmp.path().join(&context.vault.get_index()["foo/bar"])).expect("could not read pack");
+    let decrypted = VaultContext::decrypt_pack(tmp.path(), &pack).expect("could not decrypt pack");
+
+    assert_eq!(decrypted.get_attributes(), entry.get_attributes());
+  }
+}
@@ -2,13 +2,15 @@
 //! [Attributes](struct.Attribute.html).
 
 use std::error::Error;
-use std::fs::File;
+use std::fs;
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use protobuf::parse_from_bytes;
 
 use super::context::VaultContext;
-use crate::gpg;
+use super::pack;
+use crate::backend::Signature;
 use crate::pb::*;
 use crate::util;
 
@@ -25,10 +27,52 @@ impl Entry {
   where
     P: AsRef<Path>,
   {
-    let pack = gpg::decrypt(&mut File::open(util::normalize_path(handle, &path))?)?;
+    let name = path.as_ref().display().to_string();
+
+    Ok(Self::read_signed(handle, path, &name)?.0)
+  }
+
+  pub(crate) fn read_signed<P>(handle: &VaultContext, path: P, name: &str) -> Result<(Entry, Option<Signature>), Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let (pack, signature) = handle.backend().decrypt_and_verify(&fs::read(util::normalize_path(handle, &path))?)?;
+    let subject = format!("entry {}", name);
+
+    handle.verify_signature(&subject, signature.as_ref())?;
+
+    let pack = pack::decompress(&pack)?;
     let message = parse_from_bytes::<Entry>(&pack)?;
 
-    Ok(message)
+    handle.verify_location(&subject, &message, &path.as_ref().to_string_lossy())?;
+
+    Ok((message, signature))
+  }
+
+  /// Record a modification of the entry.
+  ///
+  /// Sets the modification time to now, and the creation time as well if the
+  /// entry was never written before. Attributes changed since the `previous`
+  /// version of the entry are added to their history.
+  ///
+  /// # Arguments
+  ///
+  ///  * `author`   - the identity modifying the entry, if known.
+  ///  * `previous` - the version of the entry being replaced, if any.
+  pub(crate) fn stamp(&mut self, author: Option<String>, previous: Option<&Entry>) {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs()).unwrap_or_default();
+    let author = author.unwrap_or_default();
+
+    if let Some(previous) = previous {
+      self.record_history(previous, &author, now);
+    }
+
+    if self.created == 0 {
+      self.created = now;
+    }
+
+    self.modified = now;
+    self.author = author;
   }
 
   /// Add a standard string attribute to an Entry.
@@ -92,6 +136,52 @@ mod tests {
 
     let retrieved = context.read_entry("pack.bin").expect("could not read pack");
 
-    assert_eq!(retrieved, entry);
+    assert_eq!(retrieved.get_attributes(), entry.get_attributes());
+  }
+
+  #[test]
+  fn read_uncompressed() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("lorem", &"ipsum".repeat(100));
+
+    context.write_entry("foo/bar", &entry).expect("could not write pack");
+
+    let hash = util::hash_path(context.vault.get_index().get("foo/bar"));
+    let ciphertext = context
+      .backend()
+      .encrypt(context.vault.get_identities(), &entry.pack().expect("could not pack entry"))
+      .expect("could not encrypt entry");
+
+    std::fs::write(tmp.path().join(&hash), ciphertext).expect("could not write pack");
+
+    let retrieved = context.read_entry("foo/bar").expect("could not read pack");
+
+    assert_eq!(retrieved.get_attributes(), entry.get_attributes());
+  }
+
+  #[test]
+  fn stamp() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+
+    let mut entry = context.read_entry("foo/bar").expect("could not read entry");
+
+    assert!(entry.get_created() > 0);
+    assert_eq!(entry.get_created(), entry.get_modified());
+    assert!(entry.get_author().contains(spec::GPG_IDENTITY));
+
+    entry.set_created(1);
+    entry.set_modified(1);
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let entry = context.read_entry("foo/bar").expect("could not read entry");
+
+    assert_eq!(entry.get_created(), 1);
+    assert!(entry.get_modified() > 1);
   }
 }
@@ -0,0 +1,158 @@
+//! Track when [Entries](struct.Entry.html) and their
+//! [Attributes](struct.Attribute.html) expire.
+//!
+//! Expiration dates are stored inside each encrypted entry, and the earliest
+//! one is mirrored in the metadata of the [Vault](struct.Vault.html) so that
+//! expiring entries can be found without being decrypted.
+
+use std::error::Error;
+
+use crate::pb::*;
+use crate::util::VaultError;
+
+impl Entry {
+  /// List the expiration dates of an `Entry` and its attributes, soonest
+  /// first.
+  ///
+  /// Each expiration date comes with the name of the attribute it applies
+  /// to, or `None` if it applies to the whole entry.
+  pub fn get_expirations(&self) -> Vec<(Option<&str>, u64)> {
+    let mut expirations = self
+      .get_attributes()
+      .iter()
+      .filter(|(_, attribute)| attribute.expires > 0)
+      .map(|(key, attribute)| (Some(key.as_str()), attribute.expires))
+      .collect::<Vec<(Option<&str>, u64)>>();
+
+    if self.expires > 0 {
+      expirations.push((None, self.expires));
+    }
+
+    expirations.sort_by_key(|(key, expires)| (*expires, *key));
+    expirations
+  }
+
+  /// Get the earliest expiration date of an `Entry` or any of its attributes.
+  pub fn earliest_expiry(&self) -> Option<u64> {
+    self.get_expirations().first().map(|(_, expires)| *expires)
+  }
+
+  /// Set or clear the expiration date of an attribute.
+  ///
+  /// # Arguments
+  ///
+  ///  * `key`     - the name of the attribute.
+  ///  * `expires` - the UNIX timestamp at which the attribute expires, or
+  ///    `None` to remove the expiration date.
+  pub fn set_attribute_expiry(&mut self, key: &str, expires: Option<u64>) -> Result<(), Box<dyn Error>> {
+    match self.mut_attributes().get_mut(key) {
+      Some(attribute) => {
+        attribute.expires = expires.unwrap_or_default();
+
+        Ok(())
+      }
+
+      None => Err(VaultError::throw(&format!("attribute {} does not exist in the entry", key))),
+    }
+  }
+}
+
+impl Vault {
+  /// Retrieve the earliest expiration date of an entry from the metadata.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  pub fn get_entry_expiry(&self, path: &str) -> Option<u64> {
+    self.get_expirations().get(path).cloned()
+  }
+
+  /// List the entries expiring before a point in time, soonest first.
+  ///
+  /// # Arguments
+  ///
+  ///  * `timestamp` - the UNIX timestamp before which entries should expire.
+  pub fn expiring_before(&self, timestamp: u64) -> Vec<(&str, u64)> {
+    let mut entries = self
+      .get_expirations()
+      .iter()
+      .filter(|(_, expires)| **expires <= timestamp)
+      .map(|(path, expires)| (path.as_str(), *expires))
+      .collect::<Vec<(&str, u64)>>();
+
+    entries.sort_by_key(|(path, expires)| (*expires, *path));
+    entries
+  }
+
+  pub(crate) fn index_expiry(&mut self, path: &str, expires: Option<u64>) {
+    match expires {
+      Some(expires) => {
+        self.mut_expirations().insert(path.to_string(), expires);
+      }
+
+      None => {
+        self.mut_expirations().remove(path);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn expirations() {
+    let mut entry = Entry::default();
+
+    entry.add_attribute("username", "bob");
+    entry.add_confidential_attribute("password", "secret");
+
+    assert_eq!(entry.earliest_expiry(), None);
+    assert!(entry.set_attribute_expiry("token", Some(10)).is_err());
+
+    entry.set_expires(300);
+    entry.set_attribute_expiry("password", Some(200)).expect("could not set expiry");
+
+    assert_eq!(entry.get_expirations(), vec![(Some("password"), 200), (None, 300)]);
+    assert_eq!(entry.earliest_expiry(), Some(200));
+
+    entry.set_attribute_expiry("password", None).expect("could not set expiry");
+
+    assert_eq!(entry.earliest_expiry(), Some(300));
+  }
+
+  #[test]
+  fn index() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("token", "abcdef");
+    entry.set_attribute_expiry("token", Some(100)).expect("could not set expiry");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    entry.set_attribute_expiry("token", Some(500)).expect("could not set expiry");
+
+    context.write_entry("lorem/ipsum", &entry).expect("could not write entry");
+    context.write_entry("dolor/sit", &Entry::default()).expect("could not write entry");
+    context.rename_entry("lorem/ipsum", "lorem/amet").expect("could not rename entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.get_entry_expiry("foo/bar"), Some(100));
+    assert_eq!(retrieved.vault.get_entry_expiry("lorem/ipsum"), None);
+    assert_eq!(retrieved.vault.get_entry_expiry("dolor/sit"), None);
+    assert_eq!(retrieved.vault.expiring_before(100), vec![("foo/bar", 100)]);
+    assert_eq!(retrieved.vault.expiring_before(1000), vec![("foo/bar", 100), ("lorem/amet", 500)]);
+
+    context.delete_entry("foo/bar").expect("could not delete entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.expiring_before(1000), vec![("lorem/amet", 500)]);
+  }
+}
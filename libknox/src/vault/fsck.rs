@@ -0,0 +1,299 @@
+//! Check and repair the consistency of a vault.
+
+use std::collections::{BTreeSet, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs::read_dir;
+use std::path::Path;
+
+use super::blob;
+use super::context::VaultContext;
+use crate::pb::*;
+use crate::util;
+
+/// An inconsistency found in a vault by
+/// [VaultContext::check](struct.VaultContext.html#method.check).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Issue {
+  /// An index entry references a pack that does not exist.
+  MissingPack { path: String, pack: String },
+  /// A pack is not referenced by any index entry.
+  OrphanPack { pack: String },
+  /// A pack cannot be decrypted or does not contain a valid entry.
+  CorruptPack { path: String, pack: String, error: String },
+  /// An entry has the same virtual path as a directory holding other entries.
+  Collision { path: String },
+  /// A temporary file was left behind by an interrupted write.
+  StaleTemporaryFile { file: String },
+  /// An attribute references a blob that does not exist.
+  MissingBlob { path: String, blob: String },
+  /// A blob is not referenced by any entry.
+  OrphanBlob { blob: String },
+}
+
+impl Issue {
+  /// Whether [VaultContext::repair](struct.VaultContext.html#method.repair)
+  /// can fix this issue without losing data.
+  pub fn is_repairable(&self) -> bool {
+    match self {
+      Issue::MissingPack { .. } | Issue::OrphanPack { .. } | Issue::StaleTemporaryFile { .. } | Issue::OrphanBlob { .. } => true,
+      Issue::CorruptPack { .. } | Issue::Collision { .. } | Issue::MissingBlob { .. } => false,
+    }
+  }
+}
+
+impl fmt::Display for Issue {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Issue::MissingPack { path, pack } => write!(f, "entry {} references missing pack {}", path, pack),
+      Issue::OrphanPack { pack } => write!(f, "pack {} is not referenced by any entry", pack),
+      Issue::CorruptPack { path, pack, error } => write!(f, "pack {} of entry {} cannot be read: {}", pack, path, error),
+      Issue::Collision { path } => write!(f, "entry {} collides with a directory of the same name", path),
+      Issue::StaleTemporaryFile { file } => write!(f, "temporary file {} was left behind by an interrupted write", file),
+      Issue::MissingBlob { path, blob } => write!(f, "entry {} references missing blob {}", path, blob),
+      Issue::OrphanBlob { blob } => write!(f, "blob {} is not referenced by any entry", blob),
+    }
+  }
+}
+
+impl VaultContext {
+  /// Cross-check the index of the vault against the packs on disk.
+  ///
+  /// Every pack referenced by the index is decrypted and parsed, so this
+  /// requires the GPG private key of one of the vault's identities. Files
+  /// directly under the vault's root directory are not considered packs.
+  /// Blobs are cross-checked against the attributes of the entries, without
+  /// being decrypted. Orphan blobs are only reported when all entries could
+  /// be read.
+  pub fn check(&self) -> Result<Vec<Issue>, Box<dyn Error>> {
+    let mut issues = Vec::new();
+    let mut blobs = HashSet::new();
+    let index = self.vault.get_index();
+
+    for (path, pack) in index {
+      if !self.has_pack(pack) {
+        issues.push(Issue::MissingPack {
+          path: path.clone(),
+          pack: pack.clone(),
+        });
+
+        continue;
+      }
+
+      match Entry::read(self, pack) {
+        Ok(entry) => {
+          for blob in entry.get_blobs() {
+            if !self.has_pack(blob::blob_path(blob)) {
+              issues.push(Issue::MissingBlob {
+                path: path.clone(),
+                blob: blob.to_string(),
+              });
+            }
+
+            blobs.insert(blob.to_string());
+          }
+        }
+
+        Err(err) => issues.push(Issue::CorruptPack {
+          path: path.clone(),
+          pack: pack.clone(),
+          error: err.to_string(),
+        }),
+      }
+    }
+
+    let referenced = index.values().map(String::as_str).collect::<HashSet<&str>>();
+
+    for pack in self.list_packs()? {
+      if !referenced.contains(pack.as_str()) {
+        issues.push(Issue::OrphanPack { pack });
+      }
+    }
+
+    let directory = Path::new(&util::normalize_path(self, &util::BLOB_DIRECTORY)).to_path_buf();
+
+    if directory.is_dir() && !issues.iter().any(|issue| matches!(issue, Issue::CorruptPack { .. })) {
+      for file in read_dir(&directory)? {
+        let blob = file?.file_name().to_string_lossy().to_string();
+
+        if !blobs.contains(&blob) {
+          issues.push(Issue::OrphanBlob { blob });
+        }
+      }
+    }
+
+    let paths = index.keys().map(String::as_str).collect::<BTreeSet<&str>>();
+
+    for path in &paths {
+      let prefix = format!("{}/", path);
+
+      if matches!(paths.range(prefix.as_str()..).next(), Some(other) if other.starts_with(&prefix)) {
+        issues.push(Issue::Collision { path: path.to_string() });
+      }
+    }
+
+    let temporary = Path::new(&util::normalize_path(self, &util::TEMPORARY_DIRECTORY)).to_path_buf();
+
+    if temporary.is_dir() {
+      for file in read_dir(&temporary)? {
+        issues.push(Issue::StaleTemporaryFile {
+          file: format!("{}/{}", util::TEMPORARY_DIRECTORY, file?.file_name().to_string_lossy()),
+        });
+      }
+    }
+
+    Ok(issues)
+  }
+
+  /// Fix the repairable issues found by
+  /// [VaultContext::check](struct.VaultContext.html#method.check).
+  ///
+  /// Index entries referencing missing packs are dropped, orphan packs and
+  /// blobs are moved to the `_knox.quarantine` directory, and stale temporary
+  /// files are removed. The result is recorded as a single git commit. Issues that
+  /// cannot be repaired without losing data are left untouched.
+  ///
+  /// # Arguments
+  ///
+  ///  * `issues` - the issues to be repaired.
+  pub fn repair(&mut self, issues: &[Issue]) -> Result<(), Box<dyn Error>> {
+    let mut transaction = self.transaction();
+
+    for issue in issues {
+      match issue {
+        Issue::MissingPack { path, .. } => transaction.remove_index(path),
+        Issue::OrphanPack { pack } => transaction.quarantine_pack(pack)?,
+        Issue::StaleTemporaryFile { file } => transaction.remove_temporary_file(file)?,
+        Issue::OrphanBlob { blob } => transaction.quarantine_pack(&blob::blob_path(blob))?,
+        Issue::CorruptPack { .. } | Issue::Collision { .. } | Issue::MissingBlob { .. } => {}
+      }
+    }
+
+    transaction.commit("Repaired vault.")
+  }
+
+  fn list_packs(&self) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut packs = Vec::new();
+
+    for directory in read_dir(&self.path)? {
+      let directory = directory?;
+      let name = directory.file_name().to_string_lossy().to_string();
+
+      if !directory.file_type()?.is_dir() || name == ".git" || name == util::TEMPORARY_DIRECTORY || name == util::QUARANTINE_DIRECTORY || name == util::BLOB_DIRECTORY {
+        continue;
+      }
+
+      list_files(&directory.path(), &name, &mut packs)?;
+    }
+
+    packs.sort();
+
+    Ok(packs)
+  }
+}
+
+fn list_files(directory: &Path, prefix: &str, files: &mut Vec<String>) -> Result<(), Box<dyn Error>> {
+  for file in read_dir(directory)? {
+    let file = file?;
+    let name = format!("{}/{}", prefix, file.file_name().to_string_lossy());
+
+    if file.file_type()?.is_dir() {
+      list_files(&file.path(), &name, files)?;
+    } else {
+      files.push(name);
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn check_and_repair() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("lorem", "ipsum");
+
+    context.write_entry("foo", &entry).expect("could not write entry");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.write_entry("dolor/sit", &entry).expect("could not write entry");
+
+    entry.add_tag("lorem").expect("could not add tag");
+    entry.set_expires(1);
+
+    context.write_entry("lorem/ipsum", &entry).expect("could not write entry");
+
+    let missing = context.vault.get_index().get("lorem/ipsum").expect("could not find index").clone();
+    let orphan = context.vault.get_index().get("dolor/sit").expect("could not find index").clone();
+    let corrupt = context.vault.get_index().get("foo/bar").expect("could not find index").clone();
+
+    fs::remove_file(tmp.path().join(&missing)).expect("could not remove pack");
+    fs::write(tmp.path().join(&corrupt), b"garbage").expect("could not corrupt pack");
+    context.remove_index("dolor/sit");
+    context.write().expect("could not write metadata");
+
+    let issues = context.check().expect("could not check vault");
+
+    assert_eq!(issues.len(), 4);
+    assert!(issues.contains(&Issue::MissingPack {
+      path: "lorem/ipsum".to_string(),
+      pack: missing.clone()
+    }));
+    assert!(issues.contains(&Issue::OrphanPack { pack: orphan.clone() }));
+    assert!(issues.contains(&Issue::Collision { path: "foo".to_string() }));
+    assert!(issues.iter().any(|issue| matches!(issue, Issue::CorruptPack { path, .. } if path == "foo/bar")));
+
+    context.repair(&issues).expect("could not repair vault");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+    let issues = retrieved.check().expect("could not check vault");
+
+    assert!(!retrieved.vault.get_index().contains_key("lorem/ipsum"));
+    assert!(!retrieved.vault.get_tags().contains_key("lorem/ipsum"));
+    assert!(!retrieved.vault.get_expirations().contains_key("lorem/ipsum"));
+    assert!(!retrieved.has_pack(&orphan));
+    assert!(retrieved.has_pack(format!("_knox.quarantine/{}", &orphan[3..])));
+    assert_eq!(issues.len(), 2);
+    assert!(issues.iter().all(|issue| !issue.is_repairable()));
+  }
+
+  #[test]
+  fn blobs() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    let attribute = context.write_blob(&b"lorem"[..]).expect("could not write blob");
+    let missing = attribute.get_blob().to_string();
+    entry.attributes.insert("backup".to_string(), attribute);
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let orphan = context.write_blob(&b"ipsum"[..]).expect("could not write blob").get_blob().to_string();
+
+    fs::remove_file(tmp.path().join(util::BLOB_DIRECTORY).join(&missing)).expect("could not remove blob");
+
+    let issues = context.check().expect("could not check vault");
+
+    assert_eq!(issues.len(), 2);
+    assert!(issues.contains(&Issue::MissingBlob {
+      path: "foo/bar".to_string(),
+      blob: missing
+    }));
+    assert!(issues.contains(&Issue::OrphanBlob { blob: orphan.clone() }));
+
+    context.repair(&issues).expect("could not repair vault");
+
+    assert!(context.has_pack(format!("_knox.quarantine/{}", orphan)));
+    assert_eq!(context.check().expect("could not check vault").len(), 1);
+  }
+}
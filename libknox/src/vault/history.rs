@@ -0,0 +1,136 @@
+//! Keep track of previous values of [Attributes](struct.Attribute.html).
+
+use std::error::Error;
+
+use crate::pb::*;
+use crate::util::VaultError;
+
+/// Maximum number of previous values kept for each attribute.
+pub const HISTORY_SIZE: usize = 10;
+
+impl Entry {
+  /// Record the attributes that changed since a previous version of the
+  /// entry.
+  ///
+  /// Every attribute of `previous` that was modified or removed is appended
+  /// to the history of its attribute, along with when and by whom it was
+  /// replaced. Only the [HISTORY_SIZE](constant.HISTORY_SIZE.html) most recent
+  /// values are kept.
+  pub(crate) fn record_history(&mut self, previous: &Entry, author: &str, timestamp: u64) {
+    for (key, attribute) in previous.get_attributes() {
+      if self.get_attributes().get(key) == Some(attribute) {
+        continue;
+      }
+
+      let revisions = self.mut_history().entry(key.to_string()).or_default().mut_revisions();
+
+      revisions.push(Revision {
+        attribute: protobuf::SingularPtrField::some(attribute.clone()),
+        timestamp,
+        author: author.to_string(),
+        ..Revision::default()
+      });
+
+      while revisions.len() > HISTORY_SIZE {
+        revisions.remove(0);
+      }
+    }
+  }
+
+  /// List the previous values of an attribute, most recent first.
+  ///
+  /// # Arguments
+  ///
+  ///  * `key` - the name of the attribute.
+  pub fn get_revisions(&self, key: &str) -> Vec<&Revision> {
+    match self.get_history().get(key) {
+      Some(history) => history.get_revisions().iter().rev().collect(),
+      None => Vec::new(),
+    }
+  }
+
+  /// Restore a previous value of an attribute.
+  ///
+  /// The current value of the attribute will be added to its history when the
+  /// entry is written.
+  ///
+  /// # Arguments
+  ///
+  ///  * `key`      - the name of the attribute.
+  ///  * `revision` - the position in the history, 1 being the most recent.
+  pub fn restore_attribute(&mut self, key: &str, revision: usize) -> Result<(), Box<dyn Error>> {
+    let attribute = match self.get_revisions(key).get(revision.wrapping_sub(1)) {
+      Some(revision) => revision.get_attribute().clone(),
+      None => return Err(VaultError::throw(&format!("revision {} of attribute {} does not exist", revision, key))),
+    };
+
+    self.mut_attributes().insert(key.to_string(), attribute);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn record_and_restore() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_confidential_attribute("password", "first");
+    entry.add_attribute("username", "bob");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let mut entry = context.read_entry("foo/bar").expect("could not read entry");
+    entry.add_confidential_attribute("password", "second");
+    entry.mut_attributes().remove("username");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let mut entry = context.read_entry("foo/bar").expect("could not read entry");
+    let revisions = entry.get_revisions("password");
+
+    assert_eq!(revisions.len(), 1);
+    assert_eq!(revisions[0].get_attribute().get_value(), "first");
+    assert!(revisions[0].get_author().contains(spec::GPG_IDENTITY));
+    assert_eq!(entry.get_revisions("username")[0].get_attribute().get_value(), "bob");
+
+    entry.restore_attribute("password", 1).expect("could not restore attribute");
+
+    assert!(entry.restore_attribute("password", 2).is_err());
+    assert!(entry.restore_attribute("password", 0).is_err());
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let entry = context.read_entry("foo/bar").expect("could not read entry");
+    let revisions = entry.get_revisions("password");
+
+    assert_eq!(entry.get_attributes().get("password").expect("could not get attribute").get_value(), "first");
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0].get_attribute().get_value(), "second");
+  }
+
+  #[test]
+  fn bounded() {
+    let mut previous = Entry::default();
+    let mut entry = Entry::default();
+
+    for index in 0..(HISTORY_SIZE + 5) {
+      previous.add_attribute("password", &index.to_string());
+      entry.add_attribute("password", &(index + 1).to_string());
+      entry.record_history(&previous, "", index as u64);
+    }
+
+    let revisions = entry.get_revisions("password");
+
+    assert_eq!(revisions.len(), HISTORY_SIZE);
+    assert_eq!(revisions[0].get_attribute().get_value(), (HISTORY_SIZE + 4).to_string());
+    assert_eq!(revisions[HISTORY_SIZE - 1].get_attribute().get_value(), "5");
+  }
+}
@@ -0,0 +1,202 @@
+//! Configuration specific to a local copy of a [Vault](struct.Vault.html).
+//!
+//! This configuration is stored unencrypted in the `_knox.local` file, which
+//! is not versioned: it describes what this copy of the vault trusts, and
+//! must not be modifiable by anyone able to push to its git remote.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use log::*;
+use protobuf::{parse_from_bytes, Message};
+
+use super::context::VaultContext;
+use crate::backend::Signature;
+use crate::pb::*;
+use crate::util::{self, VaultError};
+
+impl VaultContext {
+  /// Get the local configuration of the vault.
+  pub fn get_local_config(&self) -> LocalConfig {
+    self.local.borrow().clone()
+  }
+
+  /// Replace and persist the local configuration of the vault.
+  ///
+  /// Trusted signers cannot be added to vaults whose backend does not sign
+  /// data, since nothing could be read from them anymore.
+  ///
+  /// # Arguments
+  ///
+  ///  * `config` - the new local configuration.
+  pub fn write_local_config(&mut self, config: LocalConfig) -> Result<(), Box<dyn Error>> {
+    if !self.backend().can_sign() {
+      let current = self.local.borrow();
+
+      if config.get_trusted_signers().iter().any(|signer| !current.get_trusted_signers().contains(signer)) {
+        return Err(VaultError::throw(&format!(
+          "the {} backend does not sign data, trusted signers cannot be configured",
+          self.vault.get_backend().name()
+        )));
+      }
+    }
+
+    self.local.replace(config);
+    self.lock_exclusive()?;
+    self.write_local()
+  }
+
+  /// Check if a signature was made by one of the trusted signers.
+  ///
+  /// # Arguments
+  ///
+  ///  * `signature` - the signature to check.
+  pub fn is_trusted_signer(&self, signature: &Signature) -> bool {
+    is_trusted_signer(&self.local.borrow(), signature)
+  }
+
+  /// Apply the signature policy to data read from the vault.
+  ///
+  /// Nothing is verified until at least one trusted signer is configured.
+  /// Data that is not signed by a trusted signer is then either reported, or
+  /// refused, depending on the configured policy.
+  pub(crate) fn verify_signature(&self, subject: &str, signature: Option<&Signature>) -> Result<(), Box<dyn Error>> {
+    verify_signature(&self.local.borrow(), subject, signature)
+  }
+
+  /// Check that an entry was read from the pack it was written to.
+  ///
+  /// Since the location is part of the signed content, this prevents a
+  /// validly signed pack from being swapped with another one.
+  pub(crate) fn verify_location(&self, subject: &str, entry: &Entry, location: &str) -> Result<(), Box<dyn Error>> {
+    verify_location(&self.local.borrow(), subject, entry, location)
+  }
+
+  /// Apply the signature policy to data that cannot be verified, when
+  /// trusted signers are configured.
+  pub(crate) fn report_unverified(&self, message: &str) -> Result<(), Box<dyn Error>> {
+    report_unverified(&self.local.borrow(), message)
+  }
+
+  pub(crate) fn write_local(&self) -> Result<(), Box<dyn Error>> {
+    util::write_atomic(self, &util::LOCAL_FILE, &self.local.borrow().write_to_bytes()?)
+  }
+}
+
+fn is_trusted_signer(config: &LocalConfig, signature: &Signature) -> bool {
+  config.get_trusted_signers().iter().any(|signer| signer.eq_ignore_ascii_case(&signature.fingerprint))
+}
+
+pub(crate) fn verify_signature(config: &LocalConfig, subject: &str, signature: Option<&Signature>) -> Result<(), Box<dyn Error>> {
+  if config.get_trusted_signers().is_empty() {
+    return Ok(());
+  }
+
+  let message = match signature {
+    Some(signature) if is_trusted_signer(config, signature) => return Ok(()),
+    Some(signature) => format!("{} was signed by an untrusted key, {} ({})", subject, signature.signer, signature.fingerprint),
+    None => format!("{} is not signed by any trusted key", subject),
+  };
+
+  apply_signature_policy(config, &message)
+}
+
+pub(crate) fn verify_location(config: &LocalConfig, subject: &str, entry: &Entry, location: &str) -> Result<(), Box<dyn Error>> {
+  if entry.get_location() == location {
+    return Ok(());
+  }
+
+  if !entry.get_location().is_empty() {
+    return Err(VaultError::throw(&format!("{} was written to {}, but read from {}", subject, entry.get_location(), location)));
+  }
+
+  report_unverified(config, &format!("{} does not record its location", subject))
+}
+
+fn report_unverified(config: &LocalConfig, message: &str) -> Result<(), Box<dyn Error>> {
+  if config.get_trusted_signers().is_empty() {
+    return Ok(());
+  }
+
+  apply_signature_policy(config, message)
+}
+
+fn apply_signature_policy(config: &LocalConfig, message: &str) -> Result<(), Box<dyn Error>> {
+  match config.get_signature_policy() {
+    SignaturePolicy::WARN_UNSIGNED => {
+      warn!("{}", message);
+      Ok(())
+    }
+
+    SignaturePolicy::REFUSE_UNSIGNED => Err(VaultError::throw(message)),
+  }
+}
+
+pub(crate) fn write_local_config<P>(path: P, config: &LocalConfig) -> Result<(), Box<dyn Error>>
+where
+  P: AsRef<Path>,
+{
+  util::write_atomic_in(path, &util::LOCAL_FILE, &config.write_to_bytes()?)
+}
+
+pub(crate) fn read_local_config<P>(path: P) -> Result<LocalConfig, Box<dyn Error>>
+where
+  P: AsRef<Path>,
+{
+  let path = path.as_ref().join(util::LOCAL_FILE);
+
+  if !path.exists() {
+    return Ok(LocalConfig::default());
+  }
+
+  Ok(parse_from_bytes::<LocalConfig>(&fs::read(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn trusted_signers() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+
+    let mut config = LocalConfig::default();
+    config.mut_trusted_signers().push("0000000000000000000000000000000000000000".to_string());
+    config.set_signature_policy(SignaturePolicy::REFUSE_UNSIGNED);
+
+    context.write_local_config(config.clone()).expect("could not write local config");
+
+    assert!(VaultContext::open(tmp.path()).is_err());
+
+    config.mut_trusted_signers().push(spec::GPG_FINGERPRINT.to_lowercase());
+    context.write_local_config(config).expect("could not write local config");
+
+    let context = VaultContext::open(tmp.path()).expect("could not open vault");
+    let (_, signature) = context.read_signed_entry("foo/bar").expect("could not read entry");
+
+    assert!(context.is_trusted_signer(&signature.expect("entry was not signed")));
+    assert!(!git::is_tracked(std::path::Path::new(util::LOCAL_FILE)));
+  }
+
+  #[test]
+  fn swapped_packs() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    context.write_entry("bar", &Entry::default()).expect("could not write entry");
+
+    let (foo, bar) = (context.vault.get_index().get("foo").unwrap().clone(), context.vault.get_index().get("bar").unwrap().clone());
+
+    std::fs::copy(tmp.path().join(&foo), tmp.path().join(&bar)).expect("could not copy pack");
+
+    assert!(context.read_entry("foo").is_ok());
+    assert!(context.read_entry("bar").is_err());
+  }
+}
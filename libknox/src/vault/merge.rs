@@ -0,0 +1,318 @@
+//! Three-way merge of the metadata of a [Vault](struct.Vault.html).
+//!
+//! The metadata is encrypted, so git cannot merge two versions of it by
+//! itself. Vaults initialized with git register `knox git merge-driver` as
+//! the merge driver of `_knox.meta` in their `.gitattributes` file, which
+//! decrypts the common ancestor and both sides, and merges them field by
+//! field.
+//!
+//! An entry keeps its pack when it is modified, so an entry modified on both
+//! sides does not conflict in the metadata: git reports a conflict on its
+//! pack instead, which is resolved by
+//! [VaultContext::pull](struct.VaultContext.html#method.pull).
+
+use std::cmp;
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::path::Path;
+
+use protobuf::parse_from_bytes;
+
+use super::context::VaultContext;
+use super::local;
+use super::pack::{self, Packing};
+use crate::backend::{self, Backend};
+use crate::pb::*;
+use crate::util::VaultError;
+
+/// A change made differently on both sides of a merge by
+/// [VaultContext::merge_metadata](struct.VaultContext.html#method.merge_metadata).
+///
+/// The conflicting value is taken from our side, or from theirs if we
+/// deleted it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conflict {
+  /// An entry points to different packs, or was modified on one side and
+  /// deleted on the other.
+  Entry { path: String, ours: Option<String>, theirs: Option<String> },
+  /// The tags of an entry were modified differently.
+  Tags { path: String },
+  /// A template was modified differently.
+  Template { name: String },
+  /// The expiration of an entry was modified differently.
+  Expiration { path: String },
+  /// An entry was written on a side whose identities differ from the merged
+  /// ones, so it is not encrypted for all of them and must be encrypted
+  /// again.
+  Identities { path: String },
+  /// The pack of an entry was modified on both sides, or modified on one
+  /// side and deleted on the other. The version chosen by the
+  /// [Resolution](enum.Resolution.html) of the pull is kept, and deletions
+  /// are always kept.
+  Pack { path: String, deleted: bool },
+}
+
+impl fmt::Display for Conflict {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Conflict::Entry { path, ours, theirs } => write!(
+        f,
+        "entry {} points to {} on our side and to {} on theirs",
+        path,
+        ours.as_ref().map(String::as_str).unwrap_or("nothing"),
+        theirs.as_ref().map(String::as_str).unwrap_or("nothing")
+      ),
+      Conflict::Tags { path } => write!(f, "the tags of entry {} were modified on both sides", path),
+      Conflict::Template { name } => write!(f, "template {} was modified on both sides", name),
+      Conflict::Expiration { path } => write!(f, "the expiration of entry {} was modified on both sides", path),
+      Conflict::Identities { path } => write!(f, "entry {} is not encrypted for the merged identities of the vault", path),
+      Conflict::Pack { path, deleted: false } => write!(f, "entry {} was modified on both sides", path),
+      Conflict::Pack { path, deleted: true } => write!(f, "entry {} was modified on one side and deleted on the other", path),
+    }
+  }
+}
+
+impl VaultContext {
+  /// Merge two versions of the encrypted metadata of a vault.
+  ///
+  /// The common ancestor and both sides are decrypted, and their identities,
+  /// index, tags, templates and expirations are merged: a change made on
+  /// only one side is kept, and changes made differently on both sides are
+  /// reported as conflicts. The merged metadata is then encrypted for the
+  /// merged identities, with a generation above both sides.
+  ///
+  /// Entries are only encrypted for the identities of the side they were
+  /// written on: when the identities were modified on either side, the
+  /// entries written on a side whose identities differ from the merged ones
+  /// are reported as conflicts as well.
+  ///
+  /// If trusted signers are configured locally, both sides are verified
+  /// according to the configured policy before being merged.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`   - filesystem path where the vault is located.
+  ///  * `base`   - the common ancestor, which may be empty.
+  ///  * `ours`   - the current version of the metadata.
+  ///  * `theirs` - the version of the metadata being merged.
+  pub fn merge_metadata<P>(path: P, base: &[u8], ours: &[u8], theirs: &[u8]) -> Result<(Vec<u8>, Vec<Conflict>), Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let backend = backend::open(backend::detect(ours), &path)?;
+    let config = local::read_local_config(&path)?;
+
+    let base = if base.is_empty() {
+      Vault::default()
+    } else {
+      decrypt(&*backend, &config, "the common ancestor of the vault metadata", base)?
+    };
+    let ours = decrypt(&*backend, &config, "our vault metadata", ours)?;
+    let theirs = decrypt(&*backend, &config, "their vault metadata", theirs)?;
+
+    if ours.get_backend() != theirs.get_backend() {
+      return Err(VaultError::throw("cannot merge vault metadata encrypted with different backends"));
+    }
+
+    let (merged, conflicts) = merge(&base, &ours, &theirs);
+    let ciphertext = backend.encrypt(merged.get_identities(), &pack::compress(&merged.pack()?)?)?;
+
+    Ok((ciphertext, conflicts))
+  }
+}
+
+fn decrypt(backend: &dyn Backend, config: &LocalConfig, subject: &str, ciphertext: &[u8]) -> Result<Vault, Box<dyn Error>> {
+  let (pack, signature) = backend.decrypt_and_verify(ciphertext)?;
+
+  local::verify_signature(config, subject, signature.as_ref())?;
+
+  Ok(parse_from_bytes::<Vault>(&pack::decompress(&pack)?)?)
+}
+
+fn merge(base: &Vault, ours: &Vault, theirs: &Vault) -> (Vault, Vec<Conflict>) {
+  let mut merged = ours.clone();
+  let mut conflicts = Vec::new();
+
+  merged.set_identities(merge_identities(base.get_identities(), ours.get_identities(), theirs.get_identities()).into());
+  merged.set_version(cmp::max(ours.get_version(), theirs.get_version()));
+  merged.set_generation(cmp::max(ours.get_generation(), theirs.get_generation()) + 1);
+
+  let (index, paths) = merge_map(base.get_index(), ours.get_index(), theirs.get_index());
+  conflicts.extend(paths.into_iter().map(|path| Conflict::Entry {
+    ours: ours.get_index().get(&path).cloned(),
+    theirs: theirs.get_index().get(&path).cloned(),
+    path,
+  }));
+  let mut stale = stale_entries(base, ours, &merged, &index);
+  stale.extend(stale_entries(base, theirs, &merged, &index));
+  conflicts.extend(stale.into_iter().map(|path| Conflict::Identities { path }));
+  merged.set_index(index);
+
+  let (tags, paths) = merge_map(base.get_tags(), ours.get_tags(), theirs.get_tags());
+  conflicts.extend(paths.into_iter().map(|path| Conflict::Tags { path }));
+  merged.set_tags(tags);
+
+  let (templates, names) = merge_map(base.get_templates(), ours.get_templates(), theirs.get_templates());
+  conflicts.extend(names.into_iter().map(|name| Conflict::Template { name }));
+  merged.set_templates(templates);
+
+  let (expirations, paths) = merge_map(base.get_expirations(), ours.get_expirations(), theirs.get_expirations());
+  conflicts.extend(paths.into_iter().map(|path| Conflict::Expiration { path }));
+  merged.set_expirations(expirations);
+
+  (merged, conflicts)
+}
+
+fn merge_identities(base: &[String], ours: &[String], theirs: &[String]) -> Vec<String> {
+  let mut merged: Vec<String> = Vec::new();
+
+  for identity in ours.iter().chain(theirs) {
+    let kept = (ours.contains(identity) && theirs.contains(identity)) || !base.contains(identity);
+
+    if kept && !merged.contains(identity) {
+      merged.push(identity.clone());
+    }
+  }
+
+  merged
+}
+
+// Entries written on one side, and kept in the merged index, are only
+// encrypted for the identities of that side.
+fn stale_entries(base: &Vault, side: &Vault, merged: &Vault, index: &HashMap<String, String>) -> BTreeSet<String> {
+  let identities: BTreeSet<&String> = side.get_identities().iter().collect();
+
+  if identities == merged.get_identities().iter().collect() {
+    return BTreeSet::new();
+  }
+
+  side
+    .get_index()
+    .iter()
+    .filter(|(path, pack)| base.get_index().get(*path) != Some(pack) && index.get(*path) == Some(pack))
+    .map(|(path, _)| path.clone())
+    .collect()
+}
+
+fn merge_map<V>(base: &HashMap<String, V>, ours: &HashMap<String, V>, theirs: &HashMap<String, V>) -> (HashMap<String, V>, Vec<String>)
+where
+  V: Clone + PartialEq,
+{
+  let mut merged = HashMap::new();
+  let mut conflicts = Vec::new();
+  let keys: BTreeSet<&String> = base.keys().chain(ours.keys()).chain(theirs.keys()).collect();
+
+  for key in keys {
+    let (base, ours, theirs) = (base.get(key), ours.get(key), theirs.get(key));
+
+    let value = if ours == theirs || theirs == base {
+      ours
+    } else if ours == base {
+      theirs
+    } else {
+      conflicts.push(key.clone());
+      ours.or(theirs)
+    };
+
+    if let Some(value) = value {
+      merged.insert(key.clone(), value.clone());
+    }
+  }
+
+  (merged, conflicts)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use knox_testing::spec;
+
+  use super::Conflict;
+  use crate::*;
+
+  fn vault(identities: &[&str], index: &[(&str, &str)]) -> Vault {
+    let mut vault = Vault::default();
+
+    vault.set_identities(identities.iter().map(|identity| identity.to_string()).collect::<Vec<String>>().into());
+
+    for (path, pack) in index {
+      vault.mut_index().insert(path.to_string(), pack.to_string());
+    }
+
+    vault
+  }
+
+  #[test]
+  fn merge() {
+    let base = vault(&["alice", "bob"], &[("a", "1"), ("b", "2"), ("c", "3")]);
+    let mut ours = vault(&["alice", "bob", "carol"], &[("a", "1"), ("b", "4"), ("c", "5"), ("d", "6")]);
+    let theirs = vault(&["alice", "dave"], &[("b", "2"), ("c", "7"), ("e", "8")]);
+
+    ours.set_generation(3);
+
+    let (merged, conflicts) = super::merge(&base, &ours, &theirs);
+
+    assert_eq!(merged.get_identities(), &["alice", "carol", "dave"]);
+    assert_eq!(merged.get_index().len(), 4);
+    assert_eq!(merged.get_index().get("b").map(String::as_str), Some("4"));
+    assert_eq!(merged.get_index().get("c").map(String::as_str), Some("5"));
+    assert!(!merged.get_index().contains_key("a"));
+    assert_eq!(merged.get_generation(), 4);
+    assert_eq!(
+      conflicts,
+      vec![
+        Conflict::Entry {
+          path: "c".to_string(),
+          ours: Some("5".to_string()),
+          theirs: Some("7".to_string()),
+        },
+        Conflict::Identities { path: "b".to_string() },
+        Conflict::Identities { path: "c".to_string() },
+        Conflict::Identities { path: "d".to_string() },
+        Conflict::Identities { path: "e".to_string() },
+      ]
+    );
+  }
+
+  #[test]
+  fn merge_identities() {
+    let base = vault(&["alice", "bob"], &[("a", "1")]);
+    let ours = vault(&["alice"], &[("a", "2")]);
+    let theirs = vault(&["alice", "bob"], &[("a", "1"), ("b", "3")]);
+
+    let (merged, conflicts) = super::merge(&base, &ours, &theirs);
+
+    assert_eq!(merged.get_identities(), &["alice"]);
+    assert_eq!(conflicts, vec![Conflict::Identities { path: "b".to_string() }]);
+  }
+
+  #[test]
+  fn merge_metadata() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    let metadata = tmp.path().join(util::METADATA_FILE);
+    let base = fs::read(&metadata).expect("could not read metadata");
+
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    let ours = fs::read(&metadata).expect("could not read metadata");
+
+    context.delete_entry("foo").expect("could not delete entry");
+    context.write_entry("bar", &Entry::default()).expect("could not write entry");
+    let theirs = fs::read(&metadata).expect("could not read metadata");
+
+    let (merged, conflicts) = VaultContext::merge_metadata(tmp.path(), &base, &ours, &theirs).expect("could not merge metadata");
+
+    assert!(conflicts.is_empty());
+
+    fs::write(&metadata, merged).expect("could not write metadata");
+    drop(context);
+
+    let context = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert!(context.vault.get_index().contains_key("foo"));
+    assert!(context.vault.get_index().contains_key("bar"));
+    assert_eq!(context.vault.get_generation(), 5);
+  }
+}
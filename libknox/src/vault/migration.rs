@@ -0,0 +1,135 @@
+//! Upgrade vaults created with previous versions of the storage format.
+
+use std::error::Error;
+
+use super::transaction::Transaction;
+use crate::gpg;
+use crate::pb::BackendKind;
+use crate::util::VaultError;
+
+/// Version of the storage format written by this version of libknox.
+pub const VAULT_VERSION: u32 = 6;
+
+/// A step upgrading a vault from one storage format version to the next.
+pub struct Migration {
+  /// Version of the storage format after this migration was applied.
+  pub version: u32,
+  /// Human-readable description of what the migration does.
+  pub description: &'static str,
+  pub(crate) apply: fn(&mut Transaction) -> Result<(), Box<dyn Error>>,
+}
+
+/// All known migrations, in the order they must be applied.
+pub(crate) const MIGRATIONS: &[Migration] = &[
+  Migration {
+    version: 1,
+    description: "replace GPG identities with their key fingerprints",
+    apply: identities_to_fingerprints,
+  },
+  Migration {
+    version: 2,
+    description: "compress the entries",
+    apply: rewrite_entries,
+  },
+  Migration {
+    version: 3,
+    description: "record the encryption backend of the vault",
+    apply: record_gpg_backend,
+  },
+  Migration {
+    version: 4,
+    description: "allow passphrase-only vaults and their key derivation header",
+    apply: unchanged,
+  },
+  Migration {
+    version: 5,
+    description: "sign the metadata, the entries and their blobs",
+    apply: rewrite_entries,
+  },
+  Migration {
+    version: 6,
+    description: "record the generation of the metadata",
+    apply: unchanged,
+  },
+];
+
+fn identities_to_fingerprints(transaction: &mut Transaction) -> Result<(), Box<dyn Error>> {
+  let mut context = gpg::get_context()?;
+  let identities = transaction.vault.get_identities().to_vec();
+
+  for identity in identities {
+    let keys = gpg::get_keys(&mut context, std::slice::from_ref(&identity))?;
+
+    transaction.remove_identity(&identity);
+
+    for key in keys {
+      let fingerprint = key
+        .fingerprint()
+        .map_err(|_| VaultError::throw(&format!("could not get the fingerprint of the key for {}", identity)))?;
+
+      if !transaction.vault.get_identities().contains(&fingerprint.to_string()) {
+        transaction.add_identity(fingerprint);
+      }
+    }
+  }
+
+  Ok(())
+}
+
+fn rewrite_entries(transaction: &mut Transaction) -> Result<(), Box<dyn Error>> {
+  let paths: Vec<String> = transaction.vault.get_index().keys().cloned().collect();
+
+  for path in paths {
+    transaction.rewrite_entry(&path)?;
+  }
+
+  Ok(())
+}
+
+fn record_gpg_backend(transaction: &mut Transaction) -> Result<(), Box<dyn Error>> {
+  // Vaults could only be encrypted with GPG before the backend was recorded.
+  transaction.vault_mut().set_backend(BackendKind::GPG);
+
+  Ok(())
+}
+
+// Some versions only prevent older versions of knox from opening vaults they
+// cannot read, or only need the metadata to be written again, which every
+// migration does.
+fn unchanged(_: &mut Transaction) -> Result<(), Box<dyn Error>> {
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn migrate() {
+    let tmp = spec::setup();
+    let mut context = VaultContext::create(tmp.path(), &[spec::GPG_IDENTITY.to_string()]).expect("could not create vault");
+    context.vault.set_version(0);
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+
+    let mut context = VaultContext::open(tmp.path()).expect("could not open vault");
+    let migrations = context.pending_migrations();
+
+    assert_eq!(migrations.len(), VAULT_VERSION as usize);
+    assert_eq!(migrations[0].version, 1);
+    assert!(context.write_entry("bar", &Entry::default()).is_err());
+
+    for migration in migrations {
+      context.migrate(migration).expect("could not apply migration");
+    }
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.get_version(), VAULT_VERSION);
+    assert_eq!(retrieved.vault.get_identities(), &[spec::GPG_FINGERPRINT.to_string()]);
+    assert!(retrieved.pending_migrations().is_empty());
+    assert!(retrieved.read_entry("foo").is_ok());
+    assert!(context.write_entry("bar", &Entry::default()).is_ok());
+  }
+}
@@ -1,11 +1,37 @@
 pub mod attribute;
+pub mod blob;
+pub mod changelog;
 pub mod context;
+pub mod diff;
 pub mod entry;
+pub mod expiry;
+pub mod fsck;
+pub mod history;
+pub mod local;
+pub mod merge;
+pub mod migration;
 pub(crate) mod pack;
+pub mod purge;
+pub mod rollback;
+pub mod sync;
+pub mod tags;
+pub mod template;
+pub mod transaction;
 
+pub use crate::backend::Signature;
 pub use crate::vault::attribute::AttributeValue;
+pub use crate::vault::blob::BLOB_THRESHOLD;
+pub use crate::vault::changelog::LogEntry;
 pub use crate::vault::context::VaultContext;
+pub use crate::vault::diff::Difference;
+pub use crate::vault::fsck::Issue;
+pub use crate::vault::history::HISTORY_SIZE;
+pub use crate::vault::merge::Conflict;
+pub use crate::vault::migration::{Migration, VAULT_VERSION};
 pub use crate::vault::pack::Packing;
+pub use crate::vault::purge::Purge;
+pub use crate::vault::sync::{Access, Change, Resolution};
+pub use crate::vault::transaction::Transaction;
 
 pub use crate::pb::*;
-pub use crate::util::{git, totp, VaultError};
+pub use crate::util::{git, set_lock_timeout, totp, VaultError};
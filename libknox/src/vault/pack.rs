@@ -1,7 +1,30 @@
 use std::error::Error;
+use std::io::{Read, Write};
 
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
 use protobuf::{CodedOutputStream, Message};
 
+use crate::util::VaultError;
+
+/// Header of compressed payloads.
+///
+/// Serialized entries and metadata never start with these bytes, which would
+/// be decoded as a deprecated group field, so uncompressed payloads written by
+/// previous versions are still recognized.
+const COMPRESSION_HEADER: &[u8] = b"KNXZ";
+
+/// Version of the compression format, following the header.
+const COMPRESSION_DEFLATE: u8 = 1;
+
+/// Maximum size of a decompressed payload.
+///
+/// Large files are stored as blobs, which are not compressed, so entries and
+/// metadata stay well below this size, while a small crafted payload cannot
+/// expand into an unbounded amount of memory.
+const MAX_DECOMPRESSED_SIZE: u64 = 64 * 1024 * 1024;
+
 /// Trait for message serialization
 pub trait Packing {
   fn pack(&self) -> Result<Vec<u8>, Box<dyn Error>>;
@@ -22,6 +45,59 @@ where
   }
 }
 
+/// Compress a serialized payload before it is encrypted.
+///
+/// The payload is deflated and prefixed with a header describing the
+/// compression format. Payloads that would not shrink are returned as is.
+///
+/// The age and passphrase backends do not compress what they encrypt, and
+/// the metadata, which holds the whole index and is written on every change,
+/// as well as entries carrying small files and the history of their
+/// attributes, compress well. Compression can only happen before encryption,
+/// since ciphertext does not compress.
+pub(crate) fn compress(payload: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+  let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+  encoder.write_all(payload)?;
+
+  let compressed = encoder.finish()?;
+
+  if compressed.len() + COMPRESSION_HEADER.len() + 1 >= payload.len() {
+    return Ok(payload.to_vec());
+  }
+
+  let mut output = Vec::with_capacity(compressed.len() + COMPRESSION_HEADER.len() + 1);
+  output.extend_from_slice(COMPRESSION_HEADER);
+  output.push(COMPRESSION_DEFLATE);
+  output.extend(compressed);
+
+  Ok(output)
+}
+
+/// Decompress a decrypted payload.
+///
+/// Payloads without a compression header are returned as is.
+pub(crate) fn decompress(payload: &[u8]) -> Result<Vec<u8>, Box<dyn Error>> {
+  if !payload.starts_with(COMPRESSION_HEADER) {
+    return Ok(payload.to_vec());
+  }
+
+  match payload.get(COMPRESSION_HEADER.len()) {
+    Some(&COMPRESSION_DEFLATE) => {
+      let mut output = Vec::new();
+      DeflateDecoder::new(&payload[COMPRESSION_HEADER.len() + 1..]).take(MAX_DECOMPRESSED_SIZE + 1).read_to_end(&mut output)?;
+
+      if output.len() as u64 > MAX_DECOMPRESSED_SIZE {
+        return Err(VaultError::throw(&format!("decompressed payload exceeds {} bytes", MAX_DECOMPRESSED_SIZE)));
+      }
+
+      Ok(output)
+    }
+
+    Some(format) => Err(VaultError::throw(&format!("unsupported compression format {}", format))),
+    None => Err(VaultError::throw("truncated compression header")),
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use knox_testing::spec;
@@ -41,11 +117,84 @@ mod tests {
 
     assert_eq!(
       pack,
-      vec![10, 40, 54, 65, 50, 53, 70, 67, 70, 50, 49, 51, 67, 55, 55, 55, 57, 65, 68, 50, 54, 68, 67, 53, 48, 55, 48, 54, 67, 66, 54, 52, 51, 66, 52, 50, 69, 55, 67, 68, 51, 69]
+      vec![
+        10,
+        40,
+        54,
+        65,
+        50,
+        53,
+        70,
+        67,
+        70,
+        50,
+        49,
+        51,
+        67,
+        55,
+        55,
+        55,
+        57,
+        65,
+        68,
+        50,
+        54,
+        68,
+        67,
+        53,
+        48,
+        55,
+        48,
+        54,
+        67,
+        66,
+        54,
+        52,
+        51,
+        66,
+        52,
+        50,
+        69,
+        55,
+        67,
+        68,
+        51,
+        69,
+        24,
+        VAULT_VERSION as u8,
+        64,
+        1
+      ]
     );
 
     let repack = context.vault.pack().expect("could not pack vault");
 
     assert_eq!(pack, repack);
   }
+
+  #[test]
+  fn compress() {
+    let payload = "lorem ipsum dolor sit amet ".repeat(100).into_bytes();
+    let compressed = super::compress(&payload).expect("could not compress payload");
+
+    assert!(compressed.len() < payload.len());
+    assert!(compressed.starts_with(super::COMPRESSION_HEADER));
+    assert_eq!(super::decompress(&compressed).expect("could not decompress payload"), payload);
+
+    let payload = vec![10, 5, 108, 111, 114, 101, 109];
+
+    assert_eq!(super::compress(&payload).expect("could not compress payload"), payload);
+    assert_eq!(super::decompress(&payload).expect("could not decompress payload"), payload);
+    assert!(super::decompress(b"KNXZ").is_err());
+    assert!(super::decompress(b"KNXZ\x09").is_err());
+  }
+
+  #[test]
+  fn decompress_bomb() {
+    let payload = vec![0; super::MAX_DECOMPRESSED_SIZE as usize + 1];
+    let compressed = super::compress(&payload).expect("could not compress payload");
+
+    assert!(compressed.len() < 1024 * 1024);
+    assert!(super::decompress(&compressed).is_err());
+  }
 }
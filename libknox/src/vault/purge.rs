@@ -0,0 +1,125 @@
+//! Remove deleted entries from the git history of a
+//! [Vault](struct.Vault.html).
+//!
+//! Deleting an entry removes its pack from the vault, but every previous
+//! commit still holds it, encrypted for the identities of the vault at the
+//! time. Purging the history rewrites those commits without the files that
+//! do not exist in the vault anymore.
+
+use std::error::Error;
+
+use super::context::VaultContext;
+use crate::util::git;
+
+/// The outcome of
+/// [VaultContext::purge_history](struct.VaultContext.html#method.purge_history).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Purge {
+  /// Paths of the files removed from the history.
+  pub files: Vec<String>,
+  /// Number of commits that were rewritten.
+  pub commits: usize,
+  /// Number of commits squashed into the new root commit.
+  pub squashed: usize,
+  /// Reference to the history before it was rewritten, if it was.
+  pub backup: Option<String>,
+}
+
+impl VaultContext {
+  /// Rewrite the git history of the vault without the files that were
+  /// deleted from it.
+  ///
+  /// Every commit of the current branch is rewritten without the packs and
+  /// blobs that do not exist in the last commit anymore. The history up to
+  /// the last commit of the current branch made before `before` can also be
+  /// squashed into a single root commit holding the vault as it was at that
+  /// commit.
+  ///
+  /// The metadata of previous commits is left as it was, so it still lists
+  /// the virtual paths of the purged entries, encrypted for the identities
+  /// of the vault at the time.
+  ///
+  /// The previous history is kept under a `refs/knox/backup/` reference,
+  /// which must be deleted, along with the other references to it, before
+  /// git can discard its objects. The remote repository still holds the
+  /// previous history until it is replaced with
+  /// [VaultContext::force_push](struct.VaultContext.html#method.force_push),
+  /// and every other copy of the vault has to be cloned again.
+  ///
+  /// # Arguments
+  ///
+  ///  * `before`  - squash the commits made before this UNIX timestamp.
+  ///  * `dry_run` - only report what would be purged, leaving the history untouched.
+  pub fn purge_history(&mut self, before: Option<u64>, dry_run: bool) -> Result<Purge, Box<dyn Error>> {
+    let result = self.lock_exclusive().and_then(|_| git::purge(self, before, dry_run));
+    self.unlock();
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use git2::{Repository, Sort};
+
+  use knox_testing::spec;
+
+  use crate::*;
+
+  fn history(repo: &Repository) -> Vec<git2::Oid> {
+    let mut revwalk = repo.revwalk().expect("could not get revwalk");
+    revwalk.push_head().expect("could not find HEAD");
+    revwalk.set_sorting(Sort::TOPOLOGICAL).expect("could not set sort");
+
+    revwalk.collect::<Result<Vec<git2::Oid>, git2::Error>>().expect("could not walk history")
+  }
+
+  #[test]
+  fn purge_history() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    context.write_entry("bar", &Entry::default()).expect("could not write entry");
+    context.commit("Created entries.").expect("could not commit changes");
+
+    let pack = context.vault.get_index()["foo"].clone();
+
+    context.delete_entry("foo").expect("could not delete entry");
+    context.commit("Deleted entry.").expect("could not commit changes");
+
+    let repo = Repository::open(tmp.path()).expect("could not open repository");
+    let (head, tree) = {
+      let commit = repo.head().and_then(|head| head.peel_to_commit()).expect("could not find HEAD");
+      (commit.id(), commit.tree_id())
+    };
+
+    let report = context.purge_history(None, true).expect("could not purge history");
+
+    assert_eq!(report.files, vec![pack.clone()]);
+    assert_eq!(report.backup, None);
+    assert_eq!(history(&repo)[0], head);
+
+    let report = context.purge_history(None, false).expect("could not purge history");
+    let backup = report.backup.expect("no backup reference was created");
+
+    assert_eq!(report.files, vec![pack.clone()]);
+    assert_eq!(report.commits, 2);
+    assert_eq!(repo.refname_to_id(&backup).expect("could not find backup reference"), head);
+
+    for id in history(&repo) {
+      let commit = repo.find_commit(id).expect("could not find commit");
+
+      assert!(commit.tree().expect("could not get tree").get_path(std::path::Path::new(&pack)).is_err());
+    }
+
+    assert_eq!(repo.head().and_then(|head| head.peel_to_commit()).expect("could not find HEAD").tree_id(), tree);
+    assert_eq!(context.purge_history(None, false).expect("could not purge history").backup, None);
+
+    let report = context.purge_history(Some(u64::MAX), false).expect("could not purge history");
+
+    assert_eq!(report.squashed, 3);
+    assert_eq!(history(&repo).len(), 1);
+    assert!(VaultContext::open(tmp.path()).expect("could not open vault").vault.get_index().contains_key("bar"));
+  }
+}
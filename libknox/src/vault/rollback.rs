@@ -0,0 +1,157 @@
+//! Protect the metadata of a [Vault](struct.Vault.html) against rollbacks.
+//!
+//! Every write of the metadata bumps its generation, and the highest
+//! generation seen on this machine is recorded in the local configuration
+//! of the vault. Metadata older than this high-water mark was replaced with
+//! a previous copy, which could revive revoked identities or deleted entries.
+//! Such metadata is reported with a warning by default, or refused with the
+//! `REFUSE_ROLLBACK` policy.
+
+use std::cmp;
+use std::error::Error;
+use std::path::Path;
+
+use log::*;
+
+use super::context::VaultContext;
+use super::local;
+use crate::pb::*;
+use crate::util::{LockMode, VaultError};
+
+impl VaultContext {
+  /// Get the highest generation of the metadata seen on this machine.
+  pub fn get_generation_mark(&self) -> u64 {
+    self.local.borrow().get_generation()
+  }
+
+  /// Accept the current metadata of a vault after an intentional rollback.
+  ///
+  /// Opens the vault without checking for rollbacks, and lowers the
+  /// high-water mark of this machine to the generation of its metadata.
+  /// Returns that generation.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - filesystem path where the vault is located.
+  pub fn accept_rollback<P>(path: P) -> Result<u64, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let context = Self::open_with_lock(path, LockMode::Exclusive, false)?;
+    let generation = context.vault.get_generation();
+
+    context.record_generation(generation)?;
+
+    Ok(generation)
+  }
+
+  /// Compare the generation of the metadata with the high-water mark.
+  ///
+  /// Newer metadata raises the mark, but only while the vault is locked
+  /// exclusively: a handle holding a shared lock could otherwise record the
+  /// generation of metadata replaced in the meantime.
+  pub(crate) fn check_rollback(&self) -> Result<(), Box<dyn Error>> {
+    let generation = self.vault.get_generation();
+    let mark = self.get_generation_mark();
+
+    if generation > mark {
+      if !self.is_locked() {
+        return Ok(());
+      }
+
+      if let Err(err) = self.record_generation(generation) {
+        debug!("could not record the generation of the metadata: {}", err);
+      }
+
+      return Ok(());
+    }
+
+    if generation == mark {
+      return Ok(());
+    }
+
+    let message = format!(
+      "the vault metadata (generation {}) is older than the latest version seen on this machine (generation {}), it may have been rolled back",
+      generation, mark
+    );
+
+    match self.local.borrow().get_rollback_policy() {
+      RollbackPolicy::WARN_ROLLBACK => {
+        warn!("{}", message);
+        Ok(())
+      }
+
+      RollbackPolicy::REFUSE_ROLLBACK => Err(VaultError::throw(&message)),
+    }
+  }
+
+  pub(crate) fn next_generation(&self) -> u64 {
+    cmp::max(self.vault.get_generation(), self.get_generation_mark()) + 1
+  }
+
+  /// Persist the high-water mark, keeping the rest of the local
+  /// configuration as currently stored, since it could have been changed by
+  /// another handle. This must be called while holding the exclusive lock.
+  pub(crate) fn record_generation(&self, generation: u64) -> Result<(), Box<dyn Error>> {
+    let mut local = local::read_local_config(&self.path)?;
+    local.set_generation(generation);
+
+    self.local.replace(local);
+    self.write_local()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn generations() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.get_generation(), 2);
+    assert_eq!(retrieved.get_generation_mark(), 2);
+  }
+
+  #[test]
+  fn rollback() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+    let metadata = tmp.path().join(util::METADATA_FILE);
+    let previous = fs::read(&metadata).expect("could not read metadata");
+    let mut config = context.get_local_config();
+    config.set_rollback_policy(RollbackPolicy::REFUSE_ROLLBACK);
+
+    context.write_local_config(config).expect("could not write local config");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+    drop(context);
+    fs::write(&metadata, &previous).expect("could not write metadata");
+
+    assert!(VaultContext::open(tmp.path()).is_err());
+    assert_eq!(VaultContext::accept_rollback(tmp.path()).expect("could not accept rollback"), 1);
+
+    let mut context = VaultContext::open(tmp.path()).expect("could not open vault");
+    config = context.get_local_config();
+    config.set_rollback_policy(RollbackPolicy::WARN_ROLLBACK);
+
+    context.write_local_config(config).expect("could not write local config");
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+    drop(context);
+
+    assert_eq!(VaultContext::open(tmp.path()).expect("could not open vault").vault.get_generation(), 2);
+
+    fs::write(&metadata, &previous).expect("could not write metadata");
+
+    assert!(VaultContext::open(tmp.path()).is_ok());
+  }
+}
@@ -0,0 +1,363 @@
+//! Synchronize a [Vault](struct.Vault.html) with its git remote.
+
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, read_dir};
+use std::path::Path;
+
+use super::context::VaultContext;
+use super::local;
+use super::merge::Conflict;
+use crate::backend;
+use crate::pb::*;
+use crate::util::{self, git, VaultError};
+
+/// A change made upstream to an entry, reported by
+/// [VaultContext::pull](struct.VaultContext.html#method.pull).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+  /// An entry was created.
+  Added { path: String },
+  /// An entry was modified.
+  Modified { path: String },
+  /// An entry was deleted.
+  Deleted { path: String },
+}
+
+/// Which version of an entry modified on both sides is kept by
+/// [VaultContext::pull](struct.VaultContext.html#method.pull).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+  /// Keep the local version of the entry.
+  Ours,
+  /// Keep the version of the entry from the remote repository.
+  Theirs,
+}
+
+impl Change {
+  /// The virtual path of the entry that changed.
+  pub fn path(&self) -> &str {
+    match self {
+      Change::Added { path } | Change::Modified { path } | Change::Deleted { path } => path,
+    }
+  }
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self {
+      Change::Added { path } => write!(f, "{} was added", path),
+      Change::Modified { path } => write!(f, "{} was modified", path),
+      Change::Deleted { path } => write!(f, "{} was deleted", path),
+    }
+  }
+}
+
+/// Whether a vault cloned with
+/// [VaultContext::clone_remote](struct.VaultContext.html#method.clone_remote)
+/// can be decrypted on this machine.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Access {
+  /// The vault can be decrypted, with the local identity if it could be
+  /// determined.
+  Recipient { identity: Option<String> },
+  /// The vault cannot be decrypted. One of the local identities should be
+  /// added to the vault by one of its members.
+  NotRecipient { identities: Vec<String> },
+}
+
+impl VaultContext {
+  /// Clone a vault from a remote git repository.
+  ///
+  /// The repository is cloned to `path`, which must not exist or be empty,
+  /// and the git settings are stored in the local configuration of the vault,
+  /// to be used by [VaultContext::push](struct.VaultContext.html#method.push)
+  /// and [VaultContext::pull](struct.VaultContext.html#method.pull). Tokens
+  /// stored in an entry cannot be used to clone a vault.
+  ///
+  /// The vault is then checked against the local keys, to tell whether it
+  /// can be decrypted on this machine.
+  ///
+  /// # Arguments
+  ///
+  ///  * `url`    - the URL of the remote git repository.
+  ///  * `path`   - the filesystem path at which to clone the vault.
+  ///  * `config` - the remote, branch and credentials to use.
+  pub fn clone_remote<P>(url: &str, path: P, config: GitConfig) -> Result<Access, Box<dyn Error>>
+  where
+    P: AsRef<Path>,
+  {
+    let path = path.as_ref();
+
+    if path.exists() && read_dir(path)?.count() > 0 {
+      return Err(VaultError::throw(&format!("a non-empty directory already exists at {}, refusing to overwrite", path.display())));
+    }
+
+    git::clone(url, path, &config)?;
+
+    let metadata = path.join(util::METADATA_FILE);
+
+    if !metadata.exists() {
+      return Err(VaultError::throw(&format!("the repository at {} does not contain a vault", url)));
+    }
+
+    let mut settings = LocalConfig::default();
+    settings.set_git(config);
+    local::write_local_config(path, &settings)?;
+
+    let ciphertext = fs::read(&metadata)?;
+    let backend = backend::open(backend::detect(&ciphertext), path)?;
+
+    if backend.kind() != BackendKind::PASSPHRASE && backend.decrypt(&ciphertext).is_err() {
+      return Ok(Access::NotRecipient {
+        identities: backend.local_identities(),
+      });
+    }
+
+    let context = Self::open(path)?;
+
+    Ok(Access::Recipient {
+      identity: context.backend().get_author(context.vault.get_identities()),
+    })
+  }
+
+  /// Fetch and merge the modifications made to the remote git repository.
+  ///
+  /// When both the local and remote repositories were modified, their
+  /// metadata is merged with
+  /// [VaultContext::merge_metadata](struct.VaultContext.html#method.merge_metadata),
+  /// and the result is recorded as a merge commit. Conflicting changes to
+  /// the metadata keep their local version. Entries modified on both sides
+  /// keep the version chosen by `resolution`, along with their tags and
+  /// expiration. Conflicts are returned for review, along with the entries
+  /// changed upstream.
+  ///
+  /// The merged metadata is then read again, and goes through the same
+  /// verifications as when the vault is opened. The remote repository is
+  /// authenticated the same way as in
+  /// [VaultContext::push](struct.VaultContext.html#method.push). Pulling is
+  /// refused while the vault has uncommitted modifications.
+  ///
+  /// # Arguments
+  ///
+  ///  * `resolution` - the version kept for entries modified on both sides.
+  pub fn pull(&mut self, resolution: Resolution) -> Result<(Vec<Change>, Vec<Conflict>), Box<dyn Error>> {
+    let result = self.pull_locked(resolution);
+    self.unlock();
+    result
+  }
+
+  /// Pull the modifications made to the remote git repository, and push the
+  /// local ones.
+  ///
+  /// Refer to [VaultContext::pull](struct.VaultContext.html#method.pull) for
+  /// how modifications made on both sides are merged.
+  ///
+  /// # Arguments
+  ///
+  ///  * `resolution` - the version kept for entries modified on both sides.
+  pub fn sync(&mut self, resolution: Resolution) -> Result<(Vec<Change>, Vec<Conflict>), Box<dyn Error>> {
+    let result = self.pull(resolution)?;
+
+    self.push()?;
+
+    Ok(result)
+  }
+
+  fn pull_locked(&mut self, resolution: Resolution) -> Result<(Vec<Change>, Vec<Conflict>), Box<dyn Error>> {
+    self.lock_exclusive()?;
+
+    let previous = git::packs(self, git::head(self)?, self.vault.get_index())?;
+    let conflicts = git::pull(self, resolution)?;
+
+    self.reload()?;
+    self.reindex(&conflicts)?;
+
+    let current = git::packs(self, git::head(self)?, self.vault.get_index())?;
+
+    Ok((changes(&previous, &current), conflicts))
+  }
+}
+
+impl VaultContext {
+  // The tags and expiration of an entry modified on both sides are merged
+  // with the rest of the metadata, regardless of the version of its pack.
+  fn reindex(&mut self, conflicts: &[Conflict]) -> Result<(), Box<dyn Error>> {
+    let (tags, expirations) = (self.vault.get_tags().clone(), self.vault.get_expirations().clone());
+
+    for conflict in conflicts {
+      if let Conflict::Pack { path, deleted: false } = conflict {
+        if self.vault.get_index().contains_key(path) {
+          let entry = self.read_entry(path)?;
+
+          self.vault.index_tags(path, entry.get_tags());
+          self.vault.index_expiry(path, entry.earliest_expiry());
+        }
+      }
+    }
+
+    if &tags == self.vault.get_tags() && &expirations == self.vault.get_expirations() {
+      return Ok(());
+    }
+
+    self.write_metadata()?;
+
+    git::commit(self, "Indexed merged entries.")
+  }
+}
+
+/// List the entries that changed between two versions of the vault, given
+/// the blobs of their packs.
+pub(crate) fn changes<T>(previous: &HashMap<String, T>, current: &HashMap<String, T>) -> Vec<Change>
+where
+  T: PartialEq,
+{
+  let paths: BTreeSet<&String> = previous.keys().chain(current.keys()).collect();
+
+  paths
+    .into_iter()
+    .filter_map(|path| match (previous.get(path), current.get(path)) {
+      (None, Some(_)) => Some(Change::Added { path: path.clone() }),
+      (Some(_), None) => Some(Change::Deleted { path: path.clone() }),
+      (Some(before), Some(after)) if before != after => Some(Change::Modified { path: path.clone() }),
+      _ => None,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use git2::Repository;
+
+  use knox_testing::spec;
+
+  use super::{Access, Change};
+  use crate::*;
+
+  #[test]
+  fn pull() {
+    let (upstream, local) = (spec::setup(), spec::setup());
+    let mut context = crate::spec::get_test_vault(upstream.path()).expect("could not get vault");
+
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    context.write_entry("qux", &Entry::default()).expect("could not write entry");
+    context.git_init().expect("could not create local git repository");
+
+    Repository::clone(upstream.path().to_str().unwrap(), local.path()).expect("could not clone vault");
+
+    context.write_entry("bar", &Entry::default()).expect("could not write entry");
+    context.delete_entry("foo").expect("could not delete entry");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("foo", "bar");
+    context.write_entry("qux", &entry).expect("could not write entry");
+    context.commit("upstream").expect("could not commit changes");
+
+    let mut clone = VaultContext::open(local.path()).expect("could not open vault");
+    clone.write_entry("baz", &Entry::default()).expect("could not write entry");
+    clone.commit("local").expect("could not commit changes");
+
+    let (changes, conflicts) = clone.pull(Resolution::Ours).expect("could not pull changes");
+
+    assert!(conflicts.is_empty());
+    assert_eq!(
+      changes,
+      vec![
+        Change::Added { path: "bar".to_string() },
+        Change::Deleted { path: "foo".to_string() },
+        Change::Modified { path: "qux".to_string() },
+      ]
+    );
+
+    let clone = VaultContext::open(local.path()).expect("could not open vault");
+
+    assert!(clone.vault.get_index().contains_key("bar"));
+    assert!(clone.vault.get_index().contains_key("baz"));
+    assert!(!clone.vault.get_index().contains_key("foo"));
+  }
+
+  #[test]
+  fn pull_conflict() {
+    let (upstream, local) = (spec::setup(), spec::setup());
+    let mut context = crate::spec::get_test_vault(upstream.path()).expect("could not get vault");
+
+    context.write_entry("foo", &Entry::default()).expect("could not write entry");
+    context.git_init().expect("could not create local git repository");
+
+    Repository::clone(upstream.path().to_str().unwrap(), local.path()).expect("could not clone vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("side", "upstream");
+    context.write_entry("foo", &entry).expect("could not write entry");
+    context.commit("upstream").expect("could not commit changes");
+
+    let mut clone = VaultContext::open(local.path()).expect("could not open vault");
+    let mut entry = Entry::default();
+    entry.add_attribute("side", "local");
+    entry.mut_tags().push("local".to_string());
+    clone.write_entry("foo", &entry).expect("could not write entry");
+
+    assert!(clone.pull(Resolution::Theirs).is_err());
+
+    clone.commit("local").expect("could not commit changes");
+
+    let (_, conflicts) = clone.pull(Resolution::Theirs).expect("could not pull changes");
+
+    assert_eq!(
+      conflicts,
+      vec![Conflict::Pack {
+        path: "foo".to_string(),
+        deleted: false,
+      }]
+    );
+
+    let clone = VaultContext::open(local.path()).expect("could not open vault");
+    let entry = clone.read_entry("foo").expect("could not read entry");
+
+    assert_eq!(entry.get_attributes().get("side").map(Attribute::get_value), Some("upstream"));
+    assert!(clone.vault.get_tags().is_empty());
+  }
+
+  #[test]
+  fn clone_remote() {
+    let (upstream, local) = (spec::setup(), spec::setup());
+    let context = crate::spec::get_test_vault(upstream.path()).expect("could not get vault");
+    context.git_init().expect("could not create local git repository");
+
+    let mut config = GitConfig::default();
+    config.set_remote("upstream".to_string());
+
+    let access = VaultContext::clone_remote(upstream.path().to_str().unwrap(), local.path(), config).expect("could not clone vault");
+
+    assert!(match access {
+      Access::Recipient { identity } => identity.is_some(),
+      Access::NotRecipient { .. } => false,
+    });
+
+    let clone = VaultContext::open(local.path()).expect("could not open vault");
+
+    assert_eq!(clone.get_local_config().get_git().get_remote(), "upstream");
+    assert!(Repository::open(local.path()).expect("could not open repository").find_remote("upstream").is_ok());
+    assert!(VaultContext::clone_remote(upstream.path().to_str().unwrap(), local.path(), GitConfig::default()).is_err());
+  }
+
+  #[test]
+  fn clone_remote_not_recipient() {
+    let (upstream, local) = (spec::setup_age(), spec::setup_age());
+
+    let recipient = ::age::x25519::Identity::generate().to_public().to_string();
+    let mut context = VaultContext::create_with_backend(upstream.path(), BackendKind::AGE, &[recipient]).expect("could not create vault");
+    context.write().expect("could not write vault");
+    context.git_init().expect("could not create local git repository");
+
+    let access = VaultContext::clone_remote(upstream.path().to_str().unwrap(), local.path(), GitConfig::default()).expect("could not clone vault");
+
+    assert_eq!(
+      access,
+      Access::NotRecipient {
+        identities: vec![spec::AGE_RECIPIENT.to_string()]
+      }
+    );
+  }
+}
@@ -0,0 +1,151 @@
+//! Organize [Entries](struct.Entry.html) across the hierarchy of paths.
+//!
+//! Tags are stored inside each encrypted [Entry](struct.Entry.html), and
+//! mirrored in the metadata of the [Vault](struct.Vault.html) so that entries
+//! can be queried by tag without being decrypted.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+use crate::pb::*;
+use crate::util::VaultError;
+
+impl Entry {
+  /// Add a tag to an `Entry`.
+  ///
+  /// Tags cannot be empty or contain whitespace or commas. Adding a tag
+  /// twice has no effect.
+  pub fn add_tag(&mut self, tag: &str) -> Result<(), Box<dyn Error>> {
+    if tag.is_empty() || tag.contains(|c: char| c.is_whitespace() || c == ',') {
+      return Err(VaultError::throw(&format!("invalid tag '{}', tags cannot be empty or contain whitespace or commas", tag)));
+    }
+
+    if !self.has_tag(tag) {
+      self.mut_tags().push(tag.to_string());
+      self.mut_tags().sort();
+    }
+
+    Ok(())
+  }
+
+  /// Remove a tag from an `Entry`.
+  pub fn remove_tag(&mut self, tag: &str) {
+    let tags = self.get_tags().iter().filter(|other| *other != tag).cloned().collect::<Vec<String>>();
+
+    self.set_tags(protobuf::RepeatedField::from_vec(tags));
+  }
+
+  /// Check if an `Entry` carries a tag.
+  pub fn has_tag(&self, tag: &str) -> bool {
+    self.get_tags().iter().any(|other| other == tag)
+  }
+}
+
+impl Vault {
+  /// Retrieve the tags of an entry from the metadata.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  pub fn get_entry_tags(&self, path: &str) -> &[String] {
+    match self.get_tags().get(path) {
+      Some(tags) => tags.get_tags(),
+      None => &[],
+    }
+  }
+
+  /// Check if an entry carries all the provided tags.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  ///  * `tags` - the tags to look for.
+  pub fn is_tagged(&self, path: &str, tags: &[&str]) -> bool {
+    let entry_tags = self.get_entry_tags(path);
+
+    tags.iter().all(|tag| entry_tags.iter().any(|other| other == tag))
+  }
+
+  /// Count the entries carrying each tag used in the vault.
+  pub fn count_tags(&self) -> BTreeMap<&str, usize> {
+    let mut counts = BTreeMap::new();
+
+    for tags in self.get_tags().values() {
+      for tag in tags.get_tags() {
+        *counts.entry(tag.as_str()).or_insert(0) += 1;
+      }
+    }
+
+    counts
+  }
+
+  pub(crate) fn index_tags(&mut self, path: &str, tags: &[String]) {
+    if tags.is_empty() {
+      self.mut_tags().remove(path);
+    } else {
+      self.mut_tags().insert(
+        path.to_string(),
+        Tags {
+          tags: protobuf::RepeatedField::from_slice(tags),
+          ..Tags::default()
+        },
+      );
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn add_and_remove() {
+    let mut entry = Entry::default();
+
+    entry.add_tag("prod").expect("could not add tag");
+    entry.add_tag("ops").expect("could not add tag");
+    entry.add_tag("prod").expect("could not add tag");
+
+    assert_eq!(entry.get_tags(), &["ops".to_string(), "prod".to_string()]);
+    assert!(entry.add_tag("").is_err());
+    assert!(entry.add_tag("shared with ops").is_err());
+
+    entry.remove_tag("prod");
+
+    assert!(!entry.has_tag("prod"));
+    assert!(entry.has_tag("ops"));
+  }
+
+  #[test]
+  fn index() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_tag("prod").expect("could not add tag");
+    entry.add_tag("ops").expect("could not add tag");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+    context.write_entry("lorem/ipsum", &entry).expect("could not write entry");
+    context.rename_entry("lorem/ipsum", "dolor/sit").expect("could not rename entry");
+
+    entry.remove_tag("ops");
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert!(retrieved.vault.is_tagged("foo/bar", &["prod"]));
+    assert!(!retrieved.vault.is_tagged("foo/bar", &["prod", "ops"]));
+    assert!(retrieved.vault.is_tagged("dolor/sit", &["prod", "ops"]));
+    assert!(retrieved.vault.get_entry_tags("lorem/ipsum").is_empty());
+    assert_eq!(retrieved.vault.count_tags().get("prod"), Some(&2));
+
+    context.delete_entry("dolor/sit").expect("could not delete entry");
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.count_tags().get("ops"), None);
+  }
+}
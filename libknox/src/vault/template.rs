@@ -0,0 +1,272 @@
+//! Describe common shapes of [Entries](struct.Entry.html).
+//!
+//! A [Template](struct.Template.html) lists the attributes an entry is
+//! expected to hold, along with their kind and whether they are optional,
+//! confidential, or should be generated. Built-in templates are provided for
+//! the most common credentials, and user-defined templates are stored in the
+//! metadata of the [Vault](struct.Vault.html).
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use super::context::VaultContext;
+use crate::pb::*;
+use crate::util::VaultError;
+
+const BUILTIN_TEMPLATES: &[(&str, &str, &[&str])] = &[
+  (
+    "login",
+    "Website or application login",
+    &["url:url,optional", "username:username", "password:password,generate", "note:note,optional"],
+  ),
+  (
+    "database",
+    "Database credentials",
+    &["host", "port:generic,optional", "database", "username:username", "password:password,generate", "dsn:url,optional"],
+  ),
+  ("aws", "AWS access key pair", &["access_key_id", "secret_access_key:password", "region:generic,optional"]),
+  (
+    "ssh",
+    "SSH key pair",
+    &["private_key:ssh-key,confidential", "public_key:ssh-key,optional", "passphrase:password,optional"],
+  ),
+  ("card", "Payment card", &["number:card-number,confidential", "holder", "expiry", "cvv:pin", "pin:pin,optional"]),
+];
+
+impl TemplateField {
+  /// Parse the description of a template field.
+  ///
+  /// Fields are described as `name[:kind[,modifier...]]`, where `kind` is the
+  /// name of an [AttributeKind](enum.AttributeKind.html), and modifiers can
+  /// be `optional`, `confidential` or `generate`. For instance,
+  /// `password:password,generate` or `note:note,optional`.
+  pub fn parse(spec: &str) -> Result<Self, Box<dyn Error>> {
+    let mut parts = spec.splitn(2, ':');
+    let name = parts.next().unwrap_or_default();
+
+    if name.is_empty() {
+      return Err(VaultError::throw(&format!("invalid template field '{}', the name cannot be empty", spec)));
+    }
+
+    let mut field = TemplateField {
+      name: name.to_string(),
+      ..TemplateField::default()
+    };
+
+    if let Some(rest) = parts.next() {
+      let mut modifiers = rest.split(',');
+
+      field.kind = AttributeKind::parse(modifiers.next().unwrap_or_default())?;
+
+      for modifier in modifiers {
+        match modifier {
+          "optional" => field.optional = true,
+          "confidential" => field.confidential = true,
+          "generate" => field.generate = true,
+          _ => return Err(VaultError::throw(&format!("unknown template field modifier '{}'", modifier))),
+        }
+      }
+    }
+
+    if let AttributeKind::PASSWORD | AttributeKind::PIN = field.kind {
+      field.confidential = true;
+    }
+
+    if field.generate && !field.confidential {
+      return Err(VaultError::throw(&format!("generated field {} must be confidential", field.name)));
+    }
+
+    Ok(field)
+  }
+}
+
+impl fmt::Display for TemplateField {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}:{}", self.name, self.kind.name())?;
+
+    for (enabled, modifier) in &[(self.optional, "optional"), (self.confidential, "confidential"), (self.generate, "generate")] {
+      if *enabled {
+        write!(f, ",{}", modifier)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl Template {
+  /// Build a template from field descriptions.
+  ///
+  /// See [TemplateField::parse](struct.TemplateField.html#method.parse) for
+  /// the syntax of each field.
+  pub fn parse(description: &str, fields: &[&str]) -> Result<Self, Box<dyn Error>> {
+    let fields = fields.iter().map(|field| TemplateField::parse(field)).collect::<Result<Vec<TemplateField>, Box<dyn Error>>>()?;
+
+    if fields.is_empty() {
+      return Err(VaultError::throw("a template needs at least one field"));
+    }
+
+    Ok(Template {
+      description: description.to_string(),
+      fields: protobuf::RepeatedField::from_vec(fields),
+      ..Template::default()
+    })
+  }
+
+  /// Retrieve a built-in template by name.
+  pub fn builtin(name: &str) -> Option<Self> {
+    BUILTIN_TEMPLATES
+      .iter()
+      .find(|(builtin, _, _)| *builtin == name)
+      .and_then(|(_, description, fields)| Template::parse(description, fields).ok())
+  }
+
+  /// Check that an [Entry](struct.Entry.html) matches the template.
+  ///
+  /// All required fields must be present, and all fields of the template
+  /// must hold valid values for their kind.
+  pub fn check(&self, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    for field in self.get_fields() {
+      match entry.get_attributes().get(field.get_name()) {
+        Some(attribute) => {
+          let attribute = Attribute {
+            kind: field.get_kind(),
+            ..attribute.clone()
+          };
+
+          attribute
+            .validate()
+            .map_err(|err| VaultError::throw(&format!("invalid value for attribute {}: {}", field.get_name(), err)))?;
+        }
+
+        None if !field.get_optional() => return Err(VaultError::throw(&format!("missing required attribute {}", field.get_name()))),
+        None => {}
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl VaultContext {
+  /// Retrieve a template by name.
+  ///
+  /// Templates stored in the vault take precedence over built-in templates
+  /// with the same name.
+  pub fn get_template(&self, name: &str) -> Option<Template> {
+    match self.vault.get_templates().get(name) {
+      Some(template) => Some(template.clone()),
+      None => Template::builtin(name),
+    }
+  }
+
+  /// List all built-in and user-defined templates.
+  pub fn list_templates(&self) -> BTreeMap<String, Template> {
+    let mut templates = BUILTIN_TEMPLATES
+      .iter()
+      .filter_map(|(name, _, _)| Template::builtin(name).map(|template| (name.to_string(), template)))
+      .collect::<BTreeMap<String, Template>>();
+
+    templates.extend(self.vault.get_templates().iter().map(|(name, template)| (name.clone(), template.clone())));
+    templates
+  }
+
+  /// Add a template to the vault.
+  ///
+  /// To persist the change, refer to
+  /// [VaultContext::write](struct.VaultContext.html#method.write).
+  ///
+  /// # Arguments
+  ///
+  ///  * `name`     - the name of the template.
+  ///  * `template` - the template to be stored.
+  pub fn add_template(&mut self, name: &str, template: Template) {
+    self.vault.mut_templates().insert(name.to_string(), template);
+  }
+
+  /// Remove a template from the vault.
+  ///
+  /// Built-in templates cannot be removed. To persist the change, refer to
+  /// [VaultContext::write](struct.VaultContext.html#method.write).
+  ///
+  /// # Arguments
+  ///
+  ///  * `name` - the name of the template.
+  pub fn remove_template(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+    match self.vault.mut_templates().remove(name) {
+      Some(_) => Ok(()),
+      None => Err(VaultError::throw(&format!("no template named {} is stored in the vault", name))),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn parse() {
+    let field = TemplateField::parse("password:password,generate").expect("could not parse field");
+
+    assert_eq!(field.get_kind(), AttributeKind::PASSWORD);
+    assert!(field.get_confidential());
+    assert!(field.get_generate());
+    assert!(!field.get_optional());
+    assert_eq!(field.to_string(), "password:password,confidential,generate");
+
+    assert_eq!(TemplateField::parse("host").expect("could not parse field").get_kind(), AttributeKind::GENERIC);
+    assert!(TemplateField::parse("host:generic,required").is_err());
+    assert!(TemplateField::parse("host:generic,generate").is_err());
+    assert!(TemplateField::parse(":url").is_err());
+  }
+
+  #[test]
+  fn builtin() {
+    for name in &["login", "database", "aws", "ssh", "card"] {
+      assert!(Template::builtin(name).is_some());
+    }
+
+    assert!(Template::builtin("unknown").is_none());
+  }
+
+  #[test]
+  fn check() {
+    let template = Template::builtin("card").expect("could not get template");
+    let mut entry = Entry::default();
+
+    entry.add_attribute("number", "4111 1111 1111 1111");
+    entry.add_attribute("holder", "John Doe");
+    entry.add_attribute("expiry", "12/30");
+
+    assert!(template.check(&entry).is_err());
+
+    entry.add_attribute("cvv", "123");
+
+    assert!(template.check(&entry).is_ok());
+
+    entry.add_attribute("number", "4111 1111 1111 1112");
+
+    assert!(template.check(&entry).is_err());
+  }
+
+  #[test]
+  fn store() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let template = Template::parse("Wi-Fi network", &["ssid", "password:password"]).expect("could not parse template");
+
+    context.add_template("wifi", template.clone());
+    context.write().expect("could not write metadata");
+
+    let mut retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.get_template("wifi"), Some(template));
+    assert!(retrieved.list_templates().contains_key("login"));
+    assert!(retrieved.remove_template("wifi").is_ok());
+    assert!(retrieved.remove_template("login").is_err());
+  }
+}
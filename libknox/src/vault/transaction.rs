@@ -0,0 +1,428 @@
+//! Batch modifications to a [Vault](struct.Vault.html).
+
+use std::error::Error;
+use std::io::Read;
+use std::ops::Deref;
+
+use super::context::VaultContext;
+use crate::pb::*;
+use crate::util::{self, git, VaultError};
+
+/// A batch of modifications to a vault.
+///
+/// Entries written through a transaction are encrypted and persisted
+/// immediately, but the metadata is only encrypted and written once, when the
+/// transaction is flushed or committed. Packs of deleted entries, and blobs
+/// that are not referenced anymore, are only removed after the metadata
+/// stopped referencing them.
+///
+/// The vault is locked exclusively from the first modification until the
+/// transaction is dropped. A transaction dropped without being flushed leaves
+/// the in-memory index of its [VaultContext](struct.VaultContext.html)
+/// modified, but the metadata on disk untouched, and removes the blobs it
+/// wrote.
+pub struct Transaction<'a> {
+  context: &'a mut VaultContext,
+  deleted: Vec<String>,
+  discarded: Vec<String>,
+  written: Vec<String>,
+  applied: bool,
+}
+
+impl<'a> Transaction<'a> {
+  pub(crate) fn new(context: &'a mut VaultContext) -> Self {
+    Self {
+      context,
+      deleted: Vec::new(),
+      discarded: Vec::new(),
+      written: Vec::new(),
+      applied: false,
+    }
+  }
+
+  /// Persist an [Entry](struct.Entry.html) as part of the transaction.
+  ///
+  /// The entry is written to its physical location right away, and added to
+  /// the in-memory index along with its tags. Its modification time and
+  /// author are updated, as well as its creation time if it was never written
+  /// before. If the entry already exists, the attributes that changed are
+  /// added to their history, which fails if the current version of the entry
+  /// cannot be read.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`  - the virtual path to the entry.
+  ///  * `entry` - the `Entry` to be written.
+  pub fn write_entry(&mut self, path: &str, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    let previous = if self.context.vault.get_index().contains_key(path) {
+      Some(self.context.read_entry(path)?)
+    } else {
+      None
+    };
+
+    self.stamp_and_write(path, previous.as_ref(), entry)
+  }
+
+  /// Persist a modified [Entry](struct.Entry.html) as part of the
+  /// transaction.
+  ///
+  /// Behaves like [Transaction::write_entry](struct.Transaction.html#method.write_entry),
+  /// but compares the entry to `previous`, the version the caller read before
+  /// modifying it, instead of decrypting the current version again.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path`     - the virtual path to the entry.
+  ///  * `previous` - the `Entry` as it was read from the vault.
+  ///  * `entry`    - the `Entry` to be written.
+  pub fn update_entry(&mut self, path: &str, previous: &Entry, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    self.stamp_and_write(path, Some(previous), entry)
+  }
+
+  fn stamp_and_write(&mut self, path: &str, previous: Option<&Entry>, entry: &Entry) -> Result<(), Box<dyn Error>> {
+    let hash = util::hash_path(self.context.vault.get_index().get(path));
+    let mut entry = entry.clone();
+
+    entry.stamp(self.context.backend().get_author(self.context.vault.get_identities()), previous);
+
+    if let Some(previous) = previous {
+      let blobs = entry.get_blobs();
+
+      self.discarded.extend(previous.get_blobs().difference(&blobs).map(ToString::to_string));
+    }
+
+    self.context.write_pack(&hash, &entry)?;
+    self.context.add_index(path, &hash);
+    self.context.vault.index_tags(path, entry.get_tags());
+    self.context.vault.index_expiry(path, entry.earliest_expiry());
+
+    Ok(())
+  }
+
+  /// Encrypt a stream into a new blob as part of the transaction.
+  ///
+  /// Behaves like [VaultContext::write_blob](struct.VaultContext.html#method.write_blob),
+  /// but the blob is removed if the transaction is dropped before its
+  /// metadata is written.
+  ///
+  /// # Arguments
+  ///
+  ///  * `reader` - the stream providing the content of the blob.
+  pub fn write_blob<R>(&mut self, reader: R) -> Result<Attribute, Box<dyn Error>>
+  where
+    R: Read + Send,
+  {
+    let attribute = self.context.write_blob(reader)?;
+    self.written.push(attribute.get_blob().to_string());
+
+    Ok(attribute)
+  }
+
+  /// Encrypt an existing [Entry](struct.Entry.html) again for the current
+  /// identities of the vault.
+  ///
+  /// The content of the entry, including its timestamps and author, is left
+  /// untouched. The blobs referenced by the entry are encrypted again into new
+  /// blobs, and the previous ones are removed when the transaction is
+  /// flushed.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  pub fn reencrypt_entry(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut entry = self.context.read_entry(path)?;
+    let hash = util::hash_path(self.context.vault.get_index().get(path));
+    let written = self.written.len();
+    let blobs = entry.get_blob_digests();
+
+    for (blob, digest) in &blobs {
+      let (replacement, digest) = self.context.reencrypt_blob(blob, digest)?;
+
+      self.written.push(replacement.clone());
+      entry.replace_blob(blob, &replacement, &digest);
+    }
+
+    self.context.write_pack(&hash, &entry)?;
+
+    // The pack now references the new blobs, which must be kept even if the
+    // transaction is not flushed.
+    self.written.truncate(written);
+    self.discarded.extend(blobs.into_keys());
+
+    Ok(())
+  }
+
+  /// Write the pack of an existing [Entry](struct.Entry.html) again, in the
+  /// current storage format, leaving its blobs untouched. The digests of
+  /// blobs written before they were recorded are computed as well.
+  pub(crate) fn rewrite_entry(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+    let mut entry = self.context.read_entry(path)?;
+    let hash = util::hash_path(self.context.vault.get_index().get(path));
+
+    for (blob, digest) in entry.get_blob_digests() {
+      if digest.is_empty() {
+        if let Some(digest) = self.context.digest_blob(&blob)? {
+          entry.set_blob_digest(&blob, &digest);
+        }
+      }
+    }
+
+    self.context.write_pack(&hash, &entry)
+  }
+
+  /// Delete an [Entry](struct.Entry.html) as part of the transaction.
+  ///
+  /// The entry is removed from the in-memory index, and its pack will be
+  /// removed when the transaction is flushed. If the entry can be decrypted,
+  /// its blobs are removed as well, otherwise they are left for
+  /// [VaultContext::repair](struct.VaultContext.html#method.repair) to clean
+  /// up.
+  ///
+  /// # Arguments
+  ///
+  ///  * `path` - the virtual path to the entry.
+  pub fn delete_entry(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+    match self.context.vault.get_index().get(path) {
+      Some(salt) => {
+        let hash = util::hash_path(Some(salt));
+
+        if let Ok(entry) = self.context.read_entry(path) {
+          self.discarded.extend(entry.get_blobs().into_iter().map(ToString::to_string));
+        }
+
+        self.context.remove_index(path);
+        self.deleted.push(hash);
+
+        Ok(())
+      }
+
+      None => Err(VaultError::throw("requested entry does not exist in the vault")),
+    }
+  }
+
+  /// Move an [Entry](struct.Entry.html) to another virtual path as part of
+  /// the transaction.
+  ///
+  /// The pack of the entry is left untouched, only the index, its tags and
+  /// its expiration date are updated.
+  ///
+  /// # Arguments
+  ///
+  ///  * `source`      - the current virtual path to the entry.
+  ///  * `destination` - the new virtual path to the entry.
+  pub fn rename_entry(&mut self, source: &str, destination: &str) -> Result<(), Box<dyn Error>> {
+    if self.context.vault.get_index().contains_key(destination) {
+      return Err(VaultError::throw("an entry already exists at this destination"));
+    }
+
+    let hash = match self.context.vault.get_index().get(source) {
+      Some(hash) => hash.clone(),
+      None => return Err(VaultError::throw("no entry was found at this path")),
+    };
+    let tags = self.context.vault.get_entry_tags(source).to_vec();
+    let expires = self.context.vault.get_entry_expiry(source);
+
+    self.context.add_index(destination, &hash);
+    self.context.remove_index(source);
+    self.context.vault.index_tags(destination, &tags);
+    self.context.vault.index_expiry(destination, expires);
+
+    Ok(())
+  }
+
+  /// Add an [Entry](struct.Entry.html) to the index.
+  ///
+  /// See [VaultContext::add_index](struct.VaultContext.html#method.add_index).
+  pub fn add_index(&mut self, path: &str, destination: &str) {
+    self.context.add_index(path, destination);
+  }
+
+  /// Remove an [Entry](struct.Entry.html) from the index.
+  ///
+  /// See [VaultContext::remove_index](struct.VaultContext.html#method.remove_index).
+  pub fn remove_index(&mut self, path: &str) {
+    self.context.remove_index(path);
+  }
+
+  /// Add an identity to the vault.
+  ///
+  /// Entries written afterwards in the transaction will be encrypted for
+  /// this identity.
+  ///
+  /// See [VaultContext::add_identity](struct.VaultContext.html#method.add_identity).
+  pub fn add_identity(&mut self, identity: &str) {
+    self.context.add_identity(identity);
+  }
+
+  /// Remove an identity from the vault.
+  ///
+  /// Entries written afterwards in the transaction will not be encrypted for
+  /// this identity anymore.
+  ///
+  /// See [VaultContext::remove_identity](struct.VaultContext.html#method.remove_identity).
+  pub fn remove_identity(&mut self, identity: &str) {
+    self.context.remove_identity(identity);
+  }
+
+  pub(crate) fn vault_mut(&mut self) -> &mut Vault {
+    &mut self.context.vault
+  }
+
+  /// Write the metadata and remove the packs of deleted entries and the
+  /// discarded blobs.
+  pub fn flush(mut self) -> Result<(), Box<dyn Error>> {
+    self.apply()
+  }
+
+  /// Flush the transaction and record it as a single git commit.
+  ///
+  /// # Arguments
+  ///
+  ///  * `message` - the commit message to be used for the commit
+  pub fn commit(mut self, message: &str) -> Result<(), Box<dyn Error>> {
+    self.apply()?;
+
+    git::commit(self.context, message)
+  }
+
+  fn apply(&mut self) -> Result<(), Box<dyn Error>> {
+    self.context.write_metadata()?;
+    self.applied = true;
+
+    for hash in &self.deleted {
+      self.context.remove_pack(hash)?;
+    }
+    for blob in &self.discarded {
+      self.context.remove_blob(blob)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl<'a> Drop for Transaction<'a> {
+  fn drop(&mut self) {
+    if !self.applied {
+      for blob in &self.written {
+        let _ = self.context.remove_blob(blob);
+      }
+    }
+
+    self.context.unlock();
+  }
+}
+
+impl<'a> Deref for Transaction<'a> {
+  type Target = VaultContext;
+
+  fn deref(&self) -> &VaultContext {
+    self.context
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use knox_testing::spec;
+
+  use crate::*;
+
+  #[test]
+  fn write_and_delete() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let mut entry = Entry::default();
+    entry.add_attribute("lorem", "ipsum");
+
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let hash = context.vault.get_index().get("foo/bar").expect("could not find index").clone();
+
+    let mut transaction = context.transaction();
+    transaction.write_entry("lorem/ipsum", &entry).expect("could not write entry");
+    transaction.write_entry("dolor/sit", &entry).expect("could not write entry");
+    transaction.delete_entry("foo/bar").expect("could not delete entry");
+
+    assert!(transaction.has_pack(&hash));
+    assert!(transaction.delete_entry("foo/bar").is_err());
+
+    transaction.flush().expect("could not flush transaction");
+
+    assert!(!context.has_pack(&hash));
+
+    let retrieved = VaultContext::open(tmp.path()).expect("could not open vault");
+
+    assert_eq!(retrieved.vault.get_index().len(), 2);
+    assert_eq!(retrieved.read_entry("lorem/ipsum").expect("could not read entry").get_attributes(), entry.get_attributes());
+    assert_eq!(retrieved.read_entry("dolor/sit").expect("could not read entry").get_attributes(), entry.get_attributes());
+    assert!(retrieved.read_entry("foo/bar").is_err());
+  }
+
+  #[test]
+  fn discard_blobs() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let blob = {
+      let mut transaction = context.transaction();
+      let attribute = transaction.write_blob(&b"lorem"[..]).expect("could not write blob");
+
+      assert!(transaction.has_pack(format!("_knox.blobs/{}", attribute.get_blob())));
+
+      attribute.get_blob().to_string()
+    };
+
+    assert!(!context.has_pack(format!("_knox.blobs/{}", blob)));
+
+    let mut transaction = context.transaction();
+    let attribute = transaction.write_blob(&b"lorem"[..]).expect("could not write blob");
+    let mut entry = Entry::default();
+    entry.attributes.insert("file".to_string(), attribute.clone());
+
+    transaction.write_entry("foo/bar", &entry).expect("could not write entry");
+    transaction.flush().expect("could not flush transaction");
+
+    assert!(context.has_pack(format!("_knox.blobs/{}", attribute.get_blob())));
+  }
+
+  #[test]
+  fn reencrypt_blobs() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    let attribute = context.write_blob(&b"lorem"[..]).expect("could not write blob");
+    let mut entry = Entry::default();
+    entry.attributes.insert("file".to_string(), attribute.clone());
+    context.write_entry("foo/bar", &entry).expect("could not write entry");
+
+    let mut transaction = context.transaction();
+    transaction.reencrypt_entry("foo/bar").expect("could not reencrypt entry");
+
+    assert!(transaction.has_pack(format!("_knox.blobs/{}", attribute.get_blob())));
+
+    transaction.flush().expect("could not flush transaction");
+
+    let entry = context.read_entry("foo/bar").expect("could not read entry");
+    let reencrypted = entry.get_attributes().get("file").expect("could not find attribute");
+    let mut content = Vec::new();
+    context.read_blob(reencrypted, &mut content).expect("could not read blob");
+
+    assert_ne!(reencrypted.get_blob(), attribute.get_blob());
+    assert!(!context.has_pack(format!("_knox.blobs/{}", attribute.get_blob())));
+    assert_eq!(content, b"lorem");
+  }
+
+  #[test]
+  fn write_unreadable() {
+    let tmp = spec::setup();
+    let mut context = crate::spec::get_test_vault(tmp.path()).expect("could not get vault");
+
+    context.write_entry("foo/bar", &Entry::default()).expect("could not write entry");
+
+    let hash = context.vault.get_index().get("foo/bar").expect("could not find index").clone();
+    std::fs::write(tmp.path().join(&hash), b"garbage").expect("could not corrupt pack");
+
+    assert!(context.write_entry("foo/bar", &Entry::default()).is_err());
+    assert!(context.update_entry("foo/bar", &Entry::default(), &Entry::default()).is_ok());
+  }
+}